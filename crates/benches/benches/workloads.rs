@@ -0,0 +1,23 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parse(c: &mut Criterion) {
+    let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    for workload in liquid_benches::workloads() {
+        c.bench_function(&format!("parse_{}", workload.name), |b| {
+            b.iter(|| parser.parse(workload.template).unwrap())
+        });
+    }
+}
+
+fn render(c: &mut Criterion) {
+    for workload in liquid_benches::workloads() {
+        let template = workload.parse();
+        let globals = (workload.globals)();
+        c.bench_function(&format!("render_{}", workload.name), |b| {
+            b.iter(|| template.render(&globals).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, parse, render);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+//! Reports the allocations of rendering each workload.
+//!
+//! ```console
+//! $ cargo run -p liquid-benches --release --features memory-profiling --bin allocations
+//! ```
+
+#[global_allocator]
+static ALLOCATOR: liquid::CountingAllocator = liquid::CountingAllocator;
+
+fn main() {
+    let options = liquid::RenderOptions::new();
+    for workload in liquid_benches::workloads() {
+        let template = workload.parse();
+        let globals = (workload.globals)();
+        let result = template
+            .render_with_result(&globals, &options)
+            .expect("workloads render");
+        let stats = result
+            .allocations()
+            .expect("CountingAllocator is installed");
+        println!(
+            "{:<24} {:>8} allocations {:>10} peak bytes",
+            format!("render_{}", workload.name),
+            stats.allocations,
+            stats.peak_bytes
+        );
+    }
+}
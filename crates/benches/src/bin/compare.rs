@@ -0,0 +1,96 @@
+//! Compares the latest `cargo bench` run against `baseline.json`.
+//!
+//! ```console
+//! $ cargo bench -p liquid-benches
+//! $ cargo run -p liquid-benches --bin compare -- [--threshold PERCENT] [--update]
+//! ```
+//!
+//! Exits with an error when a benchmark's mean is slower than the baseline by more than the
+//! threshold, 10% by default.  `--update` replaces the baseline with the latest run instead.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path;
+use std::process;
+
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/baseline.json");
+const CRITERION: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/criterion");
+
+/// Mean time of the latest run of `name`, in nanoseconds.
+fn latest_mean(name: &str) -> Result<f64, Box<dyn Error>> {
+    let path = path::Path::new(CRITERION)
+        .join(name)
+        .join("new")
+        .join("estimates.json");
+    let estimates: serde_json::Value = serde_json::from_reader(fs::File::open(&path)?)?;
+    estimates["mean"]["point_estimate"]
+        .as_f64()
+        .ok_or_else(|| format!("No mean in {}", path.display()).into())
+}
+
+fn run() -> Result<bool, Box<dyn Error>> {
+    let mut threshold = 10.0;
+    let mut update = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                threshold = args.next().ok_or("--threshold needs a value")?.parse()?;
+            }
+            "--update" => update = true,
+            _ => return Err(format!("Unexpected argument `{}`", arg).into()),
+        }
+    }
+
+    let mut names = Vec::new();
+    for workload in liquid_benches::workloads() {
+        names.push(format!("parse_{}", workload.name));
+        names.push(format!("render_{}", workload.name));
+    }
+
+    if update {
+        let mut baseline = BTreeMap::new();
+        for name in names {
+            let mean = latest_mean(&name)?;
+            baseline.insert(name, mean.round());
+        }
+        fs::write(BASELINE, serde_json::to_string_pretty(&baseline)? + "\n")?;
+        println!("Updated {}", BASELINE);
+        return Ok(true);
+    }
+
+    let baseline: BTreeMap<String, f64> = serde_json::from_reader(fs::File::open(BASELINE)?)?;
+    let mut ok = true;
+    for name in names {
+        let latest = latest_mean(&name)?;
+        match baseline.get(&name) {
+            Some(&base) => {
+                let change = (latest - base) / base * 100.0;
+                let status = if change > threshold {
+                    ok = false;
+                    "REGRESSED"
+                } else {
+                    "ok"
+                };
+                println!(
+                    "{:<24} {:>12.0} ns {:>+8.1}%  {}",
+                    name, latest, change, status
+                );
+            }
+            None => println!("{:<24} {:>12.0} ns  (no baseline)", name, latest),
+        }
+    }
+    Ok(ok)
+}
+
+fn main() {
+    match run() {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Representative workloads for benchmarking liquid.
+//!
+//! Each workload pairs a template with the data it is rendered with, so benchmarks and
+//! regression checks measure the same thing.  See `benches/workloads.rs`.
+
+/// A template and the data it is rendered with.
+pub struct Workload {
+    /// Name of the workload; benchmarks are named like `render_<name>` in `baseline.json`.
+    pub name: &'static str,
+    /// Source of the template.
+    pub template: &'static str,
+    /// Creates the globals to render with.
+    pub globals: fn() -> liquid::Object,
+}
+
+impl Workload {
+    /// Parses the template with the standard library.
+    pub fn parse(&self) -> liquid::Template {
+        liquid::ParserBuilder::with_stdlib()
+            .build()
+            .expect("should succeed without partials")
+            .parse(self.template)
+            .expect("workload templates are valid")
+    }
+}
+
+/// All workloads.
+pub fn workloads() -> Vec<Workload> {
+    vec![
+        Workload {
+            name: "text_page",
+            template: include_str!("../templates/page.liquid"),
+            globals: page_globals,
+        },
+        Workload {
+            name: "product_grid",
+            template: include_str!("../templates/grid.liquid"),
+            globals: grid_globals,
+        },
+        Workload {
+            name: "filter_email",
+            template: include_str!("../templates/email.liquid"),
+            globals: email_globals,
+        },
+    ]
+}
+
+/// A mostly static page with a few outputs.
+fn page_globals() -> liquid::Object {
+    liquid::object!({
+        "site": {
+            "title": "Liquid Blog",
+            "tagline": "Templates for Rust",
+            "year": 2020,
+        },
+        "page": {
+            "title": "Benchmarking templates",
+            "description": "How fast can a page render?",
+            "author": "Ann",
+            "date": "2020-02-02",
+        },
+    })
+}
+
+/// Nested loops over 50 products with 6 variants each.
+fn grid_globals() -> liquid::Object {
+    let products: Vec<_> = (0..50)
+        .map(|i| {
+            let variants: Vec<_> = (0..6)
+                .map(|v| {
+                    let size = ["XS", "S", "M", "L", "XL", "XXL"][v];
+                    liquid::object!({
+                        "size": size,
+                        "price": 10.0 + i as f64 + v as f64 * 0.5,
+                        "in_stock": (i + v) % 3 != 0,
+                    })
+                })
+                .collect();
+            liquid::object!({
+                "name": format!("Product {}", i),
+                "on_sale": i % 4 == 0,
+                "variants": variants,
+            })
+        })
+        .collect();
+    liquid::object!({ "products": products })
+}
+
+/// An order confirmation using many filters per output.
+fn email_globals() -> liquid::Object {
+    let items: Vec<_> = (0..10)
+        .map(|i| {
+            liquid::object!({
+                "title": format!("A rather long product title for item number {}", i),
+                "quantity": i % 3 + 1,
+                "price": 4.99 + i as f64,
+            })
+        })
+        .collect();
+    liquid::object!({
+        "customer": {
+            "name": "ann example",
+            "address": "  1 Main Street\nSpringfield\n  ",
+        },
+        "order": {
+            "number": "a1b2 c3",
+            "date": "2020-02-02 10:00:00 +0000",
+            "items": items,
+            "subtotal": 1234.5678,
+            "note": "<p>Please leave the parcel at the <b>back door</b> if nobody answers the bell.</p>",
+            "tags": ["Gift", "rush", "gift", "Fragile"],
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn workloads_render() {
+        for workload in workloads() {
+            let output = workload
+                .parse()
+                .render(&(workload.globals)())
+                .unwrap_or_else(|e| panic!("{} failed: {}", workload.name, e));
+            assert!(!output.is_empty(), "{} is empty", workload.name);
+        }
+    }
+}
@@ -6,9 +6,11 @@ use serde_yaml;
 
 use std::ffi;
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path;
+use std::process;
 
+use sha2::{Digest, Sha256};
 use structopt::StructOpt;
 
 #[derive(Copy, Clone, Debug, derive_more::Display, derive_more::From, derive_more::Constructor)]
@@ -33,7 +35,7 @@ fn build_context(path: &path::Path) -> Result<liquid::Object, Box<dyn std::error
     let extension = path.extension().unwrap_or_else(|| ffi::OsStr::new(""));
     let value = if extension == ffi::OsStr::new("yaml") {
         load_yaml(path)
-    } else if extension == ffi::OsStr::new("yaml") {
+    } else if extension == ffi::OsStr::new("json") {
         load_json(path)
     } else {
         Err(Error::new("Unsupported file type"))?
@@ -44,32 +46,399 @@ fn build_context(path: &path::Path) -> Result<liquid::Object, Box<dyn std::error
 
 #[derive(StructOpt)]
 struct Args {
+    /// Template to render, or `-` for stdin
     #[structopt(long, parse(from_os_str))]
-    input: std::path::PathBuf,
+    input: Option<std::path::PathBuf>,
 
+    /// File to write the output to, defaulting to stdout
     #[structopt(long, parse(from_os_str))]
     output: Option<std::path::PathBuf>,
 
+    /// Compare the output with the `--output` file instead of writing it, printing a diff and
+    /// failing if they differ
+    #[structopt(long, requires = "output")]
+    check: bool,
+
+    /// Data for the template; when repeated, later files are deep merged over earlier ones
+    #[structopt(long, parse(from_os_str), number_of_values = 1)]
+    context: Vec<std::path::PathBuf>,
+
+    /// Template to wrap the output in, which it can place with `{{ content }}`
+    #[structopt(long, parse(from_os_str))]
+    layout: Option<std::path::PathBuf>,
+
+    /// How to print warnings and errors: `human`, or `json` for one object per line
+    #[structopt(long, default_value = "human")]
+    message_format: MessageFormat,
+
+    /// Keep rendering after an element fails, reporting its error on stderr
+    #[structopt(long)]
+    lax: bool,
+
+    /// Fall back to matching variables ignoring case, reporting each fallback on stderr
+    #[structopt(long)]
+    case_insensitive: bool,
+
+    /// Reject templates longer than this many bytes
+    #[structopt(long)]
+    max_template_size: Option<usize>,
+
+    /// Reject templates with more than this many text runs, outputs, tags and blocks
+    #[structopt(long)]
+    max_nodes: Option<usize>,
+
+    /// Reject templates with blocks nested deeper than this
+    #[structopt(long)]
+    max_nesting: Option<usize>,
+
+    /// Fail once a tag runs more than the given number of times, like `include=50`
+    #[structopt(long, parse(try_from_str = parse_tag_limit))]
+    tag_limit: Vec<(String, usize)>,
+
+    /// Reuse the output of an earlier run when the files, flags and liquid version are unchanged,
+    /// skipping parsing and rendering
     #[structopt(long, parse(from_os_str))]
-    context: Option<std::path::PathBuf>,
+    cache_dir: Option<std::path::PathBuf>,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Evaluate an expression, like the contents of `{{ }}`, and print the resulting value
+    Eval {
+        expression: String,
+
+        /// Data for the expression; when repeated, later files are deep merged over earlier ones
+        #[structopt(long, parse(from_os_str), number_of_values = 1)]
+        context: Vec<std::path::PathBuf>,
+
+        /// Print the value as JSON
+        #[structopt(long)]
+        json: bool,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum MessageFormat {
+    Human,
+    Json,
+}
+
+impl std::str::FromStr for MessageFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(Error::new("Expected `human` or `json`")),
+        }
+    }
+}
+
+/// Reports warnings and errors about a template on stderr.
+struct Diagnostics<'a> {
+    format: MessageFormat,
+    file: &'a path::Path,
+}
+
+impl<'a> Diagnostics<'a> {
+    fn warning(&self, message: &str) {
+        match self.format {
+            MessageFormat::Human => eprintln!("warning: {}", message),
+            MessageFormat::Json => self.emit("warning", "warning", message, None, message),
+        }
+    }
+
+    fn error(&self, code: &str, error: &liquid::Error) {
+        match self.format {
+            MessageFormat::Human => eprintln!("error: {}", error),
+            MessageFormat::Json => self.emit(
+                "error",
+                code,
+                error.message(),
+                error.context_value("position"),
+                &error.to_string(),
+            ),
+        }
+    }
+
+    fn emit(&self, level: &str, code: &str, message: &str, position: Option<&str>, rendered: &str) {
+        let span = position.and_then(|position| {
+            let mut parts = position.splitn(2, ':');
+            let line: usize = parts.next()?.parse().ok()?;
+            let column: usize = parts.next()?.parse().ok()?;
+            Some(serde_json::json!({"line": line, "column": column}))
+        });
+        let diagnostic = serde_json::json!({
+            "level": level,
+            "code": code,
+            "message": message,
+            "file": self.file.display().to_string(),
+            "span": span,
+            "rendered": rendered,
+        });
+        eprintln!("{}", diagnostic);
+    }
+}
+
+fn parse_tag_limit(arg: &str) -> Result<(String, usize), Box<dyn std::error::Error>> {
+    let mut parts = arg.splitn(2, '=');
+    let name = parts.next().unwrap_or_default();
+    let max = parts
+        .next()
+        .ok_or_else(|| Error::new("Expected NAME=MAX"))?
+        .parse()?;
+    Ok((name.to_owned(), max))
+}
+
+fn load_context(
+    paths: &[std::path::PathBuf],
+) -> Result<liquid::Object, Box<dyn std::error::Error>> {
+    let mut data = liquid::Object::new();
+    for path in paths {
+        data.deep_merge(build_context(path)?);
+    }
+    Ok(data)
+}
+
+fn eval(
+    parser: &liquid::Parser,
+    expression: &str,
+    context: &[std::path::PathBuf],
+    json: bool,
+) -> Result<i32, Box<dyn std::error::Error>> {
+    let expression = parser.parse_expression(expression)?;
+    let data = load_context(context)?;
+    let value = expression.evaluate(&data)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&value)?);
+    } else {
+        println!("{}", liquid::ValueView::source(&value));
+    }
+
+    Ok(0)
+}
+
+/// Parses the template at `path`, or from stdin for `-`.
+///
+/// Reading stdin lets the CLI be used without file system access, like under WASI.
+fn parse_file(
+    parser: &liquid::Parser,
+    path: &path::Path,
+) -> Result<liquid::Template, liquid::Error> {
+    if path != path::Path::new("-") {
+        return parser.parse_file(path);
+    }
+    let mut text = String::new();
+    std::io::stdin().read_to_string(&mut text).map_err(|e| {
+        liquid::Error::with_msg("Cannot read stdin").context("cause", e.to_string())
+    })?;
+    parser.parse(&text)
+}
+
+/// Renders the file of `diagnostics`, or returns `None` if it failed and the failure was
+/// reported as a diagnostic.
+fn render_file(
+    parser: &liquid::Parser,
+    data: &liquid::Object,
+    options: &liquid::RenderOptions,
+    diagnostics: &Diagnostics<'_>,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let json = diagnostics.format == MessageFormat::Json;
+    let template = match parse_file(parser, diagnostics.file) {
+        Ok(template) => template,
+        Err(error) if json => {
+            diagnostics.error("parse", &error);
+            return Ok(None);
+        }
+        Err(error) => return Err(error.into()),
+    };
+    let result = match template.render_with_result(data, options) {
+        Ok(result) => result,
+        Err(error) if json => {
+            diagnostics.error("render", &error);
+            return Ok(None);
+        }
+        Err(error) => return Err(error.into()),
+    };
+    for warning in result.warnings() {
+        diagnostics.warning(warning);
+    }
+    for error in result.errors() {
+        diagnostics.error("render", error);
+    }
+    let (output, _assigned) = result.into_parts();
+    Ok(Some(output))
+}
+
+/// Compares `output` with the contents of `path`, printing a unified diff if they differ.
+fn check(path: &path::Path, output: &str) -> Result<i32, Box<dyn std::error::Error>> {
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    if expected == output {
+        return Ok(0);
+    }
+
+    let name = path.display().to_string();
+    let diff = similar::TextDiff::from_lines(expected.as_str(), output)
+        .unified_diff()
+        .header(&name, &format!("{} (rendered)", name))
+        .to_string();
+    print!("{}", diff);
+    Ok(1)
+}
+
+/// Path of the cached output of a run, named by the SHA-256 of the liquid version, the flags and
+/// the files read.
+///
+/// This caches rendered output rather than parsed templates.  Templates reading the clock, like
+/// with `'now' | date`, keep the output of the cached run, and warnings are only reported when
+/// rendering.
+fn cache_path(
+    dir: &path::Path,
+    args: &Args,
+    input: &path::Path,
+) -> Result<path::PathBuf, Box<dyn std::error::Error>> {
+    // Each field is prefixed with its length, so different fields can't hash the same.
+    fn update(hasher: &mut Sha256, bytes: &[u8]) {
+        hasher.input(&(bytes.len() as u64).to_le_bytes());
+        hasher.input(bytes);
+    }
+
+    let mut hasher = Sha256::new();
+    update(&mut hasher, env!("CARGO_PKG_VERSION").as_bytes());
+    let flags = format!(
+        "{:?}",
+        (
+            args.lax,
+            args.case_insensitive,
+            args.max_template_size,
+            args.max_nodes,
+            args.max_nesting,
+            &args.tag_limit,
+        )
+    );
+    update(&mut hasher, flags.as_bytes());
+    let files = std::iter::once(input)
+        .chain(args.layout.as_ref().map(|path| path.as_path()))
+        .chain(args.context.iter().map(|path| path.as_path()));
+    for file in files {
+        update(&mut hasher, file.to_string_lossy().as_bytes());
+        update(&mut hasher, &fs::read(file)?);
+    }
+    Ok(dir.join(format!("{:x}", hasher.result())))
+}
+
+/// Writes `output` to `path` through a temporary file, so concurrent runs never read a partial
+/// cache entry.
+fn write_cache(path: &path::Path, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp = path.with_extension(format!("{}.tmp", process::id()));
+    fs::write(&temp, output)?;
+    fs::rename(&temp, path).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        e.into()
+    })
+}
+
+/// Renders `input`, then the layout around it, or returns `None` if a failure was reported as a
+/// diagnostic.
+fn render(
+    parser: &liquid::Parser,
+    args: &Args,
+    input: &path::Path,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let mut data = load_context(&args.context)?;
+    let options = args.tag_limit.iter().fold(
+        liquid::RenderOptions::new()
+            .lax(args.lax)
+            .case_insensitive_lookup(args.case_insensitive),
+        |options, (name, max)| options.tag_limit(name.as_str(), *max),
+    );
+    let diagnostics = Diagnostics {
+        format: args.message_format,
+        file: input,
+    };
+    let mut output = match render_file(parser, &data, &options, &diagnostics)? {
+        Some(output) => output,
+        None => return Ok(None),
+    };
+    if let Some(layout) = args.layout.as_ref() {
+        let diagnostics = Diagnostics {
+            format: args.message_format,
+            file: layout,
+        };
+        data.insert("content".into(), liquid::value::Value::scalar(output));
+        output = match render_file(parser, &data, &options, &diagnostics)? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+    }
+    Ok(Some(output))
 }
 
 fn run() -> Result<i32, Box<dyn std::error::Error>> {
     let args = Args::from_args();
 
-    let parser = liquid::ParserBuilder::with_stdlib()
-        .build()
-        .expect("should succeed without partials");
-    let template = parser.parse_file(&args.input)?;
+    let mut parser = liquid::ParserBuilder::with_stdlib();
+    if let Some(bytes) = args.max_template_size {
+        parser = parser.max_template_size(bytes);
+    }
+    if let Some(nodes) = args.max_nodes {
+        parser = parser.max_nodes(nodes);
+    }
+    if let Some(depth) = args.max_nesting {
+        parser = parser.max_nesting(depth);
+    }
+    if !args.tag_limit.is_empty() {
+        parser = parser.count_tags(true);
+    }
+    let parser = parser.build().expect("should succeed without partials");
+
+    if let Some(Command::Eval {
+        ref expression,
+        ref context,
+        json,
+    }) = args.command
+    {
+        return eval(&parser, expression, context, json);
+    }
 
-    let data = args
-        .context
+    let input = args
+        .input
+        .clone()
+        .ok_or_else(|| Error::new("--input is required"))?;
+    let cache = match args.cache_dir {
+        Some(ref dir) if input != path::Path::new("-") => Some(cache_path(dir, &args, &input)?),
+        _ => None,
+    };
+    let cached = cache
         .as_ref()
-        .map(|p| build_context(p.as_path()))
-        .map_or(Ok(None), |r| r.map(Some))?
-        .unwrap_or_else(liquid::Object::new);
-    let output = template.render(&data)?;
+        .and_then(|path| fs::read_to_string(path).ok());
+    let output = match cached {
+        Some(output) => output,
+        None => {
+            let output = match render(&parser, &args, &input)? {
+                Some(output) => output,
+                None => return Ok(1),
+            };
+            if let Some(ref path) = cache {
+                write_cache(path, &output)?;
+            }
+            output
+        }
+    };
+
     match args.output {
+        Some(path) if args.check => return check(&path, &output),
         Some(path) => {
             let mut out = fs::File::create(path)?;
             out.write_all(output.as_bytes())?;
@@ -86,3 +455,49 @@ fn main() {
     let code = run().unwrap();
     std::process::exit(code);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_dir(name: &str) -> path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("liquid-bin-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn cache_path_follows_contents_and_flags() {
+        let dir = temp_dir("cache-path");
+        let input = dir.join("input.liquid");
+        let args = |flags: &[&str]| {
+            let mut argv = vec!["liquid", "--input", input.to_str().unwrap()];
+            argv.extend(flags);
+            Args::from_iter(argv)
+        };
+
+        fs::write(&input, "{{ 1 }}").unwrap();
+        let first = cache_path(&dir, &args(&[]), &input).unwrap();
+        assert_eq!(first, cache_path(&dir, &args(&[]), &input).unwrap());
+        assert_eq!(first.file_name().unwrap().len(), 64);
+        assert_ne!(first, cache_path(&dir, &args(&["--lax"]), &input).unwrap());
+
+        fs::write(&input, "{{ 2 }}").unwrap();
+        assert_ne!(first, cache_path(&dir, &args(&[]), &input).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_cache_replaces_entries() {
+        let dir = temp_dir("write-cache");
+        let path = dir.join("entries").join("0123");
+
+        write_cache(&path, "first").unwrap();
+        write_cache(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
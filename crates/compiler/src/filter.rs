@@ -143,6 +143,22 @@ pub struct FilterArguments<'a> {
 pub trait Filter: Send + Sync + Debug + Display {
     // This will evaluate the expressions and evaluate the filter.
     fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime) -> Result<Value>;
+
+    /// The encoding the output is already in, like `"html"` for `escape`.
+    ///
+    /// When this is the last filter of `{{ }}` and the render's `OutputEncoder` accepts the
+    /// encoding, the output is written as is.  Defaults to `None`.
+    fn output_encoding(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Whether an undefined input is passed as `nil`, like for `default`.
+    ///
+    /// When this is the first filter of a chain, an undefined variable doesn't fail the render
+    /// under strict lookups.  Defaults to `false`.
+    fn accepts_undefined(&self) -> bool {
+        false
+    }
 }
 
 /// A trait to register a new filter in the `liquid::Parser`.
@@ -4,11 +4,11 @@ use std::io::Write;
 use itertools;
 
 use super::Filter;
-use liquid_error::{Result, ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_error::{Error, Result, ResultLiquidExt, ResultLiquidReplaceExt};
 use liquid_interpreter::Expression;
 use liquid_interpreter::Renderable;
 use liquid_interpreter::Runtime;
-use liquid_value::{ValueCow, ValueView};
+use liquid_value::{DisplayPolicy, ValueCow, ValueView};
 
 /// A `Value` expression.
 #[derive(Debug)]
@@ -23,10 +23,20 @@ impl FilterChain {
         Self { entry, filters }
     }
 
+    /// Split into the un-evaluated entry and the filters applied to it.
+    pub fn into_parts(self) -> (Expression, Vec<Box<dyn Filter>>) {
+        (self.entry, self.filters)
+    }
+
     /// Process `Value` expression within `runtime`'s stack.
     pub fn evaluate<'s>(&'s self, runtime: &'s Runtime) -> Result<ValueCow<'s>> {
         // take either the provided value or the value from the provided variable
-        let mut entry = self.entry.evaluate(runtime)?;
+        let mut entry = match self.filters.first() {
+            Some(filter) if filter.accepts_undefined() => {
+                self.entry.lookup(runtime)?.unwrap_or_nil()
+            }
+            _ => self.entry.evaluate(runtime)?,
+        };
 
         // apply all specified filters
         for filter in &self.filters {
@@ -59,7 +69,40 @@ impl fmt::Display for FilterChain {
 impl Renderable for FilterChain {
     fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime) -> Result<()> {
         let entry = self.evaluate(runtime)?;
-        write!(writer, "{}", entry.render()).replace("Failed to render")?;
+        if let Some(scalar) = entry.as_scalar() {
+            if scalar.as_bytes().is_some() {
+                return Error::with_msg("Cannot render bytes")
+                    .context(
+                        "cause",
+                        "Bytes must be encoded, like with `base64_encode` or `hex`",
+                    )
+                    .context("expression", self.to_string())
+                    .into_err();
+            }
+        }
+        let policy = runtime.get_register::<DisplayPolicy>();
+        // Output already encoded by its last filter, like `escape`, is written as is.
+        let encoded = self
+            .filters
+            .last()
+            .and_then(|filter| filter.output_encoding());
+        let encoder = runtime.output_encoder().filter(|encoder| match encoded {
+            Some(encoding) => !encoder.accepts(encoding),
+            None => true,
+        });
+        match (encoder, policy) {
+            (Some(encoder), Some(policy)) => {
+                let text = policy.render(entry.as_view()).to_string();
+                writer.write_all(encoder.encode(&text).as_bytes())
+            }
+            (Some(encoder), None) => {
+                let text = entry.to_kstr();
+                writer.write_all(encoder.encode(text.as_str()).as_bytes())
+            }
+            (None, Some(policy)) => write!(writer, "{}", policy.render(entry.as_view())),
+            (None, None) => write!(writer, "{}", entry.render()),
+        }
+        .replace("Failed to render")?;
         Ok(())
     }
 }
@@ -1,5 +1,7 @@
+use super::LimitObserver;
 use super::ParseBlock;
 use super::ParseFilter;
+use super::ParseLimits;
 use super::ParseTag;
 use super::PluginRegistry;
 
@@ -8,6 +10,25 @@ pub struct Language {
     pub blocks: PluginRegistry<Box<dyn ParseBlock>>,
     pub tags: PluginRegistry<Box<dyn ParseTag>>,
     pub filters: PluginRegistry<Box<dyn ParseFilter>>,
+    /// Accept `&&`, `||`, `eq`, `ne`, `lt`, `gt`, `le` and `ge` as aliases of the standard
+    /// operators.  Disabled by default for parity with Shopify's Liquid.
+    pub keyword_aliases: bool,
+    /// Expand `#{value}` in double-quoted string literals.  Disabled by default, as it changes
+    /// the output of existing literals.
+    pub interpolation: bool,
+    /// Parse filters missing from `filters` into calls to the runtime's `MissingFilterHandler`
+    /// instead of failing.  Disabled by default.
+    pub defer_unknown_filters: bool,
+    /// Wrap tags and blocks so renders can limit how often they run, see
+    /// `Runtime::count_tag`.  Disabled by default, as it adds work to every run of a tag.
+    pub count_tags: bool,
+    /// Wrap tags, blocks and outputs so renders can record which run, see `Runtime::cover`.
+    /// Disabled by default, as it adds work to every run of an element.
+    pub record_coverage: bool,
+    /// Budgets that templates must fit within to be parsed.
+    pub limits: ParseLimits,
+    /// Told about templates near `limits`, see `ParseLimits::warn_percent`.
+    pub limit_observer: Option<LimitObserver>,
     non_exhaustive: (),
 }
 
@@ -23,6 +44,13 @@ impl Default for Language {
             blocks: Default::default(),
             tags: Default::default(),
             filters: Default::default(),
+            keyword_aliases: false,
+            interpolation: false,
+            defer_unknown_filters: false,
+            count_tags: false,
+            record_coverage: false,
+            limits: Default::default(),
+            limit_observer: None,
             non_exhaustive: Default::default(),
         }
     }
@@ -12,8 +12,10 @@ mod filter_chain;
 mod lang;
 mod parser;
 mod registry;
+mod report;
 mod tag;
 mod text;
+mod tokenize;
 
 pub use crate::block::*;
 pub use crate::filter::*;
@@ -21,6 +23,7 @@ pub use crate::filter_chain::*;
 pub use crate::lang::*;
 pub use crate::parser::*;
 pub use crate::registry::*;
+pub use crate::report::*;
 pub use crate::tag::*;
-
-use crate::text::Text;
+pub use crate::text::Text;
+pub use crate::tokenize::*;
@@ -4,13 +4,16 @@
 //! but should be ignored for simple usage.
 
 use std;
+use std::fmt;
+use std::io::Write;
 
 use itertools;
 use liquid_error::{Error, Result, ResultLiquidExt};
 use liquid_interpreter::Expression;
 use liquid_interpreter::Renderable;
+use liquid_interpreter::Runtime;
 use liquid_interpreter::Variable;
-use liquid_value::Value;
+use liquid_value::{Object, Value, ValueView};
 
 use super::Language;
 use super::Text;
@@ -18,7 +21,7 @@ use super::{Filter, FilterArguments, FilterChain};
 
 use pest::Parser;
 
-mod pest {
+pub(crate) mod pest {
     #[derive(Parser)]
     #[grammar = "grammar.pest"]
     pub struct LiquidParser;
@@ -42,9 +45,15 @@ fn convert_pest_error(err: ::pest::error::Error<Rule>) -> Error {
         Rule::Assign => "\"=\"".to_string(),
         Rule::Comma => "\",\"".to_string(),
         Rule::Colon => "\":\"".to_string(),
+        Rule::And => "\"&&\"".to_string(),
+        Rule::Or => "\"||\"".to_string(),
         other => format!("{:?}", other),
     });
-    Error::with_msg(err.to_string())
+    let (line, column) = match err.line_col {
+        ::pest::error::LineColLocation::Pos(pos) => pos,
+        ::pest::error::LineColLocation::Span(start, _) => start,
+    };
+    Error::with_msg(err.to_string()).context("position", format!("{}:{}", line, column))
 }
 
 /// Generates a `liquid::Error` with the given message pointing to
@@ -57,8 +66,20 @@ fn error_from_pair(pair: Pair, msg: String) -> Error {
     convert_pest_error(pest_error)
 }
 
+/// Whether `text` has any liquid tags or outputs.
+///
+/// This is a cheap scan for the delimiters, letting text without liquid skip parsing.  It does
+/// not validate the liquid found.
+pub fn contains_liquid(text: &str) -> bool {
+    text.contains("{{") || text.contains("{%")
+}
+
 /// Parses the provided &str into a number of Renderable items.
+///
+/// Fails if the template doesn't fit within `options.limits`.
 pub fn parse(text: &str, options: &Language) -> Result<Vec<Box<dyn Renderable>>> {
+    options.limits.check(text, options)?;
+
     let mut liquid = LiquidParser::parse(Rule::LaxLiquidFile, text)
         .expect("Parsing with Rule::LaxLiquidFile should not raise errors, but InvalidLiquid tokens instead.")
         .next()
@@ -81,6 +102,38 @@ pub fn parse(text: &str, options: &Language) -> Result<Vec<Box<dyn Renderable>>>
     Ok(renderables)
 }
 
+/// Parses a standalone expression, like the contents of `{{ }}`.
+pub fn parse_expression(text: &str, options: &Language) -> Result<FilterChain> {
+    let expression = LiquidParser::parse(Rule::LiquidExpression, text)
+        .map_err(convert_pest_error)?
+        .next()
+        .expect("Unwrapping LiquidExpression to access the expression.")
+        .into_inner()
+        .next()
+        .expect("An expression is made of one ExpressionInner.")
+        .into_inner()
+        .next()
+        .expect("ExpressionInner is made of one FilterChain.");
+    parse_filter_chain(expression, options)
+}
+
+/// Tokenizes standalone tag arguments, like the contents of `{% if %}` after the tag name.
+pub fn parse_tag_arguments(text: &str) -> Result<TagTokenIter<'_>> {
+    let arguments = LiquidParser::parse(Rule::LiquidTagArguments, text)
+        .map_err(convert_pest_error)?
+        .next()
+        .expect("Unwrapping LiquidTagArguments to access the arguments.")
+        .into_inner()
+        .next()
+        .expect("Tag arguments are made of one TagArguments.");
+    let position = arguments.as_span().start_pos();
+    Ok(TagTokenIter {
+        iter: Box::new(arguments.into_inner().map(TagToken::from)),
+        position,
+        interpolate: false,
+    })
+}
+
 /// Parses a `Scalar` from a `Pair` with a literal value.
 /// This `Pair` must be `Rule::Literal`.
 fn parse_literal(literal: Pair) -> Value {
@@ -127,7 +180,9 @@ fn parse_literal(literal: Pair) -> Value {
 
 /// Parses a `Variable` from a `Pair` with a variable.
 /// This `Pair` must be `Rule::Variable`.
-fn parse_variable(variable: Pair) -> Variable {
+///
+/// `interpolate` is passed on to `parse_value` for indexes.
+fn parse_variable(variable: Pair, interpolate: bool) -> Variable {
     if variable.as_rule() != Rule::Variable {
         panic!("Expected variable.");
     }
@@ -143,7 +198,7 @@ fn parse_variable(variable: Pair) -> Variable {
 
     let indexes = indexes.map(|index| match index.as_rule() {
         Rule::Identifier => Expression::with_literal(index.as_str().to_owned()),
-        Rule::Value => parse_value(index),
+        Rule::Value => parse_value(index, interpolate),
         _ => unreachable!(),
     });
 
@@ -157,7 +212,10 @@ fn parse_variable(variable: Pair) -> Variable {
 /// In this runtime, value refers to either a literal value or a variable.
 ///
 /// This `Pair` must be `Rule::Value`.
-fn parse_value(value: Pair) -> Expression {
+///
+/// Double-quoted literals containing `#{value}` become interpolations when `interpolate` is set,
+/// see `Language::interpolation`.
+fn parse_value(value: Pair, interpolate: bool) -> Expression {
     if value.as_rule() != Rule::Value {
         panic!("Expected value.");
     }
@@ -165,12 +223,85 @@ fn parse_value(value: Pair) -> Expression {
     let value = value.into_inner().next().expect("Get inside the value.");
 
     match value.as_rule() {
+        Rule::Literal if interpolate => parse_interpolation(value.clone())
+            .unwrap_or_else(|| Expression::Literal(parse_literal(value))),
         Rule::Literal => Expression::Literal(parse_literal(value)),
-        Rule::Variable => Expression::Variable(parse_variable(value)),
+        Rule::Variable => Expression::Variable(parse_variable(value, interpolate)),
+        Rule::ArrayLiteral => Expression::with_array(
+            value
+                .into_inner()
+                .map(|item| parse_value(item, interpolate))
+                .collect(),
+        ),
+        Rule::ObjectLiteral => Expression::with_object(
+            value
+                .into_inner()
+                .map(|entry| {
+                    let mut entry = entry.into_inner();
+                    let key = entry.next().expect("An entry starts with a key.").as_str();
+                    let key = key[1..key.len() - 1].to_owned();
+                    let value = parse_value(
+                        entry.next().expect("An entry ends with a value."),
+                        interpolate,
+                    );
+                    (key.into(), value)
+                })
+                .collect(),
+        ),
         _ => unreachable!(),
     }
 }
 
+/// Parses a double-quoted `Rule::Literal` containing `#{value}` interpolations.
+///
+/// Returns `None` for any other literal.  Text inside `#{}` that is not a value is kept verbatim.
+fn parse_interpolation(literal: Pair) -> Option<Expression> {
+    let literal = literal
+        .into_inner()
+        .next()
+        .expect("Get into the rule inside literal.");
+    if literal.as_rule() != Rule::StringLiteral || !literal.as_str().starts_with('"') {
+        return None;
+    }
+    let literal = literal.as_str();
+    let mut rest = &literal[1..literal.len() - 1];
+
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    while let Some(start) = rest.find("#{") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let inner = rest[start + 2..end].trim();
+        let value = LiquidParser::parse(Rule::Value, inner)
+            .ok()
+            .and_then(|mut pairs| pairs.next())
+            .filter(|pair| pair.as_str().len() == inner.len());
+        match value {
+            Some(value) => {
+                text.push_str(&rest[..start]);
+                if !text.is_empty() {
+                    parts.push(Expression::with_literal(text.clone()));
+                    text.clear();
+                }
+                parts.push(parse_value(value, true));
+            }
+            None => text.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    if parts.is_empty() {
+        return None;
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(Expression::with_literal(text));
+    }
+    Some(Expression::Interpolation(parts))
+}
+
 /// Parses a `FilterCall` from a `Pair` with a filter.
 /// This `Pair` must be `Rule::Filter`.
 fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
@@ -189,34 +320,50 @@ fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
         match arg.as_rule() {
             Rule::PositionalFilterArgument => {
                 let value = arg.into_inner().next().expect("Rule ensures value.");
-                let value = parse_value(value);
+                let value = parse_value(value, options.interpolation);
                 positional_args.push(value);
             }
             Rule::KeywordFilterArgument => {
                 let mut arg = arg.into_inner();
                 let key = arg.next().expect("Rule ensures identifier.").as_str();
                 let value = arg.next().expect("Rule ensures value.");
-                let value = parse_value(value);
+                let value = parse_value(value, options.interpolation);
                 keyword_args.push((key, value));
             }
             _ => unreachable!(),
         }
     }
 
+    let f = match options.filters.get(name) {
+        Some(f) => f,
+        None if options.defer_unknown_filters => {
+            let keyword = keyword_args
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect();
+            return Ok(Box::new(MissingFilter {
+                name: name.to_owned(),
+                source: filter_str.to_owned(),
+                positional: positional_args,
+                keyword,
+            }));
+        }
+        None => {
+            let mut available: Vec<_> = options.filters.plugin_names().collect();
+            available.sort_unstable();
+            let available = itertools::join(available, ", ");
+            return Error::with_msg("Unknown filter")
+                .context("requested filter", name.to_owned())
+                .context("available filters", available)
+                .into_err();
+        }
+    };
+
     let args = FilterArguments {
         positional: Box::new(positional_args.into_iter()),
         keyword: Box::new(keyword_args.into_iter()),
     };
 
-    let f = options.filters.get(name).ok_or_else(|| {
-        let mut available: Vec<_> = options.filters.plugin_names().collect();
-        available.sort_unstable();
-        let available = itertools::join(available, ", ");
-        Error::with_msg("Unknown filter")
-            .context("requested filter", name.to_owned())
-            .context("available filters", available)
-    })?;
-
     let f = f
         .parse(args)
         .trace("Filter parsing error")
@@ -226,6 +373,39 @@ fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
     Ok(f)
 }
 
+/// A filter unknown to the parser, resolved by the runtime's `MissingFilterHandler`.
+#[derive(Debug)]
+struct MissingFilter {
+    name: String,
+    source: String,
+    positional: Vec<Expression>,
+    keyword: Vec<(String, Expression)>,
+}
+
+impl fmt::Display for MissingFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl Filter for MissingFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime) -> Result<Value> {
+        let handler = runtime.missing_filter_handler().ok_or_else(|| {
+            Error::with_msg("Unknown filter").context("requested filter", self.name.clone())
+        })?;
+        let positional = self
+            .positional
+            .iter()
+            .map(|arg| arg.evaluate(runtime).map(|value| value.into_owned()))
+            .collect::<Result<Vec<_>>>()?;
+        let mut keyword = Object::with_capacity(self.keyword.len());
+        for (key, arg) in self.keyword.iter() {
+            keyword.insert(key.clone().into(), arg.evaluate(runtime)?.into_owned());
+        }
+        handler.call(&self.name, input, &positional, &keyword)
+    }
+}
+
 /// Parses a `FilterChain` from a `Pair` with a filter chain.
 /// This `Pair` must be `Rule::FilterChain`.
 fn parse_filter_chain(chain: Pair, options: &Language) -> Result<FilterChain> {
@@ -238,6 +418,7 @@ fn parse_filter_chain(chain: Pair, options: &Language) -> Result<FilterChain> {
         chain
             .next()
             .expect("A filterchain always has starts by a value."),
+        options.interpolation,
     );
     let filters: Result<Vec<_>> = chain.map(|f| parse_filter(f, options)).collect();
     let filters = filters?;
@@ -534,16 +715,17 @@ impl<'a> Tag<'a> {
         next_elements: &mut dyn Iterator<Item = Pair>,
         options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let (name, tokens) = (self.name, self.tokens);
+        let (name, mut tokens) = (self.name, self.tokens);
+        tokens.interpolate = options.interpolation;
         let position = name.as_span();
+        let (line, column) = position.start_pos().line_col();
         let name = name.as_str();
 
-        if let Some(plugin) = options.tags.get(name) {
-            plugin.parse(tokens, options)
+        let renderable = if let Some(plugin) = options.tags.get(name) {
+            plugin.parse(tokens, options)?
         } else if let Some(plugin) = options.blocks.get(name) {
             let block = TagBlock::new(name, next_elements);
-            let renderables = plugin.parse(tokens, block, options)?;
-            Ok(renderables)
+            plugin.parse(tokens, block, options)?
         } else {
             let pest_error = ::pest::error::Error::new_from_span(
                 ::pest::error::ErrorVariant::CustomError {
@@ -561,8 +743,66 @@ impl<'a> Tag<'a> {
                 .context("requested", name.to_owned())
                 .context("available tags", all_tags)
                 .context("available blocks", all_blocks);
-            Err(error)
-        }
+            return Err(error);
+        };
+        let position = format!("{}:{}", line, column);
+        let renderable: Box<dyn Renderable> = if options.count_tags {
+            Box::new(CountedTag {
+                name: name.to_owned(),
+                position: position.clone(),
+                renderable,
+            })
+        } else {
+            renderable
+        };
+        Ok(cover(renderable, position, options))
+    }
+}
+
+/// A tag or block that counts its runs against the runtime's tag limits.
+#[derive(Debug)]
+struct CountedTag {
+    name: String,
+    position: String,
+    renderable: Box<dyn Renderable>,
+}
+
+impl Renderable for CountedTag {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        runtime
+            .count_tag(&self.name)
+            .map_err(|error| error.context("position", self.position.clone()))?;
+        self.renderable.render_to(writer, runtime)
+    }
+}
+
+/// Wrap `renderable` to record its runs, when the parser records coverage.
+fn cover(
+    renderable: Box<dyn Renderable>,
+    position: String,
+    options: &Language,
+) -> Box<dyn Renderable> {
+    if options.record_coverage {
+        Box::new(Covered {
+            position,
+            renderable,
+        })
+    } else {
+        renderable
+    }
+}
+
+/// A tag, block or output that records its runs in the runtime's coverage.
+#[derive(Debug)]
+struct Covered {
+    position: String,
+    renderable: Box<dyn Renderable>,
+}
+
+impl Renderable for Covered {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        runtime.cover(&self.position);
+        self.renderable.render_to(writer, runtime)
     }
 }
 
@@ -583,6 +823,7 @@ impl<'a> From<Pair<'a>> for Exp<'a> {
 impl<'a> Exp<'a> {
     /// Parses the expression just as if it weren't inside any block.
     pub fn parse(self, options: &Language) -> Result<Box<dyn Renderable>> {
+        let (line, column) = self.element.as_span().start_pos().line_col();
         let filter_chain = self
             .element
             .into_inner()
@@ -593,7 +834,11 @@ impl<'a> Exp<'a> {
             .expect("An expression consists of one filterchain.");
 
         let filter_chain = parse_filter_chain(filter_chain, options)?;
-        Ok(Box::new(filter_chain))
+        Ok(cover(
+            Box::new(filter_chain),
+            format!("{}:{}", line, column),
+            options,
+        ))
     }
 
     /// Returns the expression as a str.
@@ -741,12 +986,15 @@ impl<'a> BlockElement<'a> {
 pub struct TagTokenIter<'a> {
     iter: Box<dyn Iterator<Item = TagToken<'a>> + 'a>,
     position: ::pest::Position<'a>,
+    interpolate: bool,
 }
 impl<'a> Iterator for TagTokenIter<'a> {
     type Item = TagToken<'a>;
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|next| {
+        let interpolate = self.interpolate;
+        self.iter.next().map(|mut next| {
             self.position = next.token.as_span().end_pos();
+            next.interpolate = interpolate;
             next
         })
     }
@@ -756,6 +1004,7 @@ impl<'a> TagTokenIter<'a> {
         TagTokenIter {
             iter: Box::new(tokens.map(TagToken::from)),
             position: name.as_span().end_pos(),
+            interpolate: false,
         }
     }
 
@@ -815,6 +1064,8 @@ impl<'a, T> TryMatchToken<'a, T> {
 pub struct TagToken<'a> {
     token: Pair<'a>,
     expected: Vec<Rule>,
+    /// Whether values expand `#{}`, see `Language::interpolation`.
+    interpolate: bool,
 }
 
 impl<'a> From<Pair<'a>> for TagToken<'a> {
@@ -822,6 +1073,7 @@ impl<'a> From<Pair<'a>> for TagToken<'a> {
         TagToken {
             token,
             expected: Vec::new(),
+            interpolate: false,
         }
     }
 }
@@ -953,7 +1205,7 @@ impl<'a> TagToken<'a> {
     /// In this runtime, value refers to either a literal value or a variable.
     pub fn expect_value(mut self) -> TryMatchToken<'a, Expression> {
         match self.unwrap_value() {
-            Ok(t) => TryMatchToken::Matches(parse_value(t)),
+            Ok(t) => TryMatchToken::Matches(parse_value(t, self.interpolate)),
             Err(_) => {
                 self.expected.push(Rule::Value);
                 TryMatchToken::Fails(self)
@@ -964,7 +1216,7 @@ impl<'a> TagToken<'a> {
     /// Tries to obtain a `Variable` from this token.
     pub fn expect_variable(mut self) -> TryMatchToken<'a, Variable> {
         match self.unwrap_variable() {
-            Ok(t) => TryMatchToken::Matches(parse_variable(t)),
+            Ok(t) => TryMatchToken::Matches(parse_variable(t, self.interpolate)),
             Err(_) => {
                 self.expected.push(Rule::Variable);
                 TryMatchToken::Fails(self)
@@ -1010,8 +1262,8 @@ impl<'a> TagToken<'a> {
 
         let mut range = token.into_inner();
         TryMatchToken::Matches((
-            parse_value(range.next().expect("start")),
-            parse_value(range.next().expect("end")),
+            parse_value(range.next().expect("start"), self.interpolate),
+            parse_value(range.next().expect("end"), self.interpolate),
         ))
     }
 
@@ -1029,12 +1281,17 @@ impl<'a> TagToken<'a> {
     pub fn as_str(&self) -> &str {
         self.token.as_str().trim()
     }
+
+    /// Line and column where the token starts, for messages pointing at the source.
+    pub fn line_col(&self) -> (usize, usize) {
+        self.token.as_span().start_pos().line_col()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use liquid_interpreter::{Runtime, Template};
+    use liquid_interpreter::{MissingFilterHandler, Runtime, RuntimeBuilder, Template};
 
     #[test]
     fn test_parse_literal() {
@@ -1130,7 +1387,138 @@ mod test {
         let mut expected = Variable::with_literal("foo");
         expected.extend(indexes);
 
-        assert_eq!(parse_variable(variable), expected);
+        assert_eq!(parse_variable(variable, false), expected);
+    }
+
+    #[test]
+    fn test_parse_collection_literals() {
+        let array = LiquidParser::parse(Rule::Value, r#"[1, "a", [true]]"#)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_value(array, false),
+            Expression::Literal(Value::Array(vec![
+                Value::scalar(1),
+                Value::scalar("a"),
+                Value::Array(vec![Value::scalar(true)]),
+            ]))
+        );
+
+        let object = LiquidParser::parse(Rule::Value, r#"{"x": 1, "y": foo}"#)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_value(object, false),
+            Expression::Object(vec![
+                ("x".into(), Expression::Literal(Value::scalar(1))),
+                (
+                    "y".into(),
+                    Expression::Variable(Variable::with_literal("foo"))
+                ),
+            ])
+        );
+
+        let empty = LiquidParser::parse(Rule::Value, "[]")
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_value(empty, false),
+            Expression::Literal(Value::Array(vec![]))
+        );
+    }
+
+    #[test]
+    fn test_parse_interpolation() {
+        let value = LiquidParser::parse(Rule::Value, r##""Hi #{ user.name }, #{1}#{x""##)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_value(value, true),
+            Expression::Interpolation(vec![
+                Expression::with_literal("Hi "),
+                Expression::Variable(Variable::with_literal("user").push_literal("name")),
+                Expression::with_literal(", "),
+                Expression::Literal(Value::scalar(1)),
+                Expression::with_literal("#{x"),
+            ])
+        );
+
+        let value = LiquidParser::parse(Rule::Value, "'Hi #{user}'")
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_value(value, true),
+            Expression::Literal(Value::scalar("Hi #{user}"))
+        );
+
+        let value = LiquidParser::parse(Rule::Value, r##""#{not a value}""##)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_value(value, true),
+            Expression::Literal(Value::scalar("#{not a value}"))
+        );
+
+        let value = LiquidParser::parse(Rule::Value, r##""Hi #{user}""##)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            parse_value(value, false),
+            Expression::Literal(Value::scalar("Hi #{user}"))
+        );
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let options = Language::default();
+
+        let mut runtime = Runtime::new();
+        runtime.stack_mut().set_global("exp", Value::scalar(5));
+
+        let expression = parse_expression("\n  exp\n", &options).unwrap();
+        assert_eq!(
+            expression.evaluate(&runtime).unwrap().into_owned(),
+            Value::scalar(5)
+        );
+
+        parse_expression("exp exp", &options).unwrap_err();
+        parse_expression("{{ exp }}", &options).unwrap_err();
+        parse_expression("exp | unknown", &options).unwrap_err();
+    }
+
+    #[test]
+    fn test_defer_unknown_filters() {
+        let mut options = Language::default();
+        options.defer_unknown_filters = true;
+        let template = parse("{{ 'hi' | shout: 2, by: 'me' }}", &options)
+            .map(Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        template.render(&mut runtime).unwrap_err();
+
+        let handler = MissingFilterHandler::new(|name, input, args, keyword| {
+            let by = keyword.get("by").map(|by| by.to_kstr().to_string());
+            Ok(Value::scalar(format!(
+                "{} {} {} {}",
+                name,
+                input.to_kstr(),
+                args[0].to_kstr(),
+                by.unwrap_or_default()
+            )))
+        });
+        let mut runtime = RuntimeBuilder::new()
+            .set_missing_filter_handler(&handler)
+            .build();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "shout hi 2 me");
     }
 
     #[test]
@@ -1164,4 +1552,13 @@ mod test {
 
         assert_eq!(output, "5");
     }
+
+    #[test]
+    fn test_contains_liquid() {
+        assert!(!contains_liquid(""));
+        assert!(!contains_liquid("Plain { text } with %} and }}"));
+        assert!(contains_liquid("Hello {{ name }}"));
+        assert!(contains_liquid("{%- if x -%}{% endif %}"));
+        assert!(contains_liquid("Unclosed {{"));
+    }
 }
@@ -26,6 +26,11 @@ impl<P> PluginRegistry<P> {
         old.is_some()
     }
 
+    /// Remove a plugin, returning it if it was registered.
+    pub fn unregister(&mut self, name: &str) -> Option<P> {
+        self.plugins.remove(name)
+    }
+
     /// Look up an existing plugin.
     ///
     /// Generally this is used for running plugins.
@@ -0,0 +1,504 @@
+//! Compile report
+//!
+//! Summarizes the structure of a template, for use in enforcing template-complexity budgets.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+
+use pest::Parser;
+
+use liquid_error::{Error, Result};
+use liquid_interpreter::Coverage;
+
+use super::parser::pest::{LiquidParser, Rule};
+use super::Language;
+
+type Pair<'a> = ::pest::iterators::Pair<'a, Rule>;
+
+/// Blocks whose content is not parsed as liquid, and whether they nest.
+const OPAQUE_BLOCKS: &[(&str, bool)] = &[("raw", false), ("comment", true)];
+
+/// Tags that render another template, named by their first argument.
+const PARTIAL_TAGS: &[&str] = &["include"];
+
+/// Tags that create a variable, named by their first argument.
+const BINDING_TAGS: &[&str] = &[
+    "assign",
+    "capture",
+    "for",
+    "tablerow",
+    "increment",
+    "decrement",
+    "constant",
+];
+
+/// Tags that create a variable, named by the argument after `into`.
+const INTO_BINDING_TAGS: &[&str] = &["call", "fetch"];
+
+/// Tags whose first argument names a host function rather than a variable.
+const FUNCTION_TAGS: &[&str] = &["call"];
+
+/// Binding tags whose variable is only meant to be read, see `CompileReport::unused_assigns`.
+const ASSIGN_TAGS: &[&str] = &["assign", "capture"];
+
+/// Variables that blocks create without a tag naming them.
+const IMPLICIT_VARIABLES: &[&str] = &["forloop", "tablerowloop"];
+
+/// Words that are part of a tag's syntax when they are standalone arguments.
+const TAG_KEYWORDS: &[&str] = &[
+    "and", "or", "contains", "in", "limit", "offset", "reversed", "cols", "with", "eq", "ne", "lt",
+    "gt", "le", "ge", "into",
+];
+
+/// The structure of a template.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompileReport {
+    size: usize,
+    text: usize,
+    outputs: usize,
+    tags: BTreeMap<String, usize>,
+    max_nesting: usize,
+    partials: BTreeMap<String, usize>,
+    filters: BTreeMap<String, usize>,
+    variables: BTreeSet<String>,
+    unused_assigns: Vec<(String, String)>,
+    elements: Vec<(String, String)>,
+}
+
+impl CompileReport {
+    /// Length of the template source, in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Total number of nodes: text, outputs, tags and blocks.
+    pub fn nodes(&self) -> usize {
+        self.text + self.outputs + self.tags.values().sum::<usize>()
+    }
+
+    /// Number of runs of text outside of tags and outputs.
+    pub fn text_nodes(&self) -> usize {
+        self.text
+    }
+
+    /// Number of `{{ }}` outputs.
+    pub fn output_nodes(&self) -> usize {
+        self.outputs
+    }
+
+    /// Number of tags and blocks, by name.  Closing tags are not counted.
+    pub fn tags(&self) -> &BTreeMap<String, usize> {
+        &self.tags
+    }
+
+    /// Deepest level of blocks nested within each other.
+    pub fn max_nesting(&self) -> usize {
+        self.max_nesting
+    }
+
+    /// Number of times each partial is included, by name.
+    pub fn partials(&self) -> &BTreeMap<String, usize> {
+        &self.partials
+    }
+
+    /// Number of distinct partials included.
+    pub fn partial_fanout(&self) -> usize {
+        self.partials.len()
+    }
+
+    /// Number of times each filter is used, by name.
+    pub fn filters(&self) -> &BTreeMap<String, usize> {
+        &self.filters
+    }
+
+    /// Variables read from the globals, by their top-level name.
+    ///
+    /// Variables the template creates before reading them, like with `assign` or `for`, are not
+    /// included.
+    pub fn variables(&self) -> &BTreeSet<String> {
+        &self.variables
+    }
+
+    /// Variables set by `assign` or `capture` that the template never reads, with the position
+    /// of each assignment, like `3:11`.
+    ///
+    /// Reads by included partials are not seen, so variables only they use are reported too.
+    pub fn unused_assigns(&self) -> &[(String, String)] {
+        &self.unused_assigns
+    }
+
+    /// Tags, blocks and outputs, with their position, like `3:11`.
+    ///
+    /// Tags are named, like `if`, and outputs are their source, like `{{ title }}`.
+    pub fn elements(&self) -> &[(String, String)] {
+        &self.elements
+    }
+
+    /// Elements of the template that never ran, as recorded in `coverage` under `template`.
+    ///
+    /// A branch that never ran shows up as the elements it holds, so branches of only text are
+    /// not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let options = liquid_compiler::Language::empty();
+    /// let text = "{{ a }}{{ b }}";
+    /// let report = liquid_compiler::analyze(text, &options);
+    ///
+    /// let coverage = liquid_interpreter::Coverage::new();
+    /// coverage.record("", "1:1");
+    /// assert_eq!(
+    ///     report.uncovered(&coverage, ""),
+    ///     [("{{ b }}".to_owned(), "1:8".to_owned())]
+    /// );
+    /// ```
+    pub fn uncovered(&self, coverage: &Coverage, template: &str) -> Vec<(String, String)> {
+        self.elements
+            .iter()
+            .filter(|(_, position)| !coverage.is_covered(template, position))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Parse-time budgets, rejecting pathological templates before they are compiled.
+///
+/// Limits are unset by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum length of a template, in bytes.
+    pub max_size: Option<usize>,
+    /// Maximum number of nodes, see `CompileReport::nodes`.
+    pub max_nodes: Option<usize>,
+    /// Maximum depth of nested blocks, see `CompileReport::max_nesting`.
+    pub max_nesting: Option<usize>,
+    /// Percentage of a limit from which templates are reported to `Language::limit_observer`,
+    /// while still being accepted.
+    pub warn_percent: Option<usize>,
+}
+
+impl ParseLimits {
+    /// Checks `text` against the limits, analyzing it only when its structure is limited.
+    ///
+    /// Templates near a limit are reported to `options.limit_observer`.
+    pub fn check(&self, text: &str, options: &Language) -> Result<()> {
+        check_limit("max_size", self.max_size, text.len())?;
+        let report = if self.max_nodes.is_some() || self.max_nesting.is_some() {
+            analyze(text, options)
+        } else {
+            CompileReport {
+                size: text.len(),
+                ..Default::default()
+            }
+        };
+        self.check_report(&report)?;
+        if let Some(ref observer) = options.limit_observer {
+            for warning in self.warnings(&report) {
+                observer(&warning);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a report against the limits.
+    pub fn check_report(&self, report: &CompileReport) -> Result<()> {
+        for &(name, limit, actual) in self.usage(report).iter() {
+            check_limit(name, limit, actual)?;
+        }
+        Ok(())
+    }
+
+    /// The limits a report is within `warn_percent` of.
+    pub fn warnings(&self, report: &CompileReport) -> Vec<LimitWarning> {
+        let percent = match self.warn_percent {
+            Some(percent) => percent,
+            None => return Vec::new(),
+        };
+        self.usage(report)
+            .iter()
+            .filter_map(|&(limit, allowed, actual)| {
+                let allowed = allowed?;
+                // Widened so huge limits or percentages can't overflow.
+                if (allowed as u128) * (percent as u128) <= (actual as u128) * 100 {
+                    Some(LimitWarning {
+                        limit,
+                        allowed,
+                        actual,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn usage(&self, report: &CompileReport) -> [(&'static str, Option<usize>, usize); 3] {
+        [
+            ("max_size", self.max_size, report.size()),
+            ("max_nodes", self.max_nodes, report.nodes()),
+            ("max_nesting", self.max_nesting, report.max_nesting()),
+        ]
+    }
+}
+
+/// A template that is accepted but close to a parse limit, see `ParseLimits::warn_percent`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LimitWarning {
+    /// Name of the limit, like `max_size`.
+    pub limit: &'static str,
+    /// Value of the limit.
+    pub allowed: usize,
+    /// What the template uses of it.
+    pub actual: usize,
+}
+
+impl fmt::Display for LimitWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Template is near parse limit `{}` ({} of {})",
+            self.limit, self.actual, self.allowed
+        )
+    }
+}
+
+/// Called with templates that are near a parse limit.
+pub type LimitObserver = Arc<dyn Fn(&LimitWarning) + Send + Sync>;
+
+fn check_limit(name: &'static str, limit: Option<usize>, actual: usize) -> Result<()> {
+    match limit {
+        Some(limit) if limit < actual => Err(Error::with_msg("Template exceeds parse limit")
+            .context("limit", name)
+            .context("allowed", limit.to_string())
+            .context("actual", actual.to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// Summarizes the structure of `text`.
+///
+/// This does not validate the template; see `parse`.  Content of `raw` and `comment` blocks is
+/// not counted.
+pub fn analyze(text: &str, options: &Language) -> CompileReport {
+    let elements = LiquidParser::parse(Rule::LaxLiquidFile, text)
+        .expect("Parsing with Rule::LaxLiquidFile should not raise errors, but InvalidLiquid tokens instead.")
+        .next()
+        .expect("Unwrapping LiquidFile to access the elements.")
+        .into_inner();
+
+    let mut report = CompileReport {
+        size: text.len(),
+        ..Default::default()
+    };
+    let mut open: Vec<&str> = Vec::new();
+    let mut bound: BTreeSet<&str> = IMPLICIT_VARIABLES.iter().cloned().collect();
+    let mut read: BTreeSet<&str> = BTreeSet::new();
+    let mut assigned: Vec<(&str, String)> = Vec::new();
+    // The opaque block being skipped, its nesting flag and level.
+    let mut opaque: Option<(&str, bool, usize)> = None;
+
+    for element in elements {
+        match element.as_rule() {
+            Rule::Raw if opaque.is_none() => report.text += 1,
+            Rule::Expression if opaque.is_none() => {
+                report.outputs += 1;
+                let (line, column) = element.as_span().start_pos().line_col();
+                report.elements.push((
+                    element.as_str().trim().to_owned(),
+                    format!("{}:{}", line, column),
+                ));
+                count_variables(element.clone(), &bound, &mut read, &mut report.variables);
+                count_filters(element, &mut report.filters);
+            }
+            Rule::Tag => {
+                let mut tag = element
+                    .into_inner()
+                    .next()
+                    .expect("Unwrapping TagInner")
+                    .into_inner();
+                let name = tag.next().expect("Tags start by their identifier.");
+                let (line, column) = name.as_span().start_pos().line_col();
+                let name = name.as_str();
+                let closed = if name.len() > 3 && name.starts_with("end") {
+                    Some(&name[3..])
+                } else {
+                    None
+                };
+
+                if let Some((block, allow_nesting, level)) = opaque {
+                    if closed == Some(block) {
+                        if level == 1 {
+                            opaque = None;
+                            open.pop();
+                        } else {
+                            opaque = Some((block, allow_nesting, level - 1));
+                        }
+                    } else if name == block && allow_nesting {
+                        opaque = Some((block, allow_nesting, level + 1));
+                    }
+                    continue;
+                }
+
+                if closed.is_some() && closed == open.last().cloned() {
+                    open.pop();
+                    continue;
+                }
+
+                *report.tags.entry(name.to_owned()).or_insert(0) += 1;
+                if options.tags.get(name).is_some() || options.blocks.get(name).is_some() {
+                    report
+                        .elements
+                        .push((name.to_owned(), format!("{}:{}", line, column)));
+                }
+                if PARTIAL_TAGS.contains(&name) {
+                    if let Some(partial) = tag.clone().next() {
+                        let partial = partial
+                            .as_str()
+                            .trim()
+                            .trim_matches(|c| c == '"' || c == '\'');
+                        *report.partials.entry(partial.to_owned()).or_insert(0) += 1;
+                    }
+                }
+                let tokens: Vec<_> = tag.collect();
+                let mut binding = None;
+                for (i, token) in tokens.iter().enumerate() {
+                    let root = variable_root(token.clone());
+                    let is_word = root.map_or(false, |root| token.as_str().trim() == root);
+                    let after_into = i > 0 && tokens[i - 1].as_str().trim() == "into";
+                    let before_colon = tokens.get(i + 1).map_or(false, |next| next.as_str() == ":");
+                    if (i == 0 && BINDING_TAGS.contains(&name))
+                        || (after_into && INTO_BINDING_TAGS.contains(&name))
+                    {
+                        binding = root;
+                        if let Some(binding) = binding.filter(|_| ASSIGN_TAGS.contains(&name)) {
+                            let (line, column) = token.as_span().start_pos().line_col();
+                            assigned.push((binding, format!("{}:{}", line, column)));
+                        }
+                    } else if i == 0 && FUNCTION_TAGS.contains(&name) {
+                        // A function name, like `pricing.quote`.
+                    } else if is_word
+                        && (before_colon || root.map_or(false, |root| TAG_KEYWORDS.contains(&root)))
+                    {
+                        // Syntax, like `limit` or an argument name before `:`.
+                    } else {
+                        count_variables(token.clone(), &bound, &mut read, &mut report.variables);
+                    }
+                    count_filters(token.clone(), &mut report.filters);
+                }
+                if let Some(binding) = binding {
+                    bound.insert(binding);
+                }
+
+                if options.blocks.get(name).is_some() {
+                    open.push(name);
+                    report.max_nesting = report.max_nesting.max(open.len());
+                    if let Some(&(block, allow_nesting)) =
+                        OPAQUE_BLOCKS.iter().find(|&&(block, _)| block == name)
+                    {
+                        opaque = Some((block, allow_nesting, 1));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    report.unused_assigns = assigned
+        .into_iter()
+        .filter(|(name, _)| !read.contains(name))
+        .map(|(name, position)| (name.to_owned(), position))
+        .collect();
+    report
+}
+
+fn count_filters(pair: Pair, filters: &mut BTreeMap<String, usize>) {
+    for pair in pair.into_inner() {
+        if pair.as_rule() == Rule::Filter {
+            let name = pair
+                .clone()
+                .into_inner()
+                .next()
+                .expect("A filter always has a name.")
+                .as_str();
+            *filters.entry(name.to_owned()).or_insert(0) += 1;
+        }
+        count_filters(pair, filters);
+    }
+}
+
+/// The top-level name of a variable, when `pair` is only a variable.
+fn variable_root<'a>(pair: Pair<'a>) -> Option<&'a str> {
+    let mut pair = pair;
+    loop {
+        match pair.as_rule() {
+            Rule::Variable => {
+                return pair
+                    .into_inner()
+                    .next()
+                    .map(|identifier| identifier.as_str());
+            }
+            Rule::FilterChain | Rule::Value => {
+                let mut inner = pair.into_inner();
+                let first = inner.next()?;
+                if inner.next().is_some() {
+                    return None;
+                }
+                pair = first;
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn count_variables<'a>(
+    pair: Pair<'a>,
+    bound: &BTreeSet<&str>,
+    read: &mut BTreeSet<&'a str>,
+    variables: &mut BTreeSet<String>,
+) {
+    if pair.as_rule() == Rule::Variable {
+        let mut inner = pair.into_inner();
+        if let Some(root) = inner.next() {
+            let root = root.as_str();
+            read.insert(root);
+            if !bound.contains(root) {
+                variables.insert(root.to_owned());
+            }
+        }
+        for index in inner {
+            count_variables(index, bound, read, variables);
+        }
+        return;
+    }
+    for pair in pair.into_inner() {
+        count_variables(pair, bound, read, variables);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn warnings_with_huge_limits() {
+        let limits = ParseLimits {
+            max_size: Some(usize::max_value()),
+            warn_percent: Some(usize::max_value()),
+            ..Default::default()
+        };
+        let report = CompileReport {
+            size: 10,
+            ..Default::default()
+        };
+        assert_eq!(limits.warnings(&report), Vec::new());
+
+        let limits = ParseLimits {
+            max_size: Some(10),
+            warn_percent: Some(80),
+            ..Default::default()
+        };
+        assert_eq!(limits.warnings(&report).len(), 1);
+    }
+}
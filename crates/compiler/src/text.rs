@@ -6,13 +6,13 @@ use liquid_interpreter::Runtime;
 
 /// A raw template expression.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub(crate) struct Text {
+pub struct Text {
     text: String,
 }
 
 impl Text {
     /// Create a raw template expression.
-    pub(crate) fn new<S: Into<String>>(text: S) -> Text {
+    pub fn new<S: Into<String>>(text: S) -> Text {
         Text { text: text.into() }
     }
 }
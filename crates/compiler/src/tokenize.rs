@@ -0,0 +1,351 @@
+//! Tokenizer
+//!
+//! Splits a template into classified tokens without building renderables, for use by syntax
+//! highlighters and editor tooling.
+
+use std::ops::Range;
+
+use pest::Parser;
+
+use super::parser::pest::{LiquidParser, Rule};
+
+type Pair<'a> = ::pest::iterators::Pair<'a, Rule>;
+
+/// Identifiers that act as keywords inside of tags.
+const KEYWORDS: &[&str] = &["and", "or", "contains", "in", "with", "as", "for"];
+
+/// Classification of a `SpannedToken`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Text outside of tags and outputs.
+    Text,
+    /// `{%` or `{%-`.
+    TagOpen,
+    /// `%}` or `-%}`.
+    TagClose,
+    /// `{{` or `{{-`.
+    ExpressionOpen,
+    /// `}}` or `-}}`.
+    ExpressionClose,
+    /// The name of a tag or block, like `if` or `endfor`.
+    TagName,
+    /// A keyword within a tag, like `and` or `in`.
+    Keyword,
+    /// A variable or one of its indexes.
+    Variable,
+    /// The name of a filter.
+    Filter,
+    /// The name of a keyword filter argument.
+    Parameter,
+    /// A quoted string.
+    String,
+    /// An integer or float.
+    Number,
+    /// `true` or `false`.
+    Boolean,
+    /// `nil`, `empty` or `blank`.
+    Constant,
+    /// A comparison or assignment operator.
+    Operator,
+    /// Separators like `|`, `:`, `,`, `.`, brackets and parentheses.
+    Punctuation,
+    /// A `#` comment inside of a tag or output.
+    Comment,
+    /// Anything that could not be recognized.
+    Invalid,
+}
+
+/// A classified piece of the template source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpannedToken<'a> {
+    kind: TokenKind,
+    span: Range<usize>,
+    text: &'a str,
+}
+
+impl<'a> SpannedToken<'a> {
+    fn new(source: &'a str, kind: TokenKind, span: Range<usize>) -> Self {
+        let text = &source[span.clone()];
+        SpannedToken { kind, span, text }
+    }
+
+    /// The classification of this token.
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// Byte offsets of this token within the source.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The source text of this token.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+}
+
+/// Splits `source` into classified tokens.
+///
+/// Tokenizing never fails: text that isn't valid liquid is reported as `TokenKind::Invalid`.
+/// Whitespace inside of tags and outputs is skipped.  The contents of blocks like `raw` and
+/// `comment` are tokenized like any other part of the template.
+pub fn tokenize(source: &str) -> impl Iterator<Item = SpannedToken<'_>> {
+    let file = LiquidParser::parse(Rule::LaxLiquidFile, source)
+        .expect("Parsing with Rule::LaxLiquidFile should not raise errors, but InvalidLiquid tokens instead.")
+        .next()
+        .expect("Unwrapping LiquidFile to access the elements.");
+
+    let mut leaves = Vec::new();
+    for element in file.into_inner() {
+        visit(source, element, &mut leaves);
+    }
+
+    // Everything not covered by a pest token is made of delimiters, punctuation or comments.
+    let mut tokens = Vec::with_capacity(leaves.len());
+    let mut position = 0;
+    for leaf in leaves {
+        lex_gap(source, position..leaf.span.start, &mut tokens);
+        position = leaf.span.end;
+        push(source, &mut tokens, leaf);
+    }
+    lex_gap(source, position..source.len(), &mut tokens);
+
+    tokens.into_iter()
+}
+
+/// Push `token`, merging runs of invalid text.
+fn push<'a>(source: &'a str, tokens: &mut Vec<SpannedToken<'a>>, token: SpannedToken<'a>) {
+    if let Some(last) = tokens.last_mut() {
+        if last.kind == TokenKind::Invalid
+            && token.kind == TokenKind::Invalid
+            && last.span.end == token.span.start
+        {
+            *last = SpannedToken::new(source, TokenKind::Invalid, last.span.start..token.span.end);
+            return;
+        }
+    }
+    tokens.push(token);
+}
+
+fn visit<'a>(source: &'a str, pair: Pair<'a>, out: &mut Vec<SpannedToken<'a>>) {
+    let span = pair.as_span().start()..pair.as_span().end();
+    let kind = match pair.as_rule() {
+        Rule::Raw => TokenKind::Text,
+        Rule::InvalidLiquid => TokenKind::Invalid,
+        Rule::StringLiteral => TokenKind::String,
+        Rule::IntegerLiteral | Rule::FloatLiteral => TokenKind::Number,
+        Rule::BooleanLiteral => TokenKind::Boolean,
+        Rule::NilLiteral | Rule::EmptyLiteral | Rule::BlankLiteral => TokenKind::Constant,
+        Rule::Equals
+        | Rule::NotEquals
+        | Rule::LesserThanGreaterThan
+        | Rule::GreaterThanEquals
+        | Rule::LesserThanEquals
+        | Rule::GreaterThan
+        | Rule::LesserThan
+        | Rule::Assign
+        | Rule::And
+        | Rule::Or => TokenKind::Operator,
+        Rule::Comma | Rule::Colon => TokenKind::Punctuation,
+        Rule::Identifier => TokenKind::Variable,
+        Rule::TagInner => {
+            let mut inner = pair.into_inner();
+            if let Some(name) = inner.next() {
+                let span = name.as_span().start()..name.as_span().end();
+                out.push(SpannedToken::new(source, TokenKind::TagName, span));
+            }
+            for token in inner {
+                visit(source, token, out);
+            }
+            return;
+        }
+        Rule::Variable => {
+            let mut inner = pair.clone().into_inner();
+            let is_keyword = match (inner.next(), inner.next()) {
+                (Some(only), None) => KEYWORDS.contains(&only.as_str()),
+                _ => false,
+            };
+            if is_keyword {
+                TokenKind::Keyword
+            } else {
+                visit_children(source, pair, out);
+                return;
+            }
+        }
+        Rule::Filter => {
+            let mut inner = pair.into_inner();
+            if let Some(name) = inner.next() {
+                let span = name.as_span().start()..name.as_span().end();
+                out.push(SpannedToken::new(source, TokenKind::Filter, span));
+            }
+            for argument in inner {
+                visit(source, argument, out);
+            }
+            return;
+        }
+        Rule::KeywordFilterArgument => {
+            let mut inner = pair.into_inner();
+            if let Some(name) = inner.next() {
+                let span = name.as_span().start()..name.as_span().end();
+                out.push(SpannedToken::new(source, TokenKind::Parameter, span));
+            }
+            for value in inner {
+                visit(source, value, out);
+            }
+            return;
+        }
+        Rule::EOI => return,
+        _ => {
+            visit_children(source, pair, out);
+            return;
+        }
+    };
+    push(source, out, SpannedToken::new(source, kind, span));
+}
+
+fn visit_children<'a>(source: &'a str, pair: Pair<'a>, out: &mut Vec<SpannedToken<'a>>) {
+    for child in pair.into_inner() {
+        visit(source, child, out);
+    }
+}
+
+/// Classify text between pest tokens.
+fn lex_gap<'a>(source: &'a str, gap: Range<usize>, out: &mut Vec<SpannedToken<'a>>) {
+    const SYMBOLS: &[(&str, TokenKind)] = &[
+        ("{%-", TokenKind::TagOpen),
+        ("{%", TokenKind::TagOpen),
+        ("-%}", TokenKind::TagClose),
+        ("%}", TokenKind::TagClose),
+        ("{{-", TokenKind::ExpressionOpen),
+        ("{{", TokenKind::ExpressionOpen),
+        ("-}}", TokenKind::ExpressionClose),
+        ("}}", TokenKind::ExpressionClose),
+        ("..", TokenKind::Punctuation),
+    ];
+
+    let mut position = gap.start;
+    'outer: while position < gap.end {
+        let rest = &source[position..gap.end];
+        let c = rest.chars().next().expect("Position is within the gap.");
+        if c.is_whitespace() {
+            position += c.len_utf8();
+            continue;
+        }
+        for &(symbol, kind) in SYMBOLS {
+            if rest.starts_with(symbol) {
+                out.push(SpannedToken::new(
+                    source,
+                    kind,
+                    position..position + symbol.len(),
+                ));
+                position += symbol.len();
+                continue 'outer;
+            }
+        }
+        let (kind, len) = match c {
+            '#' => {
+                let len = rest
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        c == '\n'
+                            || c == '\r'
+                            || rest[i..].starts_with("-%}")
+                            || rest[i..].starts_with("%}")
+                            || rest[i..].starts_with("-}}")
+                            || rest[i..].starts_with("}}")
+                    })
+                    .map_or(rest.len(), |(i, _)| i);
+                (TokenKind::Comment, len)
+            }
+            '|' | ':' | ',' | '.' | '[' | ']' | '(' | ')' | '{' | '}' => {
+                (TokenKind::Punctuation, 1)
+            }
+            _ => (TokenKind::Invalid, c.len_utf8()),
+        };
+        push(
+            source,
+            out,
+            SpannedToken::new(source, kind, position..position + len),
+        );
+        position += len;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(source).map(|t| (t.kind(), t.as_str())).collect()
+    }
+
+    #[test]
+    fn test_tokenize_text() {
+        assert_eq!(kinds("Hello"), vec![(TokenKind::Text, "Hello")]);
+        assert_eq!(kinds(""), vec![]);
+    }
+
+    #[test]
+    fn test_tokenize_expression() {
+        assert_eq!(
+            kinds("a {{- user.name | default: 'x', allow_false: true -}}"),
+            vec![
+                (TokenKind::Text, "a"),
+                (TokenKind::ExpressionOpen, "{{-"),
+                (TokenKind::Variable, "user"),
+                (TokenKind::Punctuation, "."),
+                (TokenKind::Variable, "name"),
+                (TokenKind::Punctuation, "|"),
+                (TokenKind::Filter, "default"),
+                (TokenKind::Punctuation, ":"),
+                (TokenKind::String, "'x'"),
+                (TokenKind::Punctuation, ","),
+                (TokenKind::Parameter, "allow_false"),
+                (TokenKind::Punctuation, ":"),
+                (TokenKind::Boolean, "true"),
+                (TokenKind::ExpressionClose, "-}}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_tag() {
+        assert_eq!(
+            kinds("{% for i in (1..max) %}{% if i >= 2 and x[0] # note\n %}"),
+            vec![
+                (TokenKind::TagOpen, "{%"),
+                (TokenKind::TagName, "for"),
+                (TokenKind::Variable, "i"),
+                (TokenKind::Keyword, "in"),
+                (TokenKind::Punctuation, "("),
+                (TokenKind::Number, "1"),
+                (TokenKind::Punctuation, ".."),
+                (TokenKind::Variable, "max"),
+                (TokenKind::Punctuation, ")"),
+                (TokenKind::TagClose, "%}"),
+                (TokenKind::TagOpen, "{%"),
+                (TokenKind::TagName, "if"),
+                (TokenKind::Variable, "i"),
+                (TokenKind::Operator, ">="),
+                (TokenKind::Number, "2"),
+                (TokenKind::Keyword, "and"),
+                (TokenKind::Variable, "x"),
+                (TokenKind::Punctuation, "["),
+                (TokenKind::Number, "0"),
+                (TokenKind::Punctuation, "]"),
+                (TokenKind::Comment, "# note"),
+                (TokenKind::TagClose, "%}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_invalid() {
+        let tokens: Vec<_> = tokenize("{{ ~~ }}x").collect();
+        assert_eq!(tokens[0].kind(), TokenKind::Invalid);
+        assert_eq!(tokens[0].span(), 0..1);
+        assert_eq!(tokens[1].kind(), TokenKind::Text);
+        assert_eq!(tokens[1].as_str(), "{ ~~ }}x");
+    }
+}
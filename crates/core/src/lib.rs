@@ -26,8 +26,8 @@ pub use liquid_interpreter::Expression;
 pub use liquid_interpreter::Renderable;
 pub use liquid_interpreter::Runtime;
 pub use liquid_interpreter::Template;
+pub use liquid_value::{from_value, to_value, value, IntoValue, Value, ValueCow};
 pub use liquid_value::{object, to_object, Object};
-pub use liquid_value::{to_value, value, Value, ValueCow};
 pub use liquid_value::{ObjectView, ValueView};
 
 #[allow(unused_macros)]
@@ -23,6 +23,9 @@ use super::PartialSource;
 ///
 /// Note: partial-compilation error reporting is deferred to render-time so content can still be
 /// generated even when the content is in an intermediate-state.
+///
+/// With the `rayon` feature, partial-templates are compiled in parallel.  Each keeps its own
+/// result, so which errors are reported, and for which partial, doesn't depend on scheduling.
 #[derive(Debug)]
 pub struct EagerCompiler<S: PartialSource> {
     source: S,
@@ -84,22 +87,24 @@ where
     S: PartialSource + Send + Sync + 'static,
 {
     fn compile(self, language: sync::Arc<Language>) -> Result<Box<dyn PartialStore + Send + Sync>> {
-        let store: HashMap<_, _> = self
-            .source
-            .names()
-            .into_iter()
-            .map(|name| {
-                let source = self.source.get(name).and_then(|s| {
-                    compiler::parse(s.as_ref(), &language)
-                        .map(interpreter::Template::new)
-                        .map(|t| {
-                            let t: sync::Arc<dyn interpreter::Renderable> = sync::Arc::new(t);
-                            t
-                        })
-                });
-                (name.to_owned(), source)
-            })
-            .collect();
+        let compile = |name: &str| {
+            let source = self.source.get(name).and_then(|s| {
+                compiler::parse(s.as_ref(), &language)
+                    .map(interpreter::Template::new)
+                    .map(|t| {
+                        let t: sync::Arc<dyn interpreter::Renderable> = sync::Arc::new(t);
+                        t
+                    })
+            });
+            (name.to_owned(), source)
+        };
+        #[cfg(feature = "rayon")]
+        let store: HashMap<_, _> = {
+            use rayon::prelude::*;
+            self.source.names().into_par_iter().map(compile).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let store: HashMap<_, _> = self.source.names().into_iter().map(compile).collect();
         let store = EagerStore { store };
         Ok(Box::new(store))
     }
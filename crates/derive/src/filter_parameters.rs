@@ -527,7 +527,10 @@ fn generate_evaluate_field(field: &FilterParameter<'_>) -> TokenStream {
         },
         FilterParameterType::DateTime => quote! {
             #name.as_scalar()
-            .and_then(|s| s.to_date_time())
+            .and_then(|s| match runtime.get_register::<::liquid_core::value::DateFormats>() {
+                ::std::option::Option::Some(formats) => s.to_date_time_with(formats),
+                ::std::option::Option::None => s.to_date_time(),
+            })
             .ok_or_else(||
                 ::liquid_core::error::Error::with_msg("Invalid argument")
                     .context("argument", #liquid_name)
@@ -252,12 +252,29 @@ pub fn derive_display_filter(item: TokenStream) -> TokenStream {
     filter::display::derive(&input).into()
 }
 
+/// Implements `ValueView` for a struct that also derives `ObjectView`, see `ObjectView`.
 #[proc_macro_derive(ValueView)]
 pub fn derive_value_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     value_view::derive(&input).into()
 }
 
+/// Implements `ObjectView` for a struct with named fields, each implementing `ValueView`.
+///
+/// Together with `derive(ValueView)`, the struct can be passed to `Template::render` as is,
+/// without first converting it into an `Object`.  Fields are only read when the template uses
+/// them.
+///
+/// ```ignore
+/// #[derive(liquid::ObjectView, liquid::ValueView, Debug)]
+/// struct Page {
+///     title: String,
+///     views: i32,
+/// }
+///
+/// let template = parser.parse("{{ title }} ({{ views }})")?;
+/// let output = template.render(&Page { title: "Home".to_owned(), views: 3 })?;
+/// ```
 #[proc_macro_derive(ObjectView)]
 pub fn derive_object_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
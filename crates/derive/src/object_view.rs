@@ -28,10 +28,10 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
                 #num_fields as i32
             }
 
-            fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = ::kstring::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
+            fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = ::liquid::kstring::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
                 let mut keys = Vec::with_capacity(#num_fields);
                 #(
-                    keys.push(::kstring::KStringCow::from_static(stringify!(#fields)));
+                    keys.push(::liquid::kstring::KStringCow::from_static(stringify!(#fields)));
                 )*
                 Box::new(keys.into_iter())
             }
@@ -44,11 +44,11 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
                 Box::new(values.into_iter())
             }
 
-            fn iter<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = (::kstring::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid::ValueView)> + 'liquid_derive_k> {
-                let mut values = Vec::<(::kstring::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid::ValueView)>::with_capacity(#num_fields);
+            fn iter<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = (::liquid::kstring::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid::ValueView)> + 'liquid_derive_k> {
+                let mut values = Vec::<(::liquid::kstring::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid::ValueView)>::with_capacity(#num_fields);
                 #(
                     values.push((
-                        ::kstring::KStringCow::from_static(stringify!(#fields)),
+                        ::liquid::kstring::KStringCow::from_static(stringify!(#fields)),
                         &self.#fields,
                     ));
                 )*
@@ -73,6 +73,12 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
                 }
             }
         }
+
+        impl #impl_generics ::liquid::ObjectKeys for #ident #ty_generics #where_clause {
+            fn object_keys() -> &'static [&'static str] {
+                &[#(stringify!(#fields)),*]
+            }
+        }
     }
 }
 
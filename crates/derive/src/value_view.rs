@@ -29,16 +29,20 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
                     ::liquid::value::State::Truthy => true,
                     ::liquid::value::State::DefaultValue |
                     ::liquid::value::State::Empty |
-                    ::liquid::value::State::Blank => self.size() == 0,
+                    ::liquid::value::State::Blank => ::liquid::ObjectView::size(self) == 0,
                 }
             }
 
-            fn to_kstr(&self) -> ::kstring::KStringCow<'_> {
+            fn to_kstr(&self) -> ::liquid::kstring::KStringCow<'_> {
                 let s = ::liquid::value::ObjectRender::new(self).to_string();
-                ::kstring::KStringCow::from_string(s)
+                ::liquid::kstring::KStringCow::from_string(s)
             }
             fn to_value(&self) -> ::liquid::value::Value {
-                ::liquid::value::to_value(self).unwrap()
+                let mut object = ::liquid::Object::with_capacity(::liquid::ObjectView::size(self) as usize);
+                for (key, value) in ::liquid::ObjectView::iter(self) {
+                    object.insert(key.into(), ::liquid::ValueView::to_value(value));
+                }
+                ::liquid::value::Value::Object(object)
             }
 
             fn as_object(&self) -> Option<&dyn ::liquid::ObjectView> {
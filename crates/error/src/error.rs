@@ -44,6 +44,21 @@ impl Error {
         }
     }
 
+    /// The message, without the backtrace or context.
+    pub fn message(&self) -> &str {
+        self.inner.msg.as_str()
+    }
+
+    /// The value of a context `key`, from the innermost call that has it.
+    pub fn context_value(&self, key: &str) -> Option<&str> {
+        self.inner
+            .user_backtrace
+            .iter()
+            .flat_map(|trace| trace.get_context())
+            .find(|(k, _)| k.as_str() == key)
+            .map(|(_, v)| v.as_str())
+    }
+
     /// Add a new call to the user-visible backtrace
     pub fn trace<T>(self, trace: T) -> Self
     where
@@ -0,0 +1,70 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+/// The tags, blocks and outputs run over a set of renders, see `RuntimeBuilder::set_coverage`.
+///
+/// Elements are recorded by the name of their template, `""` for the template being rendered
+/// and the partial's name for included ones, and their position, like `3:11`.  One `Coverage`
+/// can be shared by renders on several threads.
+///
+/// # Examples
+///
+/// ```rust
+/// let coverage = liquid_interpreter::Coverage::new();
+/// coverage.record("", "1:4");
+/// assert!(coverage.is_covered("", "1:4"));
+/// assert!(!coverage.is_covered("footer", "1:4"));
+/// ```
+#[derive(Debug, Default)]
+pub struct Coverage {
+    executed: Mutex<BTreeMap<String, BTreeSet<String>>>,
+}
+
+impl Coverage {
+    /// Create an empty coverage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a run of the element at `position` in `template`.
+    pub fn record(&self, template: &str, position: &str) {
+        let mut executed = self.executed.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(positions) = executed.get_mut(template) {
+            if !positions.contains(position) {
+                positions.insert(position.to_owned());
+            }
+            return;
+        }
+        let mut positions = BTreeSet::new();
+        positions.insert(position.to_owned());
+        executed.insert(template.to_owned(), positions);
+    }
+
+    /// Whether the element at `position` in `template` ran at least once.
+    pub fn is_covered(&self, template: &str, position: &str) -> bool {
+        self.executed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(template)
+            .map(|positions| positions.contains(position))
+            .unwrap_or(false)
+    }
+
+    /// Names of the templates that ran, `""` being the template rendered.
+    pub fn templates(&self) -> Vec<String> {
+        self.executed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Forget everything recorded so far.
+    pub fn clear(&self) {
+        self.executed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
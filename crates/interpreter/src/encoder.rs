@@ -0,0 +1,164 @@
+use std::borrow::Cow;
+use std::fmt;
+
+/// Escapes the output of `{{ }}` for the format being generated.
+///
+/// Text of the template itself is written as is; only the values it outputs are encoded.
+pub trait OutputEncoder: fmt::Debug + Send + Sync {
+    /// Escape `text` so it is literal text in the output.
+    fn encode<'s>(&self, text: &'s str) -> Cow<'s, str>;
+
+    /// Whether text already in `encoding`, like `"html"`, is written as is instead of encoded
+    /// again, see `Filter::output_encoding`.
+    ///
+    /// Defaults to `false`.
+    fn accepts(&self, _encoding: &str) -> bool {
+        false
+    }
+}
+
+/// Replace each character of `text` that `escape` maps to a replacement.
+fn replace_chars<'s, F>(text: &'s str, escape: F) -> Cow<'s, str>
+where
+    F: Fn(char) -> Option<&'static str>,
+{
+    if !text.chars().any(|c| escape(c).is_some()) {
+        return Cow::Borrowed(text);
+    }
+    let mut encoded = String::with_capacity(text.len() + text.len() / 8);
+    for c in text.chars() {
+        match escape(c) {
+            Some(replacement) => encoded.push_str(replacement),
+            None => encoded.push(c),
+        }
+    }
+    Cow::Owned(encoded)
+}
+
+/// Escapes text for HTML elements and quoted attributes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HtmlEncoder;
+
+impl OutputEncoder for HtmlEncoder {
+    fn encode<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        replace_chars(text, |c| match c {
+            '&' => Some("&amp;"),
+            '<' => Some("&lt;"),
+            '>' => Some("&gt;"),
+            '"' => Some("&quot;"),
+            '\'' => Some("&#39;"),
+            _ => None,
+        })
+    }
+
+    fn accepts(&self, encoding: &str) -> bool {
+        encoding == "html"
+    }
+}
+
+/// Escapes text for XML elements and quoted attributes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct XmlEncoder;
+
+impl OutputEncoder for XmlEncoder {
+    fn encode<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        replace_chars(text, |c| match c {
+            '&' => Some("&amp;"),
+            '<' => Some("&lt;"),
+            '>' => Some("&gt;"),
+            '"' => Some("&quot;"),
+            '\'' => Some("&apos;"),
+            _ => None,
+        })
+    }
+
+    /// HTML's escapes are among XML's, so `"html"` is accepted too.
+    fn accepts(&self, encoding: &str) -> bool {
+        encoding == "xml" || encoding == "html"
+    }
+}
+
+/// Escapes LaTeX's special characters for use in text.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatexEncoder;
+
+impl OutputEncoder for LatexEncoder {
+    fn encode<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        replace_chars(text, |c| match c {
+            '\\' => Some("\\textbackslash{}"),
+            '&' => Some("\\&"),
+            '%' => Some("\\%"),
+            '$' => Some("\\$"),
+            '#' => Some("\\#"),
+            '_' => Some("\\_"),
+            '{' => Some("\\{"),
+            '}' => Some("\\}"),
+            '~' => Some("\\textasciitilde{}"),
+            '^' => Some("\\textasciicircum{}"),
+            _ => None,
+        })
+    }
+}
+
+/// Encodes text as a single CSV field, quoting it when it contains a separator, quote or line
+/// break.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CsvFieldEncoder;
+
+impl OutputEncoder for CsvFieldEncoder {
+    fn encode<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        if !text.contains(&[',', '"', '\n', '\r'][..]) {
+            return Cow::Borrowed(text);
+        }
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    }
+}
+
+/// Encodes text as a single POSIX shell word, quoting it unless it only has safe characters.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShellWordEncoder;
+
+impl OutputEncoder for ShellWordEncoder {
+    fn encode<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        let safe = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+        if !text.is_empty() && text.chars().all(safe) {
+            return Cow::Borrowed(text);
+        }
+        Cow::Owned(format!("'{}'", text.replace('\'', "'\\''")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encoders() {
+        assert_eq!(HtmlEncoder.encode("plain"), "plain");
+        assert_eq!(
+            HtmlEncoder.encode("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        assert_eq!(XmlEncoder.encode("it's <b>"), "it&apos;s &lt;b&gt;");
+        assert_eq!(
+            LatexEncoder.encode("50% of $10_000 & {x}\\"),
+            "50\\% of \\$10\\_000 \\& \\{x\\}\\textbackslash{}"
+        );
+        assert_eq!(CsvFieldEncoder.encode("plain text"), "plain text");
+        assert_eq!(
+            CsvFieldEncoder.encode("say \"hi\", bye"),
+            "\"say \"\"hi\"\", bye\""
+        );
+        assert_eq!(ShellWordEncoder.encode("file-1.txt"), "file-1.txt");
+        assert_eq!(ShellWordEncoder.encode(""), "''");
+        assert_eq!(ShellWordEncoder.encode("it's $HOME"), "'it'\\''s $HOME'");
+    }
+
+    #[test]
+    fn encoders_accept() {
+        assert!(HtmlEncoder.accepts("html"));
+        assert!(XmlEncoder.accepts("html"));
+        assert!(!LatexEncoder.accepts("html"));
+        assert!(!CsvFieldEncoder.accepts("csv"));
+    }
+}
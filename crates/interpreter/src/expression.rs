@@ -1,11 +1,14 @@
 use std::fmt;
 
+use kstring::KString;
 use liquid_error::Result;
+use liquid_value::Object;
 use liquid_value::Scalar;
 use liquid_value::Value;
 use liquid_value::ValueCow;
 use liquid_value::ValueView;
 
+use super::Lookup;
 use super::Runtime;
 use crate::variable::Variable;
 
@@ -16,6 +19,12 @@ pub enum Expression {
     Variable(Variable),
     /// Evaluated.
     Literal(Value),
+    /// Array literal with un-evaluated elements.
+    Array(Vec<Expression>),
+    /// Object literal with un-evaluated values.
+    Object(Vec<(KString, Expression)>),
+    /// String built by appending each part's string representation.
+    Interpolation(Vec<Expression>),
 }
 
 impl Expression {
@@ -24,19 +33,50 @@ impl Expression {
         Expression::Literal(Value::scalar(literal))
     }
 
+    /// Create an array expression, folding it into a literal when every element is one.
+    pub fn with_array(elements: Vec<Expression>) -> Self {
+        if elements.iter().all(|e| e.as_literal().is_some()) {
+            let values = elements.into_iter().filter_map(Expression::into_literal);
+            Expression::Literal(Value::array(values))
+        } else {
+            Expression::Array(elements)
+        }
+    }
+
+    /// Create an object expression, folding it into a literal when every value is one.
+    pub fn with_object(entries: Vec<(KString, Expression)>) -> Self {
+        if entries.iter().all(|(_, e)| e.as_literal().is_some()) {
+            let object: Object = entries
+                .into_iter()
+                .filter_map(|(k, e)| e.into_literal().map(|v| (k, v)))
+                .collect();
+            Expression::Literal(Value::Object(object))
+        } else {
+            Expression::Object(entries)
+        }
+    }
+
+    /// Access the literal, if this is one.
+    pub fn as_literal(&self) -> Option<&Value> {
+        match self {
+            Expression::Literal(x) => Some(x),
+            _ => None,
+        }
+    }
+
     /// Convert into a literal if possible.
     pub fn into_literal(self) -> Option<Value> {
         match self {
             Expression::Literal(x) => Some(x),
-            Expression::Variable(_) => None,
+            _ => None,
         }
     }
 
     /// Convert into a variable, if possible.
     pub fn into_variable(self) -> Option<Variable> {
         match self {
-            Expression::Literal(_) => None,
             Expression::Variable(x) => Some(x),
+            _ => None,
         }
     }
 
@@ -48,6 +88,40 @@ impl Expression {
                 let path = x.try_evaluate(runtime)?;
                 runtime.stack().try_get(&path)
             }
+            Expression::Array(ref x) => {
+                let values: Option<Vec<_>> = x
+                    .iter()
+                    .map(|e| e.try_evaluate(runtime).map(|v| v.into_owned()))
+                    .collect();
+                Some(ValueCow::Owned(Value::Array(values?)))
+            }
+            Expression::Object(ref x) => {
+                let object: Option<Object> = x
+                    .iter()
+                    .map(|(k, e)| e.try_evaluate(runtime).map(|v| (k.clone(), v.into_owned())))
+                    .collect();
+                Some(ValueCow::Owned(Value::Object(object?)))
+            }
+            Expression::Interpolation(ref x) => {
+                let mut result = String::new();
+                for part in x {
+                    result.push_str(part.try_evaluate(runtime)?.to_kstr().as_str());
+                }
+                Some(ValueCow::Owned(Value::scalar(result)))
+            }
+        }
+    }
+
+    /// Convert to a `Value`, telling apart a variable that is undefined from one set to `nil`.
+    ///
+    /// Variables used as indexes, like `b` in `a[b]`, must still be defined, like with `evaluate`.
+    pub fn lookup<'c>(&'c self, runtime: &'c Runtime<'_>) -> Result<Lookup<'c>> {
+        match self {
+            Expression::Variable(ref x) => {
+                let path = x.evaluate(runtime)?;
+                Ok(runtime.stack().lookup(&path))
+            }
+            _ => self.evaluate(runtime).map(Lookup::Defined),
         }
     }
 
@@ -59,6 +133,27 @@ impl Expression {
                 let path = x.evaluate(runtime)?;
                 runtime.stack().get(&path)?
             }
+            Expression::Array(ref x) => {
+                let values: Result<Vec<_>> = x
+                    .iter()
+                    .map(|e| e.evaluate(runtime).map(|v| v.into_owned()))
+                    .collect();
+                ValueCow::Owned(Value::Array(values?))
+            }
+            Expression::Object(ref x) => {
+                let object: Result<Object> = x
+                    .iter()
+                    .map(|(k, e)| e.evaluate(runtime).map(|v| (k.clone(), v.into_owned())))
+                    .collect();
+                ValueCow::Owned(Value::Object(object?))
+            }
+            Expression::Interpolation(ref x) => {
+                let mut result = String::new();
+                for part in x {
+                    result.push_str(part.evaluate(runtime)?.to_kstr().as_str());
+                }
+                ValueCow::Owned(Value::scalar(result))
+            }
         };
         Ok(val)
     }
@@ -69,6 +164,22 @@ impl fmt::Display for Expression {
         match self {
             Expression::Literal(ref x) => write!(f, "{}", x.source()),
             Expression::Variable(ref x) => write!(f, "{}", x),
+            Expression::Array(ref x) => write!(f, "[{}]", itertools::join(x, ", ")),
+            Expression::Object(ref x) => write!(
+                f,
+                "{{{}}}",
+                itertools::join(x.iter().map(|(k, e)| format!(r#""{}": {}"#, k, e)), ", ")
+            ),
+            Expression::Interpolation(ref x) => {
+                write!(f, "\"")?;
+                for part in x {
+                    match part.as_literal() {
+                        Some(literal) => write!(f, "{}", literal.render())?,
+                        None => write!(f, "#{{{}}}", part)?,
+                    }
+                }
+                write!(f, "\"")
+            }
         }
     }
 }
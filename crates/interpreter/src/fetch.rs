@@ -0,0 +1,175 @@
+use std::fmt;
+use std::time::Duration;
+
+use liquid_error::{Error, Result};
+use url::Url;
+
+/// Default time a fetch may take, see `FetchSandbox::timeout`.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default size of a response, see `FetchSandbox::max_bytes`.
+const DEFAULT_MAX_BYTES: usize = 1024 * 1024;
+
+/// HTTP client for the `fetch` tag, provided by the application.
+///
+/// Implementations must give up after `timeout` and stop reading once a response exceeds
+/// `max_bytes`, so a slow or oversized response can't stall the render.
+pub trait HttpFetcher: fmt::Debug + Send + Sync {
+    /// Fetch the body of `url`.
+    fn fetch(&self, url: &str, timeout: Duration, max_bytes: usize) -> Result<Vec<u8>>;
+}
+
+/// Restricts which URLs the `fetch` tag may request, and how.
+///
+/// Nothing can be fetched until a URL prefix is allowed.  URLs are parsed before being checked,
+/// resolving `.` and `..` segments, and the client is given the parsed URL.
+///
+/// # Examples
+///
+/// ```rust
+/// #[derive(Debug)]
+/// struct Canned;
+///
+/// impl liquid_interpreter::HttpFetcher for Canned {
+///     fn fetch(
+///         &self,
+///         _url: &str,
+///         _timeout: std::time::Duration,
+///         _max_bytes: usize,
+///     ) -> liquid_error::Result<Vec<u8>> {
+///         Ok(b"{\"ok\": true}".to_vec())
+///     }
+/// }
+///
+/// let sandbox =
+///     liquid_interpreter::FetchSandbox::new(Canned).allow("https://status.example.com/");
+/// assert!(sandbox.fetch("https://status.example.com/api").is_ok());
+/// assert!(sandbox.fetch("https://example.com/").is_err());
+/// assert!(sandbox.fetch("https://status.example.com.evil.org/api").is_err());
+/// ```
+#[derive(Debug)]
+pub struct FetchSandbox {
+    client: Box<dyn HttpFetcher>,
+    allowed: Vec<Url>,
+    timeout: Duration,
+    max_bytes: usize,
+}
+
+impl FetchSandbox {
+    /// Fetch through `client`, allowing no URLs yet.
+    pub fn new<C: HttpFetcher + 'static>(client: C) -> Self {
+        Self {
+            client: Box::new(client),
+            allowed: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+
+    /// Allow URLs with the scheme, host and port of `prefix`, and a path within its path.
+    ///
+    /// `https://example.com/api` allows `https://example.com/api/status` but not
+    /// `https://example.com/apiary`.  A `prefix` that isn't a valid URL allows nothing.
+    pub fn allow<S: Into<String>>(mut self, prefix: S) -> Self {
+        if let Ok(prefix) = Url::parse(&prefix.into()) {
+            self.allowed.push(prefix);
+        }
+        self
+    }
+
+    /// Set how long a fetch may take, 5 seconds by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how large a response may be, 1 MiB by default.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Fetch the body of `url`, if allowed.
+    pub fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+        let parsed = Url::parse(url).map_err(|e| {
+            Error::with_msg("Invalid URL")
+                .context("url", url.to_owned())
+                .context("cause", e.to_string())
+        })?;
+        if !self.allowed.iter().any(|prefix| is_within(&parsed, prefix)) {
+            return Err(Error::with_msg("URL is not allowed").context("url", url.to_owned()));
+        }
+        let body = self
+            .client
+            .fetch(parsed.as_str(), self.timeout, self.max_bytes)?;
+        if body.len() > self.max_bytes {
+            return Err(Error::with_msg("Response is too large")
+                .context("url", url.to_owned())
+                .context("max bytes", self.max_bytes.to_string()));
+        }
+        Ok(body)
+    }
+}
+
+/// Whether `url` has the scheme, host and port of `prefix`, and a path within its path.
+fn is_within(url: &Url, prefix: &Url) -> bool {
+    if url.scheme() != prefix.scheme()
+        || url.host_str() != prefix.host_str()
+        || url.port_or_known_default() != prefix.port_or_known_default()
+    {
+        return false;
+    }
+    let (path, base) = (url.path(), prefix.path());
+    if !path.starts_with(base) {
+        return false;
+    }
+    base.ends_with('/') || path.len() == base.len() || path[base.len()..].starts_with('/')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Echo;
+
+    impl HttpFetcher for Echo {
+        fn fetch(&self, url: &str, _timeout: Duration, _max_bytes: usize) -> Result<Vec<u8>> {
+            Ok(url.as_bytes().to_vec())
+        }
+    }
+
+    #[test]
+    fn enforces_allowlist_and_size() {
+        let sandbox = FetchSandbox::new(Echo);
+        assert!(sandbox.fetch("https://example.com/").is_err());
+
+        let sandbox = FetchSandbox::new(Echo)
+            .allow("https://example.com/")
+            .max_bytes(24);
+        assert_eq!(
+            sandbox.fetch("https://example.com/a").unwrap(),
+            b"https://example.com/a".to_vec()
+        );
+        assert!(sandbox.fetch("https://example.com.evil.org/").is_err());
+        assert!(sandbox.fetch("https://example.com/too-long").is_err());
+    }
+
+    #[test]
+    fn compares_normalized_urls() {
+        let sandbox = FetchSandbox::new(Echo).allow("https://example.com/api");
+        assert!(sandbox.fetch("https://example.com/api").is_ok());
+        assert_eq!(
+            sandbox.fetch("https://EXAMPLE.com:443/api/./a").unwrap(),
+            b"https://example.com/api/a".to_vec()
+        );
+        assert!(sandbox.fetch("https://example.com/api/../admin").is_err());
+        assert!(sandbox
+            .fetch("https://example.com/api/%2e%2e/admin")
+            .is_err());
+        assert!(sandbox.fetch("https://example.com/apiary").is_err());
+        assert!(sandbox.fetch("https://example.com:8443/api").is_err());
+        assert!(sandbox.fetch("http://example.com/api").is_err());
+        assert!(sandbox.fetch("not a url").is_err());
+    }
+}
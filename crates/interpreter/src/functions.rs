@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+use std::fmt;
+#[cfg(feature = "async-functions")]
+use std::future::Future;
+#[cfg(feature = "async-functions")]
+use std::pin::Pin;
+#[cfg(feature = "async-functions")]
+use std::sync::Arc;
+#[cfg(feature = "async-functions")]
+use std::task::{Context, Poll, Wake, Waker};
+#[cfg(feature = "async-functions")]
+use std::thread;
+
+use liquid_error::{Error, Result};
+use liquid_value::{Object, Value};
+
+#[cfg(feature = "async-functions")]
+type BoxedFuture = Pin<Box<dyn Future<Output = Result<Value>> + Send>>;
+
+enum HostFunction {
+    Sync(Box<dyn Fn(&Object) -> Result<Value> + Send + Sync>),
+    #[cfg(feature = "async-functions")]
+    Async(Box<dyn Fn(Object) -> BoxedFuture + Send + Sync>),
+}
+
+type AuditHook = Box<dyn Fn(&str, &Object) + Send + Sync>;
+
+/// Host functions that templates may call with the `call` tag.
+///
+/// Templates can only reach the functions registered here, by name, like `pricing.quote`.  Each
+/// call receives the tag's named arguments as an `Object`.
+///
+/// # Examples
+///
+/// ```rust
+/// use liquid_value::ValueView;
+///
+/// let mut functions = liquid_interpreter::FunctionRegistry::new();
+/// functions.register("pricing.quote", |args| {
+///     let sku = args.get("sku").map(|v| v.render().to_string()).unwrap_or_default();
+///     Ok(liquid_value::Value::scalar(format!("{}: 4.20", sku)))
+/// });
+/// let args = liquid_value::object!({"sku": "ABC"});
+/// assert_eq!(
+///     functions.call("pricing.quote", &args).unwrap(),
+///     liquid_value::value!("ABC: 4.20")
+/// );
+/// ```
+#[derive(Default)]
+pub struct FunctionRegistry {
+    functions: BTreeMap<String, HostFunction>,
+    audit: Option<AuditHook>,
+}
+
+impl FunctionRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a function under `name`, replacing any function of that name.
+    pub fn register<S, F>(&mut self, name: S, f: F)
+    where
+        S: Into<String>,
+        F: Fn(&Object) -> Result<Value> + Send + Sync + 'static,
+    {
+        self.functions
+            .insert(name.into(), HostFunction::Sync(Box::new(f)));
+    }
+
+    /// Register an `async` function under `name`, replacing any function of that name.
+    ///
+    /// Rendering is synchronous, so the render waits for the future on the current thread.  A
+    /// future that needs a particular executor's reactor, like for network IO, should be spawned
+    /// onto that executor by the function and its handle awaited instead.
+    ///
+    /// Needs the `async-functions` feature, and Rust 1.51.
+    #[cfg(feature = "async-functions")]
+    pub fn register_async<S, F, Fut>(&mut self, name: S, f: F)
+    where
+        S: Into<String>,
+        F: Fn(Object) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value>> + Send + 'static,
+    {
+        let f = move |args| -> BoxedFuture { Box::pin(f(args)) };
+        self.functions
+            .insert(name.into(), HostFunction::Async(Box::new(f)));
+    }
+
+    /// Report every call, with its name and arguments, to `hook` before it is made, like for an
+    /// audit log.
+    pub fn on_call<F>(&mut self, hook: F)
+    where
+        F: Fn(&str, &Object) + Send + Sync + 'static,
+    {
+        self.audit = Some(Box::new(hook));
+    }
+
+    /// Names of the registered functions.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(|name| name.as_str())
+    }
+
+    /// Call the function `name` with `args`.
+    pub fn call(&self, name: &str, args: &Object) -> Result<Value> {
+        let function = self.functions.get(name).ok_or_else(|| {
+            let available = itertools::join(self.names(), ", ");
+            Error::with_msg("Unknown function")
+                .context("requested function", name.to_owned())
+                .context("available functions", available)
+        })?;
+        if let Some(ref audit) = self.audit {
+            audit(name, args);
+        }
+        match function {
+            HostFunction::Sync(f) => f(args),
+            #[cfg(feature = "async-functions")]
+            HostFunction::Async(f) => block_on(f(args.clone())),
+        }
+    }
+}
+
+impl fmt::Debug for FunctionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FunctionRegistry")
+            .field("functions", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(feature = "async-functions")]
+struct ThreadWaker(thread::Thread);
+
+#[cfg(feature = "async-functions")]
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+#[cfg(feature = "async-functions")]
+fn block_on(mut future: BoxedFuture) -> Result<Value> {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use liquid_value::ValueView;
+
+    #[test]
+    fn calls_registered_functions() {
+        let mut functions = FunctionRegistry::new();
+        functions.register("math.double", |args| {
+            let n = args
+                .get("n")
+                .and_then(|n| n.as_scalar())
+                .and_then(|n| n.to_integer())
+                .unwrap_or(0);
+            Ok(Value::scalar(n * 2))
+        });
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let log = calls.clone();
+        functions.on_call(move |name, _args| log.lock().unwrap().push(name.to_owned()));
+
+        let args = liquid_value::object!({"n": 7});
+        assert_eq!(
+            functions.call("math.double", &args).unwrap(),
+            Value::scalar(14)
+        );
+        assert!(functions.call("math.half", &args).is_err());
+        assert_eq!(*calls.lock().unwrap(), vec!["math.double"]);
+    }
+
+    #[test]
+    #[cfg(feature = "async-functions")]
+    fn calls_async_functions() {
+        let mut functions = FunctionRegistry::new();
+        functions.register_async("math.triple", |args| async move {
+            let n = args
+                .get("n")
+                .and_then(|n| n.as_scalar())
+                .and_then(|n| n.to_integer())
+                .unwrap_or(0);
+            Ok(Value::scalar(n * 3))
+        });
+
+        let args = liquid_value::object!({"n": 7});
+        assert_eq!(
+            functions.call("math.triple", &args).unwrap(),
+            Value::scalar(21)
+        );
+    }
+}
@@ -3,7 +3,13 @@
 #![warn(missing_docs)]
 #![warn(unused_extern_crates)]
 
+mod coverage;
+mod encoder;
 mod expression;
+mod fetch;
+mod functions;
+mod loader;
+mod missing_filter;
 mod partials;
 mod renderable;
 mod runtime;
@@ -11,7 +17,13 @@ mod stack;
 mod template;
 mod variable;
 
+pub use self::coverage::*;
+pub use self::encoder::*;
 pub use self::expression::*;
+pub use self::fetch::*;
+pub use self::functions::*;
+pub use self::loader::*;
+pub use self::missing_filter::*;
 pub use self::partials::*;
 pub use self::renderable::*;
 pub use self::runtime::*;
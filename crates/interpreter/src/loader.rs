@@ -0,0 +1,74 @@
+use std::fmt;
+
+use liquid_error::Result;
+
+/// Resolves fetches for lazily-loaded values in batches.
+///
+/// Lazy `ObjectView`s share a handle to the loader and register the data they will need ahead of
+/// time, like when their parent collection is read.  The runtime flushes the loader at block
+/// boundaries, resolving everything registered so far together, so rendering a grid of values
+/// takes one fetch rather than one per cell.
+pub trait Loader: fmt::Debug {
+    /// Resolve all pending fetches.
+    fn flush(&self) -> Result<()>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use crate::Renderable;
+    use crate::Runtime;
+    use crate::RuntimeBuilder;
+    use crate::Template;
+
+    #[derive(Debug, Default)]
+    struct BatchLoader {
+        pending: Mutex<Vec<&'static str>>,
+        batches: Mutex<Vec<Vec<&'static str>>>,
+    }
+
+    impl Loader for BatchLoader {
+        fn flush(&self) -> Result<()> {
+            let pending: Vec<_> = self.pending.lock().unwrap().drain(..).collect();
+            if !pending.is_empty() {
+                self.batches.lock().unwrap().push(pending);
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct Fetch(Arc<BatchLoader>, &'static str);
+
+    impl Renderable for Fetch {
+        fn render_to(&self, _writer: &mut dyn Write, _runtime: &mut Runtime<'_>) -> Result<()> {
+            self.0.pending.lock().unwrap().push(self.1);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flushes_at_block_boundaries() {
+        let loader = Arc::new(BatchLoader::default());
+        let block = Template::new(vec![Box::new(Fetch(loader.clone(), "c"))]);
+        let template = Template::new(vec![
+            Box::new(Fetch(loader.clone(), "a")),
+            Box::new(Fetch(loader.clone(), "b")),
+            Box::new(block),
+        ]);
+
+        let mut runtime = RuntimeBuilder::new().set_loader(loader.as_ref()).build();
+        template.render_to(&mut Vec::new(), &mut runtime).unwrap();
+        assert_eq!(*loader.batches.lock().unwrap(), vec![vec!["a", "b"]]);
+
+        runtime.flush_loader().unwrap();
+        assert_eq!(
+            *loader.batches.lock().unwrap(),
+            vec![vec!["a", "b"], vec!["c"]]
+        );
+    }
+}
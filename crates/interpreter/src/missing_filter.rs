@@ -0,0 +1,62 @@
+use std::fmt;
+
+use liquid_error::Result;
+use liquid_value::{Object, Value, ValueView};
+
+type HandlerFn = dyn Fn(&str, &dyn ValueView, &[Value], &Object) -> Result<Value> + Send + Sync;
+
+/// Resolves the filters a template uses that the parser doesn't know, see
+/// `RuntimeBuilder::set_missing_filter_handler`.
+///
+/// Templates only parse with unknown filters when the language defers them, like with
+/// `ParserBuilder::defer_unknown_filters`.  The handler gets the filter's name, its input and
+/// its evaluated positional and keyword arguments, like to proxy the filter to a scripting
+/// engine, or to log it and return the input as is.
+///
+/// # Examples
+///
+/// ```rust
+/// use liquid_value::{Object, Value, ValueView};
+///
+/// let identity = liquid_interpreter::MissingFilterHandler::new(|name, input, _, _| {
+///     eprintln!("Unknown filter `{}`", name);
+///     Ok(input.to_value())
+/// });
+/// let output = identity
+///     .call("shout", &Value::scalar("hi"), &[], &Object::new())
+///     .unwrap();
+/// assert_eq!(output, Value::scalar("hi"));
+/// ```
+pub struct MissingFilterHandler {
+    handler: Box<HandlerFn>,
+}
+
+impl MissingFilterHandler {
+    /// Create a handler calling `f` with the filter's name, input, and positional and keyword
+    /// arguments.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&str, &dyn ValueView, &[Value], &Object) -> Result<Value> + Send + Sync + 'static,
+    {
+        Self {
+            handler: Box::new(f),
+        }
+    }
+
+    /// Apply the filter `name` to `input`.
+    pub fn call(
+        &self,
+        name: &str,
+        input: &dyn ValueView,
+        positional: &[Value],
+        keyword: &Object,
+    ) -> Result<Value> {
+        (self.handler)(name, input, positional, keyword)
+    }
+}
+
+impl fmt::Debug for MissingFilterHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MissingFilterHandler").finish()
+    }
+}
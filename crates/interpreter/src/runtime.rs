@@ -1,13 +1,28 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
 use std::sync;
 
 use anymap;
 use liquid_error::Error;
 use liquid_error::Result;
-use liquid_value::ObjectView;
-
+use liquid_value::{Object, ObjectView, Value, ValueView};
+
+use super::Coverage;
+use super::FetchSandbox;
+use super::FunctionRegistry;
+use super::Loader;
+use super::MissingFilterHandler;
+use super::OutputEncoder;
 use super::PartialStore;
 use super::Renderable;
 use super::Stack;
+use super::UndefinedPolicy;
+
+/// Variable holding the errors collected by a lax render, see `RuntimeBuilder::lax`.
+pub const RENDER_ERRORS: &str = "render_errors";
 
 /// Block processing interrupt state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -47,6 +62,46 @@ impl InterruptState {
     }
 }
 
+/// Resources used by a render so far, see `Runtime::usage`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuntimeUsage {
+    /// Number of scopes entered, like by `for` blocks and includes.
+    pub scope_depth: usize,
+    /// Number of registers in use by plugins.
+    pub registers: usize,
+    /// Bytes written to the output, see `Runtime::count_output`.
+    pub bytes_written: usize,
+}
+
+/// Writer that counts the bytes written for `RuntimeUsage::bytes_written`.
+///
+/// Fails once the output would grow past `RuntimeBuilder::set_max_output`.
+pub struct CountingWriter<'w> {
+    writer: &'w mut dyn Write,
+    written: Rc<Cell<usize>>,
+    max_output: Option<usize>,
+}
+
+impl<'w> Write for CountingWriter<'w> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(max_output) = self.max_output {
+            if max_output < self.written.get() + buf.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("Output size limit of {} bytes exceeded", max_output),
+                ));
+            }
+        }
+        let written = self.writer.write(buf)?;
+        self.written.set(self.written.get() + written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 struct NullPartials;
 
@@ -72,6 +127,22 @@ impl PartialStore for NullPartials {
 pub struct RuntimeBuilder<'g> {
     globals: Option<&'g dyn ObjectView>,
     partials: Option<&'g dyn PartialStore>,
+    loader: Option<&'g dyn Loader>,
+    encoder: Option<&'g dyn OutputEncoder>,
+    functions: Option<&'g FunctionRegistry>,
+    fetcher: Option<&'g FetchSandbox>,
+    coverage: Option<&'g Coverage>,
+    missing_filters: Option<&'g MissingFilterHandler>,
+    registers: anymap::AnyMap,
+    record_accesses: bool,
+    case_insensitive: bool,
+    frozen_globals: bool,
+    undefined_policy: UndefinedPolicy,
+    aliases: Vec<(String, String)>,
+    lax: bool,
+    tag_limits: HashMap<String, usize>,
+    max_iterations: Option<usize>,
+    max_output: Option<usize>,
 }
 
 impl<'g> RuntimeBuilder<'g> {
@@ -80,6 +151,22 @@ impl<'g> RuntimeBuilder<'g> {
         Self {
             globals: None,
             partials: None,
+            loader: None,
+            encoder: None,
+            functions: None,
+            fetcher: None,
+            coverage: None,
+            missing_filters: None,
+            registers: anymap::AnyMap::new(),
+            record_accesses: false,
+            case_insensitive: false,
+            frozen_globals: false,
+            undefined_policy: UndefinedPolicy::default(),
+            aliases: Vec::new(),
+            lax: false,
+            tag_limits: HashMap::new(),
+            max_iterations: None,
+            max_output: None,
         }
     }
 
@@ -95,18 +182,153 @@ impl<'g> RuntimeBuilder<'g> {
         self
     }
 
+    /// Initialize the loader for batching fetches of lazy values, see `Runtime::flush_loader`.
+    pub fn set_loader(mut self, loader: &'g dyn Loader) -> Self {
+        self.loader = Some(loader);
+        self
+    }
+
+    /// Initialize the encoder escaping the output of `{{ }}`, see `Runtime::output_encoder`.
+    pub fn set_output_encoder(mut self, encoder: &'g dyn OutputEncoder) -> Self {
+        self.encoder = Some(encoder);
+        self
+    }
+
+    /// Initialize the host functions callable by the `call` tag, see `Runtime::functions`.
+    pub fn set_functions(mut self, functions: &'g FunctionRegistry) -> Self {
+        self.functions = Some(functions);
+        self
+    }
+
+    /// Initialize the sandbox the `fetch` tag requests URLs through, see `Runtime::fetcher`.
+    pub fn set_fetcher(mut self, fetcher: &'g FetchSandbox) -> Self {
+        self.fetcher = Some(fetcher);
+        self
+    }
+
+    /// Initialize the handler of filters the parser didn't know, see
+    /// `Runtime::missing_filter_handler`.
+    pub fn set_missing_filter_handler(mut self, handler: &'g MissingFilterHandler) -> Self {
+        self.missing_filters = Some(handler);
+        self
+    }
+
+    /// Record the tags, blocks and outputs that run into `coverage`, see `Runtime::cover`.
+    pub fn set_coverage(mut self, coverage: &'g Coverage) -> Self {
+        self.coverage = Some(coverage);
+        self
+    }
+
+    /// Initialize a register, see `Runtime::get_register`.
+    ///
+    /// This lets plugins be configured per-render, like with a default locale.
+    pub fn set_register<T: anymap::any::IntoBox<dyn anymap::any::Any>>(mut self, value: T) -> Self {
+        self.registers.insert(value);
+        self
+    }
+
+    /// Record the paths read from the globals, see `Stack::accessed`.
+    pub fn record_accesses(mut self) -> Self {
+        self.record_accesses = true;
+        self
+    }
+
+    /// Fall back to matching variables and keys ignoring case, see `Stack::warnings`.
+    pub fn case_insensitive_lookup(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Reject assignments to variables of the globals, see `Stack::freeze_globals`.
+    pub fn freeze_globals(mut self) -> Self {
+        self.frozen_globals = true;
+        self
+    }
+
+    /// Set how undefined variables are handled, see `UndefinedPolicy`.
+    pub fn set_undefined_policy(mut self, policy: UndefinedPolicy) -> Self {
+        self.undefined_policy = policy;
+        self
+    }
+
+    /// Look up `to` when `from` is missing, see `Stack::alias`.
+    pub fn set_alias<S: Into<String>>(mut self, from: S, to: S) -> Self {
+        self.aliases.push((from.into(), to.into()));
+        self
+    }
+
+    /// Keep rendering after an element fails, collecting its error instead.
+    ///
+    /// Templates can list the errors collected so far through the `render_errors` variable, each
+    /// with a `message` and the full `details`.
+    pub fn lax(mut self) -> Self {
+        self.lax = true;
+        self
+    }
+
+    /// Fail the render once the tag or block `name` runs more than `max` times, see
+    /// `Runtime::count_tag`.
+    pub fn set_tag_limit<S: Into<String>>(mut self, name: S, max: usize) -> Self {
+        self.tag_limits.insert(name.into(), max);
+        self
+    }
+
+    /// Fail the render once loops, like `for` and `tablerow`, run more than `max` iterations in
+    /// total, see `Runtime::count_iteration`.
+    pub fn set_max_iterations(mut self, max: usize) -> Self {
+        self.max_iterations = Some(max);
+        self
+    }
+
+    /// Fail the render once more than `bytes` are written to the output, see
+    /// `Runtime::count_output`.
+    pub fn set_max_output(mut self, bytes: usize) -> Self {
+        self.max_output = Some(bytes);
+        self
+    }
+
     /// Create the `Runtime`.
     pub fn build(self) -> Runtime<'g> {
-        let stack = match self.globals {
+        let mut stack = match self.globals {
             Some(globals) => Stack::with_globals(globals),
             None => Stack::empty(),
         };
+        if self.record_accesses {
+            stack.record_accesses();
+        }
+        if self.case_insensitive {
+            stack.case_insensitive_lookup();
+        }
+        if self.frozen_globals {
+            stack.freeze_globals();
+        }
+        stack.set_undefined_policy(self.undefined_policy);
+        for (from, to) in self.aliases.iter() {
+            stack.alias(from, to);
+        }
         let partials = self.partials.unwrap_or(&NullPartials);
         Runtime {
             stack,
             partials,
-            registers: anymap::AnyMap::new(),
+            loader: self.loader,
+            encoder: self.encoder,
+            functions: self.functions,
+            fetcher: self.fetcher,
+            coverage: self.coverage,
+            missing_filters: self.missing_filters,
+            registers: self.registers,
+            render_registers: RefCell::new(anymap::AnyMap::new()),
             interrupt: InterruptState::default(),
+            lax: self.lax,
+            errors: Vec::new(),
+            metadata: Object::new(),
+            slots: HashMap::new(),
+            written: Rc::new(Cell::new(0)),
+            tag_limits: self.tag_limits,
+            tag_counts: HashMap::new(),
+            max_iterations: self.max_iterations,
+            iterations: 0,
+            max_output: self.max_output,
         }
     }
 }
@@ -118,12 +340,40 @@ impl<'g> Default for RuntimeBuilder<'g> {
 }
 
 /// Processing runtime for a template.
+///
+/// # Registers
+///
+/// Plugins keep state in registers, one per type:
+/// - Per-runtime registers, from `RuntimeBuilder::set_register` and `get_register_mut`, hold
+///   configuration like the `Locale`, and are kept for the life of the runtime.
+/// - Per-render registers, from `with_render_register`, hold the state of a render like `cycle`
+///   positions, and are cleared by `reset`.
+///
+/// A runtime reused across renders, like from a pool, should be `reset` between them so state
+/// doesn't bleed from one render to the next.
 pub struct Runtime<'g> {
     stack: Stack<'g>,
     partials: &'g dyn PartialStore,
+    loader: Option<&'g dyn Loader>,
+    encoder: Option<&'g dyn OutputEncoder>,
+    functions: Option<&'g FunctionRegistry>,
+    fetcher: Option<&'g FetchSandbox>,
+    coverage: Option<&'g Coverage>,
+    missing_filters: Option<&'g MissingFilterHandler>,
 
     registers: anymap::AnyMap,
+    render_registers: RefCell<anymap::AnyMap>,
     interrupt: InterruptState,
+    lax: bool,
+    errors: Vec<Error>,
+    metadata: Object,
+    slots: HashMap<kstring::KString, String>,
+    written: Rc<Cell<usize>>,
+    tag_limits: HashMap<String, usize>,
+    tag_counts: HashMap<String, usize>,
+    max_iterations: Option<usize>,
+    iterations: usize,
+    max_output: Option<usize>,
 }
 
 impl<'g> Runtime<'g> {
@@ -144,21 +394,212 @@ impl<'g> Runtime<'g> {
         &mut self.interrupt
     }
 
+    /// Resources used by the render so far.
+    pub fn usage(&self) -> RuntimeUsage {
+        RuntimeUsage {
+            scope_depth: self.stack.depth(),
+            registers: self.registers.len() + self.render_registers.borrow().len(),
+            bytes_written: self.written.get(),
+        }
+    }
+
+    /// Wrap the output of the render, to count the bytes written to it.
+    ///
+    /// Writes fail once they would pass `RuntimeBuilder::set_max_output`.
+    pub fn count_output<'w>(&self, writer: &'w mut dyn Write) -> CountingWriter<'w> {
+        CountingWriter {
+            writer,
+            written: self.written.clone(),
+            max_output: self.max_output,
+        }
+    }
+
+    /// Record a run of the element at `position` of the current template, when coverage is
+    /// enabled, see `RuntimeBuilder::set_coverage`.
+    pub fn cover(&self, position: &str) {
+        if let Some(coverage) = self.coverage {
+            let template = self.stack.frame_name();
+            let template = template.as_ref().map(|t| t.as_str()).unwrap_or("");
+            coverage.record(template, position);
+        }
+    }
+
+    /// Count a run of the tag or block `name`, failing when it is over its limit, see
+    /// `RuntimeBuilder::set_tag_limit`.
+    pub fn count_tag(&mut self, name: &str) -> Result<()> {
+        let allowed = match self.tag_limits.get(name) {
+            Some(&allowed) => allowed,
+            None => return Ok(()),
+        };
+        let count = self.tag_counts.entry(name.to_owned()).or_insert(0);
+        *count += 1;
+        if allowed < *count {
+            return Err(Error::with_msg("Tag limit exceeded")
+                .context("tag", name.to_owned())
+                .context("allowed", allowed.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Count an iteration of a loop, failing when the render is over its limit, see
+    /// `RuntimeBuilder::set_max_iterations`.
+    pub fn count_iteration(&mut self) -> Result<()> {
+        let allowed = match self.max_iterations {
+            Some(allowed) => allowed,
+            None => return Ok(()),
+        };
+        self.iterations += 1;
+        if allowed < self.iterations {
+            return Err(Error::with_msg("Loop iteration limit exceeded")
+                .context("allowed", allowed.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Whether elements that fail are skipped, see `RuntimeBuilder::lax`.
+    pub fn is_lax(&self) -> bool {
+        self.lax
+    }
+
+    /// Collect the error of a skipped element, exposing it to the template as `render_errors`.
+    ///
+    /// A variable the template or globals define as `render_errors` shadows the errors.
+    pub fn record_error(&mut self, error: Error) {
+        let mut details = Object::new();
+        details.insert("message".into(), Value::scalar(error.message().to_owned()));
+        details.insert("details".into(), Value::scalar(error.to_string()));
+        self.errors.push(error);
+
+        let render_errors = self
+            .stack
+            .provided_mut()
+            .entry(kstring::KString::from_static(RENDER_ERRORS))
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(ref mut render_errors) = *render_errors {
+            render_errors.push(Value::Object(details));
+        }
+    }
+
+    /// Errors collected by a lax render, in the order they happened.
+    pub fn render_errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Information for the application collected by tags, like caching hints.
+    ///
+    /// Unlike variables, it isn't visible to the template.
+    pub fn metadata(&self) -> &Object {
+        &self.metadata
+    }
+
+    /// Information for the application collected by tags, see `Runtime::metadata`.
+    pub fn metadata_mut(&mut self) -> &mut Object {
+        &mut self.metadata
+    }
+
+    /// Named content captured for the application to place, like a page's scripts.
+    pub fn slots(&self) -> Object {
+        self.slots
+            .iter()
+            .map(|(name, content)| (name.clone(), Value::scalar(content.clone())))
+            .collect()
+    }
+
+    /// Append `content` to the slot `name`, see `Runtime::slots`.
+    pub fn append_slot<S: Into<kstring::KString>>(&mut self, name: S, content: &str) {
+        self.slots
+            .entry(name.into())
+            .or_insert_with(String::new)
+            .push_str(content);
+    }
+
     /// Partial templates for inclusion.
     pub fn partials(&self) -> &dyn PartialStore {
         self.partials
     }
 
-    /// Data store for stateful tags/blocks.
+    /// Resolve the fetches registered with the `Loader`, if any.
+    ///
+    /// This is done at the start of every block.
+    pub fn flush_loader(&self) -> Result<()> {
+        match self.loader {
+            Some(loader) => loader.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Encoder escaping the output of `{{ }}`, if any.
+    pub fn output_encoder(&self) -> Option<&dyn OutputEncoder> {
+        self.encoder
+    }
+
+    /// Host functions callable by the `call` tag, if any.
+    pub fn functions(&self) -> Option<&FunctionRegistry> {
+        self.functions
+    }
+
+    /// Sandbox the `fetch` tag requests URLs through, if any.
+    pub fn fetcher(&self) -> Option<&FetchSandbox> {
+        self.fetcher
+    }
+
+    /// Handler of filters the parser didn't know, if any.
+    pub fn missing_filter_handler(&self) -> Option<&MissingFilterHandler> {
+        self.missing_filters
+    }
+
+    /// Forget the state of the previous render, so the runtime can be reused.
+    ///
+    /// Clears the variables and counters written, the per-render registers, errors, metadata,
+    /// slots, tag counts and loop iterations.  The globals, per-runtime registers and options
+    /// are kept.
+    pub fn reset(&mut self) {
+        self.stack.reset();
+        self.render_registers.borrow_mut().clear();
+        self.interrupt = InterruptState::default();
+        self.errors.clear();
+        self.metadata.clear();
+        self.slots.clear();
+        self.written.set(0);
+        self.tag_counts.clear();
+        self.iterations = 0;
+    }
+
+    /// Data store for configuring tags/blocks, kept for the life of the runtime.
     ///
-    /// If a plugin needs state, it creates a `struct State : Default` and accesses it via
-    /// `get_register_mut`.
+    /// For state that changes while rendering, use `with_render_register`.
     pub fn get_register_mut<T: anymap::any::IntoBox<dyn anymap::any::Any> + Default>(
         &mut self,
     ) -> &mut T {
         self.registers.entry::<T>().or_insert_with(Default::default)
     }
 
+    /// Read a register, if it has been set.
+    ///
+    /// Unlike `get_register_mut`, this is available to filters.
+    pub fn get_register<T: anymap::any::IntoBox<dyn anymap::any::Any>>(&self) -> Option<&T> {
+        self.registers.get::<T>()
+    }
+
+    /// Data store for stateful plugins, cleared by `reset`.
+    ///
+    /// If a plugin needs state, it creates a `struct State : Default` and accesses it by calling
+    /// `with_render_register` with a closure taking `&mut State`.  This only needs a shared
+    /// `Runtime`, so filters can use it too.
+    ///
+    /// Fails if `f` accesses the render registers itself.
+    pub fn with_render_register<T, R, F>(&self, f: F) -> Result<R>
+    where
+        T: anymap::any::IntoBox<dyn anymap::any::Any> + Default,
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut registers = self
+            .render_registers
+            .try_borrow_mut()
+            .map_err(|_| Error::with_msg("Render registers are already in use"))?;
+        Ok(f(registers.entry::<T>().or_insert_with(Default::default)))
+    }
+
     /// Access the current `Stack`.
     pub fn stack(&self) -> &Stack<'_> {
         &self.stack
@@ -208,8 +649,25 @@ impl<'g> Default for Runtime<'g> {
         Self {
             stack: Stack::empty(),
             partials: &NullPartials,
+            loader: None,
+            encoder: None,
+            functions: None,
+            fetcher: None,
+            coverage: None,
+            missing_filters: None,
             registers: anymap::AnyMap::new(),
+            render_registers: RefCell::new(anymap::AnyMap::new()),
             interrupt: InterruptState::default(),
+            lax: false,
+            errors: Vec::new(),
+            metadata: Object::new(),
+            slots: HashMap::new(),
+            written: Rc::new(Cell::new(0)),
+            tag_limits: HashMap::new(),
+            tag_counts: HashMap::new(),
+            max_iterations: None,
+            iterations: 0,
+            max_output: None,
         }
     }
 }
@@ -264,4 +722,99 @@ mod test {
             &ValueViewCmp::new(&"some value")
         );
     }
+
+    #[test]
+    fn usage() {
+        let mut rt = RuntimeBuilder::new().set_register(5usize).build();
+        let mut output = Vec::new();
+        write!(rt.count_output(&mut output), "Hello").unwrap();
+        write!(Vec::new(), "Ignored").unwrap();
+
+        rt.run_in_scope(|scope| {
+            let usage = scope.usage();
+            assert_eq!(usage.scope_depth, 1);
+            assert_eq!(usage.registers, 1);
+            assert_eq!(usage.bytes_written, 5);
+        });
+        assert_eq!(rt.usage().scope_depth, 0);
+    }
+
+    #[test]
+    fn reset() {
+        #[derive(Default)]
+        struct Count(usize);
+
+        let mut rt = RuntimeBuilder::new().set_register(5usize).build();
+        rt.with_render_register(|count: &mut Count| count.0 += 1)
+            .unwrap();
+        rt.stack_mut().set_global("assigned", Value::scalar(1));
+        rt.append_slot("scripts", "a.js");
+
+        rt.reset();
+        assert_eq!(rt.get_register::<usize>(), Some(&5));
+        assert_eq!(
+            rt.with_render_register(|count: &mut Count| count.0)
+                .unwrap(),
+            0
+        );
+        assert!(rt.stack().snapshot().assigned().is_empty());
+        assert!(rt.slots().is_empty());
+    }
+
+    #[test]
+    fn render_register_reentrant() {
+        #[derive(Default)]
+        struct Count(usize);
+
+        let rt = Runtime::new();
+        let inner = rt
+            .with_render_register(|_: &mut Count| {
+                rt.with_render_register(|count: &mut Count| count.0)
+            })
+            .unwrap();
+        assert!(inner.is_err());
+    }
+
+    #[test]
+    fn append_slot() {
+        let mut rt = Runtime::new();
+        rt.append_slot("scripts", "<script src=\"a.js\"></script>");
+        rt.append_slot("scripts", "<script src=\"b.js\"></script>");
+        assert_eq!(
+            rt.slots().get("scripts").unwrap(),
+            &Value::scalar("<script src=\"a.js\"></script><script src=\"b.js\"></script>")
+        );
+    }
+
+    #[test]
+    fn tag_limit() {
+        let mut rt = RuntimeBuilder::new().set_tag_limit("include", 2).build();
+        assert!(rt.count_tag("include").is_ok());
+        assert!(rt.count_tag("include").is_ok());
+        assert!(rt.count_tag("include").is_err());
+        assert!(rt.count_tag("if").is_ok());
+    }
+
+    #[test]
+    fn max_iterations() {
+        let mut rt = RuntimeBuilder::new().set_max_iterations(2).build();
+        assert!(rt.count_iteration().is_ok());
+        assert!(rt.count_iteration().is_ok());
+        assert!(rt.count_iteration().is_err());
+        rt.reset();
+        assert!(rt.count_iteration().is_ok());
+    }
+
+    #[test]
+    fn max_output() {
+        let rt = RuntimeBuilder::new().set_max_output(4).build();
+        let mut output = Vec::new();
+        {
+            let mut writer = rt.count_output(&mut output);
+            assert!(writer.write_all(b"abc").is_ok());
+            assert!(writer.write_all(b"de").is_err());
+        }
+        assert_eq!(output, b"abc");
+        assert_eq!(rt.usage().bytes_written, 3);
+    }
 }
@@ -1,11 +1,15 @@
+use std::cell::RefCell;
+
 use itertools;
 use liquid_error::{Error, Result};
-use liquid_value::{Object, ObjectView, PathRef, Scalar, Value, ValueCow, ValueView};
+use liquid_value::{Object, ObjectView, Path, PathRef, Scalar, Value, ValueCow, ValueView};
 
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq)]
 struct Frame {
     name: Option<kstring::KString>,
     data: Object,
+    // Variables bound by the block of the frame, with where they are declared.
+    declared: Vec<(kstring::KString, String)>,
 }
 
 impl Frame {
@@ -16,11 +20,70 @@ impl Frame {
     fn with_name<S: Into<kstring::KString>>(name: S) -> Self {
         Self {
             name: Some(name.into()),
-            data: Object::new(),
+            ..Default::default()
         }
     }
 }
 
+/// Whether a variable is defined, and its value when it is, see `Stack::lookup`.
+///
+/// A variable set to `nil` is `Defined`, unlike one that was never set.
+#[derive(Debug)]
+pub enum Lookup<'s> {
+    /// The variable and every index of its path exist.
+    Defined(ValueCow<'s>),
+    /// The variable, or an index of its path, doesn't exist.
+    Undefined {
+        /// How many leading parts of the path exist, `0` when the variable itself doesn't.
+        defined: usize,
+    },
+}
+
+impl<'s> Lookup<'s> {
+    /// Whether the variable doesn't exist.
+    pub fn is_undefined(&self) -> bool {
+        match self {
+            Lookup::Defined(_) => false,
+            Lookup::Undefined { .. } => true,
+        }
+    }
+
+    /// The value, if the variable exists.
+    pub fn defined(self) -> Option<ValueCow<'s>> {
+        match self {
+            Lookup::Defined(value) => Some(value),
+            Lookup::Undefined { .. } => None,
+        }
+    }
+
+    /// The value, treating an undefined variable as `nil` like lenient lookups do.
+    pub fn unwrap_or_nil(self) -> ValueCow<'s> {
+        self.defined().unwrap_or(ValueCow::Owned(Value::Nil))
+    }
+}
+
+/// How `Stack::get` handles variables that are undefined.
+///
+/// Lenient lookups, like `Stack::try_get`, always treat undefined variables as missing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UndefinedPolicy {
+    /// Fail with an error.
+    Error,
+    /// Use `nil` for an undefined variable or index, like `missing` or `post.missing`, but fail
+    /// on indexing into it, like `missing.title`.
+    Empty,
+    /// Like `Empty`, with a warning, see `Stack::warnings`.
+    Warn,
+    /// Use `nil` for any path through an undefined variable or index, like `missing.a.b`.
+    Chain,
+}
+
+impl Default for UndefinedPolicy {
+    fn default() -> Self {
+        UndefinedPolicy::Error
+    }
+}
+
 /// Stack of variables.
 #[derive(Debug, Clone)]
 pub struct Stack<'g> {
@@ -28,6 +91,16 @@ pub struct Stack<'g> {
     stack: Vec<Frame>,
     // State of variables created through increment or decrement tags.
     indexes: Object,
+    // Variables provided by the runtime, like `render_errors`, shadowed by all others.
+    provided: Object,
+    // Paths read from the globals, when recording.
+    accessed: Option<RefCell<Vec<Path<'static>>>>,
+    case_insensitive: bool,
+    frozen_globals: bool,
+    undefined: UndefinedPolicy,
+    // Old paths, and the paths that replace them.
+    aliases: Vec<(Vec<Scalar>, Vec<Scalar>)>,
+    warnings: RefCell<Vec<String>>,
 }
 
 impl<'g> Stack<'g> {
@@ -36,8 +109,15 @@ impl<'g> Stack<'g> {
         Self {
             globals: None,
             indexes: Object::new(),
+            provided: Object::new(),
             // Mutable frame for globals.
             stack: vec![Frame::new()],
+            accessed: None,
+            case_insensitive: false,
+            frozen_globals: false,
+            undefined: UndefinedPolicy::default(),
+            aliases: Vec::new(),
+            warnings: RefCell::new(Vec::new()),
         }
     }
 
@@ -72,6 +152,11 @@ impl<'g> Stack<'g> {
         };
     }
 
+    /// Number of scopes pushed above the global scope.
+    pub fn depth(&self) -> usize {
+        self.stack.len() - 1
+    }
+
     /// The name of the currently active template.
     pub fn frame_name(&self) -> Option<kstring::KStringRef<'_>> {
         self.stack
@@ -80,30 +165,335 @@ impl<'g> Stack<'g> {
             .find_map(|f| f.name.as_ref().map(|s| s.as_ref()))
     }
 
+    /// Start recording the paths read from the globals, see `accessed`.
+    pub fn record_accesses(&mut self) {
+        if self.accessed.is_none() {
+            self.accessed = Some(RefCell::new(Vec::new()));
+        }
+    }
+
+    /// Paths read from the globals since `record_accesses`, in the order first read.
+    ///
+    /// Values copied out of the globals, like loop items, are only reported by the path they
+    /// were copied from.
+    pub fn accessed(&self) -> Vec<Path<'static>> {
+        self.accessed
+            .as_ref()
+            .map(|accessed| accessed.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    fn record_access(&self, path: PathRef<'_, '_>) {
+        let accessed = match self.accessed {
+            Some(ref accessed) => accessed,
+            None => return,
+        };
+        let key = match path.iter().next() {
+            Some(key) => key.to_kstr(),
+            None => return,
+        };
+        let is_local = self
+            .stack
+            .iter()
+            .any(|frame| frame.data.contains_key(key.as_str()));
+        let is_global = self
+            .globals
+            .map(|g| g.contains_key(key.as_str()))
+            .unwrap_or(false);
+        if is_local || !is_global {
+            return;
+        }
+
+        let mut owned = Path::with_index(path[0].clone().into_owned());
+        owned.extend(path[1..].iter().map(|index| index.clone().into_owned()));
+        let mut accessed = accessed.borrow_mut();
+        if !accessed.contains(&owned) {
+            accessed.push(owned);
+        }
+    }
+
+    /// Fall back to matching variables and object keys ignoring case, with a warning.
+    pub fn case_insensitive_lookup(&mut self) {
+        self.case_insensitive = true;
+    }
+
+    /// Reject assignments to variables of the globals the stack was created with, see
+    /// `try_set_global`.
+    pub fn freeze_globals(&mut self) {
+        self.frozen_globals = true;
+    }
+
+    /// Set how `get` handles variables that are undefined.
+    pub fn set_undefined_policy(&mut self, policy: UndefinedPolicy) {
+        self.undefined = policy;
+    }
+
+    /// Look up `to` when `from` is missing, with a warning, like for a renamed variable.
+    ///
+    /// Paths are separated by `.`, like `user.name`.
+    pub fn alias(&mut self, from: &str, to: &str) {
+        let split = |path: &str| -> Vec<Scalar> {
+            path.split('.')
+                .map(|index| Scalar::new(index.to_owned()))
+                .collect()
+        };
+        self.aliases.push((split(from), split(to)));
+    }
+
+    /// Record that the current scope binds `name`, like a loop variable, declared at `position`.
+    ///
+    /// See `warn_shadowing`.
+    pub fn declare<S: Into<kstring::KString>>(&mut self, name: S, position: &str) {
+        let frame = match self.stack.last_mut() {
+            Some(frame) => frame,
+            None => panic!("Global frame removed."),
+        };
+        frame.declared.push((name.into(), position.to_owned()));
+    }
+
+    /// Warn if setting the global `name`, from `position`, is hidden by a variable declared in
+    /// an enclosing scope, or hides a global the stack was created with.
+    pub fn warn_shadowing(&self, name: &str, position: &str) {
+        let declared = self
+            .stack
+            .iter()
+            .skip(1)
+            .rev()
+            .flat_map(|frame| frame.declared.iter().rev())
+            .find(|(declared, _)| declared.as_str() == name);
+        if let Some((_, declared_at)) = declared {
+            self.warn(format!(
+                "Assigning `{}` at {} has no effect in the scope declaring it at {}",
+                name, position, declared_at
+            ));
+        } else if self.globals.map(|g| g.contains_key(name)).unwrap_or(false) {
+            self.warn(format!(
+                "Assigning `{}` at {} hides the global of the same name",
+                name, position
+            ));
+        }
+    }
+
+    /// Report a problem that doesn't stop rendering, like a deprecated lookup.
+    ///
+    /// Repeats of a warning are dropped.
+    pub fn warn<S: Into<String>>(&self, warning: S) {
+        let warning = warning.into();
+        let mut warnings = self.warnings.borrow_mut();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// Warnings reported so far, in the order first reported.
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.borrow().clone()
+    }
+
+    /// The path with aliases and case-insensitive matches applied.
+    ///
+    /// Returns `None` when nothing was replaced.
+    fn rewrite(&self, path: PathRef<'_, '_>) -> Option<Vec<Scalar>> {
+        let aliased = self.resolve_alias(path);
+        let folded = match aliased {
+            Some(ref aliased) => self.fold_case(aliased),
+            None => self.fold_case(path),
+        };
+        folded.or(aliased)
+    }
+
+    fn resolve_alias(&self, path: PathRef<'_, '_>) -> Option<Vec<Scalar>> {
+        let (from, to) = self.aliases.iter().find(|(from, _)| {
+            from.len() <= path.len()
+                && from
+                    .iter()
+                    .zip(path.iter())
+                    .all(|(f, p)| f.to_kstr() == p.to_kstr())
+        })?;
+        let old = &path[..from.len()];
+        if self
+            .find_path_frame(old)
+            .and_then(|frame| liquid_value::try_find(frame.as_value(), old))
+            .is_some()
+        {
+            return None;
+        }
+
+        let join = |path: &[Scalar]| itertools::join(path.iter().map(ValueView::render), ".");
+        self.warn(format!(
+            "Variable `{}` is deprecated, use `{}`",
+            join(from),
+            join(to)
+        ));
+        let mut aliased = to.clone();
+        aliased.extend(path[from.len()..].iter().map(|i| i.clone().into_owned()));
+        Some(aliased)
+    }
+
+    /// The path with each variable or key that only matches ignoring case replaced by its match.
+    ///
+    /// Returns `None` when nothing was replaced.
+    fn fold_case(&self, path: PathRef<'_, '_>) -> Option<Vec<Scalar>> {
+        if !self.case_insensitive {
+            return None;
+        }
+
+        let mut folded: Vec<Scalar> = path.iter().map(|i| i.clone().into_owned()).collect();
+        let mut changed = false;
+        let root = folded.first()?.to_kstr().into_owned();
+        if self.find_frame(root.as_str()).is_none() {
+            let actual = find_ignoring_case(self.roots().into_iter(), root.as_str())?;
+            self.warn(format!(
+                "Variable `{}` only matches `{}` ignoring case",
+                root, actual
+            ));
+            folded[0] = Scalar::new(actual);
+            changed = true;
+        }
+
+        let frame = self.find_frame(folded[0].to_kstr().as_str())?;
+        for i in 1..folded.len() {
+            let actual = {
+                let parent = match liquid_value::try_find(frame.as_value(), &folded[..i]) {
+                    Some(parent) => parent,
+                    None => break,
+                };
+                let object = match parent.as_object() {
+                    Some(object) => object,
+                    None => continue,
+                };
+                let key = folded[i].to_kstr();
+                if object.contains_key(key.as_str()) {
+                    continue;
+                }
+                match find_ignoring_case(object.keys(), key.as_str()) {
+                    Some(actual) => actual,
+                    None => break,
+                }
+            };
+            let variable = itertools::join(folded[..i].iter().map(ValueView::render), ".");
+            self.warn(format!(
+                "Index `{}` of `{}` only matches `{}` ignoring case",
+                folded[i].to_kstr(),
+                variable,
+                actual
+            ));
+            folded[i] = Scalar::new(actual);
+            changed = true;
+        }
+
+        if changed {
+            Some(folded)
+        } else {
+            None
+        }
+    }
+
     /// Recursively index into the stack.
     pub fn try_get(&self, path: PathRef<'_, '_>) -> Option<ValueCow<'_>> {
-        let frame = self.find_path_frame(path)?;
+        self.lookup(path).defined()
+    }
 
-        liquid_value::try_find(frame.as_value(), path)
+    /// Recursively index into the stack, telling apart a variable that is undefined from one set
+    /// to `nil`.
+    pub fn lookup(&self, path: PathRef<'_, '_>) -> Lookup<'_> {
+        let rewritten = self.rewrite(path);
+        let path = match rewritten {
+            Some(ref rewritten) => rewritten.as_slice(),
+            None => path,
+        };
+        self.record_access(path);
+        self.find_path(path)
     }
 
     /// Recursively index into the stack.
+    ///
+    /// Undefined variables are handled by the `UndefinedPolicy`, failing by default.
     pub fn get(&self, path: PathRef<'_, '_>) -> Result<ValueCow<'_>> {
-        let frame = self.find_path_frame(path).ok_or_else(|| {
-            let key = path
-                .iter()
-                .next()
-                .cloned()
-                .unwrap_or_else(|| Scalar::new("nil"));
-            let globals = itertools::join(self.roots().iter(), ", ");
-            Error::with_msg("Unknown variable")
-                .context("requested variable", key.to_kstr())
-                .context("available variables", globals)
-        })?;
+        let rewritten = self.rewrite(path);
+        let path = match rewritten {
+            Some(ref rewritten) => rewritten.as_slice(),
+            None => path,
+        };
+        self.record_access(path);
+        match self.find_path(path) {
+            Lookup::Defined(value) => Ok(value),
+            Lookup::Undefined { defined } => match self.undefined_value(path, defined) {
+                Some(value) => Ok(value),
+                None => self.undefined_error(path, defined),
+            },
+        }
+    }
+
+    fn find_path(&self, path: PathRef<'_, '_>) -> Lookup<'_> {
+        let frame = match self.find_path_frame(path) {
+            Some(frame) => frame,
+            None => return Lookup::Undefined { defined: 0 },
+        };
+        let mut value = ValueCow::Borrowed(frame.as_value());
+        for (defined, index) in path.iter().enumerate().skip(1) {
+            let index = std::slice::from_ref(index);
+            let child = match value {
+                ValueCow::Borrowed(parent) => liquid_value::try_find(parent, index),
+                ValueCow::Owned(ref parent) => liquid_value::try_find(parent, index)
+                    .map(|child| ValueCow::Owned(child.into_owned())),
+            };
+            value = match child {
+                Some(child) => child,
+                None => return Lookup::Undefined { defined },
+            };
+        }
+        Lookup::Defined(value)
+    }
+
+    /// The error for an undefined `path`, telling apart indexing into `nil` from a missing index.
+    fn undefined_error(&self, path: PathRef<'_, '_>, defined: usize) -> Result<ValueCow<'_>> {
+        let frame = match self.find_path_frame(path) {
+            Some(frame) => frame,
+            None => {
+                let key = path
+                    .iter()
+                    .next()
+                    .cloned()
+                    .unwrap_or_else(|| Scalar::new("nil"));
+                let globals = itertools::join(self.roots().iter(), ", ");
+                return Error::with_msg("Unknown variable")
+                    .context("requested variable", key.to_kstr())
+                    .context("available variables", globals)
+                    .into_err();
+            }
+        };
 
+        let parent = liquid_value::try_find(frame.as_value(), &path[..defined]);
+        if parent.map(|parent| parent.is_nil()).unwrap_or(false) {
+            return Error::with_msg("Cannot index into nil")
+                .context(
+                    "variable",
+                    itertools::join(path[..defined].iter().map(ValueView::render), "."),
+                )
+                .context("requested index", path[defined].render().to_string())
+                .into_err();
+        }
         liquid_value::find(frame.as_value(), path)
     }
 
+    /// The value of an undefined `path`, of which `defined` leading parts exist, under the
+    /// `UndefinedPolicy`, or `None` to fail.
+    fn undefined_value(&self, path: PathRef<'_, '_>, defined: usize) -> Option<ValueCow<'_>> {
+        // Whether the path indexes into something undefined.
+        let chained = defined + 1 < path.len();
+        match self.undefined {
+            UndefinedPolicy::Error => return None,
+            UndefinedPolicy::Empty | UndefinedPolicy::Warn if chained => return None,
+            UndefinedPolicy::Empty | UndefinedPolicy::Chain => (),
+            UndefinedPolicy::Warn => self.warn(format!(
+                "Variable `{}` is undefined",
+                itertools::join(path.iter().map(ValueView::render), ".")
+            )),
+        }
+        Some(ValueCow::Owned(Value::Nil))
+    }
+
     fn roots(&self) -> Vec<kstring::KStringCow<'_>> {
         let mut roots = Vec::new();
         if let Some(globals) = self.globals {
@@ -112,6 +502,7 @@ impl<'g> Stack<'g> {
         for frame in self.stack.iter() {
             roots.extend(frame.data.keys().map(kstring::KStringCow::from));
         }
+        roots.extend(self.provided.keys().map(kstring::KStringCow::from));
         roots.sort();
         roots.dedup();
         roots
@@ -138,9 +529,50 @@ impl<'g> Stack<'g> {
             return Some(&self.indexes);
         }
 
+        if self.provided.contains_key(name) {
+            return Some(&self.provided);
+        }
+
         None
     }
 
+    /// Forget the variables, counters and warnings written so far, keeping the globals and
+    /// options.
+    pub fn reset(&mut self) {
+        self.stack = vec![Frame::new()];
+        self.indexes.clear();
+        self.provided.clear();
+        if let Some(ref accessed) = self.accessed {
+            accessed.borrow_mut().clear();
+        }
+        self.warnings.borrow_mut().clear();
+    }
+
+    /// Variables provided by the runtime, like `render_errors`.
+    ///
+    /// Any other variable of the same name shadows them.
+    pub(crate) fn provided_mut(&mut self) -> &mut Object {
+        &mut self.provided
+    }
+
+    /// Capture the variables written so far, to inspect or `restore` later.
+    ///
+    /// Globals the stack was created with are not included.
+    pub fn snapshot(&self) -> StackSnapshot {
+        StackSnapshot {
+            frames: self.stack.clone(),
+            indexes: self.indexes.clone(),
+        }
+    }
+
+    /// Replace the variables written so far with a `snapshot`.
+    pub fn restore(&mut self, snapshot: StackSnapshot) {
+        let StackSnapshot { frames, indexes } = snapshot;
+        assert!(!frames.is_empty(), "Snapshot is missing the global frame.");
+        self.stack = frames;
+        self.indexes = indexes;
+    }
+
     /// Used by increment and decrement tags
     pub fn set_index<S>(&mut self, name: S, val: Value) -> Option<Value>
     where
@@ -163,6 +595,26 @@ impl<'g> Stack<'g> {
         self.global_frame().insert(name, val)
     }
 
+    /// Sets a value in the global runtime, on behalf of the template, like for `assign`.
+    ///
+    /// Fails if the globals are frozen and `name` is one of them.
+    pub fn try_set_global<S>(&mut self, name: S, val: Value) -> Result<Option<Value>>
+    where
+        S: Into<kstring::KString>,
+    {
+        let name = name.into();
+        if self.frozen_globals
+            && self
+                .globals
+                .map(|g| g.contains_key(name.as_str()))
+                .unwrap_or(false)
+        {
+            return Err(Error::with_msg("Cannot assign to a global")
+                .context("variable", name.as_str().to_owned()));
+        }
+        Ok(self.set_global(name, val))
+    }
+
     /// Sets a value to the rendering runtime.
     /// Note that it needs to be wrapped in a liquid::Value.
     ///
@@ -193,6 +645,43 @@ impl<'g> Stack<'g> {
     }
 }
 
+fn find_ignoring_case<'k>(
+    keys: impl Iterator<Item = kstring::KStringCow<'k>>,
+    key: &str,
+) -> Option<String> {
+    let key = key.to_lowercase();
+    keys.map(|k| k.as_str().to_owned())
+        .find(|k| k.to_lowercase() == key)
+}
+
+/// Variables written to a `Stack`, see `Stack::snapshot`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackSnapshot {
+    frames: Vec<Frame>,
+    indexes: Object,
+}
+
+impl StackSnapshot {
+    /// Variables assigned at the top level, like by `{% assign %}` and `{% capture %}`.
+    pub fn assigned(&self) -> &Object {
+        &self.frames[0].data
+    }
+
+    /// Counters created by the `increment` and `decrement` tags.
+    pub fn indexes(&self) -> &Object {
+        &self.indexes
+    }
+
+    /// All variables visible from the innermost scope, with inner scopes shadowing outer ones.
+    pub fn variables(&self) -> Object {
+        let mut variables = self.indexes.clone();
+        for frame in self.frames.iter() {
+            variables.extend(frame.data.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        variables
+    }
+}
+
 impl<'g> Default for Stack<'g> {
     fn default() -> Self {
         Self::empty()
@@ -221,6 +710,64 @@ mod test {
         assert!(stack.find_frame("post.number").is_none());
     }
 
+    #[test]
+    fn stack_lookup_nil() {
+        let mut stack = Stack::empty();
+        let mut post = Object::new();
+        post.insert("author".into(), Value::Nil);
+        stack.set_global("post", Value::Object(post));
+
+        let author = [Scalar::new("post"), Scalar::new("author")];
+        assert_eq!(
+            stack.lookup(&author).defined().map(|v| v.into_owned()),
+            Some(Value::Nil)
+        );
+        let title = [Scalar::new("post"), Scalar::new("title")];
+        assert!(stack.lookup(&title).is_undefined());
+        assert!(stack.lookup(&[Scalar::new("page")]).is_undefined());
+
+        let name = [
+            Scalar::new("post"),
+            Scalar::new("author"),
+            Scalar::new("name"),
+        ];
+        match stack.lookup(&name) {
+            Lookup::Undefined { defined } => assert_eq!(defined, 2),
+            Lookup::Defined(_) => panic!("`post.author.name` is undefined"),
+        }
+        let error = stack.get(&name).unwrap_err();
+        assert_eq!(error.message(), "Cannot index into nil");
+        let error = stack.get(&title).unwrap_err();
+        assert_eq!(error.message(), "Unknown index");
+    }
+
+    #[test]
+    fn stack_get_undefined() {
+        let mut stack = Stack::empty();
+        stack.set_global("post", Value::Object(Object::new()));
+        let title = [Scalar::new("post"), Scalar::new("title")];
+        let chained = [Scalar::new("missing"), Scalar::new("title")];
+        let nested = [
+            Scalar::new("post"),
+            Scalar::new("author"),
+            Scalar::new("name"),
+        ];
+        assert!(stack.get(&title).is_err());
+
+        stack.set_undefined_policy(UndefinedPolicy::Empty);
+        assert_eq!(stack.get(&title).unwrap().into_owned(), Value::Nil);
+        assert!(stack.get(&chained).is_err());
+        assert!(stack.get(&nested).is_err());
+
+        stack.set_undefined_policy(UndefinedPolicy::Warn);
+        assert_eq!(stack.get(&title).unwrap().into_owned(), Value::Nil);
+        assert_eq!(stack.warnings(), ["Variable `post.title` is undefined"]);
+
+        stack.set_undefined_policy(UndefinedPolicy::Chain);
+        assert_eq!(stack.get(&chained).unwrap().into_owned(), Value::Nil);
+        assert_eq!(stack.get(&nested).unwrap().into_owned(), Value::Nil);
+    }
+
     #[test]
     fn stack_get() {
         let mut stack = Stack::empty();
@@ -230,4 +777,110 @@ mod test {
         let indexes = [Scalar::new("post"), Scalar::new("number")];
         assert_eq!(&stack.get(&indexes).unwrap(), &ValueViewCmp::new(&42f64));
     }
+
+    #[test]
+    fn stack_snapshot() {
+        let mut stack = Stack::empty();
+        stack.set_global("layout", Value::scalar("post"));
+        stack.push_frame();
+        stack.set("item", Value::scalar(1));
+        stack.set_index("counter", Value::scalar(2));
+
+        let snapshot = stack.snapshot();
+        assert_eq!(snapshot.assigned().len(), 1);
+        assert_eq!(snapshot.indexes().len(), 1);
+        assert_eq!(snapshot.variables().len(), 3);
+
+        stack.pop_frame();
+        stack.set_global("layout", Value::scalar("page"));
+        stack.restore(snapshot.clone());
+        assert_eq!(stack.snapshot(), snapshot);
+        let indexes = [Scalar::new("item")];
+        assert_eq!(&stack.get(&indexes).unwrap(), &ValueViewCmp::new(&1));
+    }
+
+    #[test]
+    fn stack_warn_shadowing() {
+        let globals = liquid_value::object!({"user": "Ann"});
+        let mut stack = Stack::with_globals(&globals);
+        stack.push_frame();
+        stack.declare("item", "1:8");
+        stack.warn_shadowing("item", "2:11");
+        stack.warn_shadowing("user", "3:11");
+        stack.warn_shadowing("title", "4:11");
+        assert_eq!(
+            stack.warnings(),
+            [
+                "Assigning `item` at 2:11 has no effect in the scope declaring it at 1:8",
+                "Assigning `user` at 3:11 hides the global of the same name",
+            ]
+        );
+    }
+
+    #[test]
+    fn stack_frozen_globals() {
+        let globals = liquid_value::object!({"tenant": "acme"});
+        let mut stack = Stack::with_globals(&globals);
+        assert!(stack
+            .try_set_global("tenant", Value::scalar("evil"))
+            .is_ok());
+
+        let mut stack = Stack::with_globals(&globals);
+        stack.freeze_globals();
+        assert!(stack
+            .try_set_global("tenant", Value::scalar("evil"))
+            .is_err());
+        assert!(stack.try_set_global("title", Value::scalar("Home")).is_ok());
+        let indexes = [Scalar::new("tenant")];
+        assert_eq!(&stack.get(&indexes).unwrap(), &ValueViewCmp::new(&"acme"));
+    }
+
+    #[test]
+    fn stack_accessed() {
+        let globals = liquid_value::object!({"user": {"name": "Ann"}, "posts": [1, 2]});
+        let mut stack = Stack::with_globals(&globals);
+        stack.record_accesses();
+        stack.set_global("local", Value::scalar(1));
+
+        let name = [Scalar::new("user"), Scalar::new("name")];
+        stack.get(&name).unwrap();
+        stack.get(&name).unwrap();
+        stack.try_get(&[Scalar::new("posts")]);
+        stack.try_get(&[Scalar::new("local")]);
+        stack.try_get(&[Scalar::new("missing")]);
+
+        let accessed: Vec<_> = stack.accessed().iter().map(|p| p.to_string()).collect();
+        assert_eq!(accessed, vec!["user.name", "posts"]);
+    }
+
+    #[test]
+    fn stack_case_insensitive() {
+        let globals = liquid_value::object!({"User": {"Name": "Ann"}});
+        let mut stack = Stack::with_globals(&globals);
+        let path = [Scalar::new("user"), Scalar::new("name")];
+        assert!(stack.try_get(&path).is_none());
+
+        stack.case_insensitive_lookup();
+        assert_eq!(&stack.get(&path).unwrap(), &ValueViewCmp::new(&"Ann"));
+        assert_eq!(&stack.get(&path).unwrap(), &ValueViewCmp::new(&"Ann"));
+        assert_eq!(stack.warnings().len(), 2);
+        assert!(stack.try_get(&[Scalar::new("missing")]).is_none());
+    }
+
+    #[test]
+    fn stack_alias() {
+        let globals = liquid_value::object!({"user": {"name": "Ann"}, "title": "Old"});
+        let mut stack = Stack::with_globals(&globals);
+        stack.alias("author", "user.name");
+        stack.alias("title", "user.name");
+
+        let path = [Scalar::new("author")];
+        assert_eq!(&stack.get(&path).unwrap(), &ValueViewCmp::new(&"Ann"));
+        let path = [Scalar::new("title")];
+        assert_eq!(&stack.get(&path).unwrap(), &ValueViewCmp::new(&"Old"));
+        assert_eq!(
+            stack.warnings(),
+            vec!["Variable `author` is deprecated, use `user.name`".to_owned()]
+        );
+    }
 }
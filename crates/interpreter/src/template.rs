@@ -20,8 +20,14 @@ impl Template {
 
 impl Renderable for Template {
     fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        runtime.flush_loader()?;
         for el in &self.elements {
-            el.render_to(writer, runtime)?;
+            if let Err(error) = el.render_to(writer, runtime) {
+                if !runtime.is_lax() {
+                    return Err(error);
+                }
+                runtime.record_error(error);
+            }
 
             // Did the last element we processed set an interrupt? If so, we
             // need to abandon the rest of our child elements and just
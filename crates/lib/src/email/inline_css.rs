@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+use crate::invalid_input;
+
+#[derive(Debug, FilterParameters)]
+struct InlineCssArgs {
+    #[parameter(
+        description = "A stylesheet to inline in addition to the document's own `<style>` tags.",
+        arg_type = "str"
+    )]
+    stylesheet: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "inline_css",
+    description = "Moves CSS rules from `<style>` tags, and an optional stylesheet, into `style` attributes of the HTML elements they match, as many email clients ignore stylesheets. Remote stylesheets are never fetched.",
+    parameters(InlineCssArgs),
+    parsed(InlineCssFilter)
+)]
+pub struct InlineCss;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "inline_css"]
+struct InlineCssFilter {
+    #[parameters]
+    args: InlineCssArgs,
+}
+
+impl Filter for InlineCssFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let stylesheet = args.stylesheet.map(|s| Cow::Owned(s.into_string()));
+        let inliner = css_inline::CSSInliner::options()
+            .load_remote_stylesheets(false)
+            .extra_css(stylesheet)
+            .build();
+
+        let input = input.to_kstr();
+        let inlined = inliner
+            .inline(input.as_str())
+            .map_err(|e| invalid_input(e.to_string()))?;
+        Ok(Value::scalar(inlined))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_inline_css() {
+        let html =
+            "<html><head><style>p { color: red; }</style></head><body><p>Hi</p></body></html>";
+        let inlined = liquid_core::call_filter!(InlineCss, html).unwrap();
+        assert!(inlined
+            .to_kstr()
+            .contains(r#"<p style="color: red;">Hi</p>"#));
+
+        let html = "<html><head></head><body><h1>Order</h1></body></html>";
+        let inlined = liquid_core::call_filter!(InlineCss, html, "h1 { margin: 0 }").unwrap();
+        assert!(inlined
+            .to_kstr()
+            .contains(r#"<h1 style="margin: 0;">Order</h1>"#));
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(feature = "inline-css")]
+mod inline_css;
+
+#[cfg(feature = "inline-css")]
+pub use self::inline_css::*;
+
+use std::fmt::Write;
+
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// Encoded lines, soft line break excluded, are at most this long, see RFC 2045 section 6.7.
+const QP_LINE_LENGTH: usize = 75;
+
+/// Encoded words are at most this long, see RFC 2047 section 2.
+const ENCODED_WORD_LENGTH: usize = 75;
+
+const ENCODED_WORD_START: &str = "=?UTF-8?Q?";
+
+const ENCODED_WORD_END: &str = "?=";
+
+fn push_hex(encoded: &mut String, byte: u8) {
+    write!(encoded, "={:02X}", byte).expect("writing to a string never fails");
+}
+
+fn quoted_printable_line(line: &str, encoded: &mut String) {
+    let bytes = line.as_bytes();
+    let mut length = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        let last = i + 1 == bytes.len();
+        let mut token = String::with_capacity(3);
+        match byte {
+            // Trailing whitespace would be stripped in transport.
+            b' ' | b'\t' if !last => token.push(char::from(byte)),
+            b'=' => push_hex(&mut token, byte),
+            33..=126 => token.push(char::from(byte)),
+            _ => push_hex(&mut token, byte),
+        }
+        if length + token.len() > QP_LINE_LENGTH {
+            encoded.push_str("=\r\n");
+            length = 0;
+        }
+        length += token.len();
+        encoded.push_str(&token);
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "quoted_printable",
+    description = "Encodes an email body as quoted-printable (RFC 2045), with CRLF line breaks and lines of at most 76 characters.",
+    parsed(QuotedPrintableFilter)
+)]
+pub struct QuotedPrintable;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "quoted_printable"]
+struct QuotedPrintableFilter;
+
+impl Filter for QuotedPrintableFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let input = input.to_kstr();
+        let mut encoded = String::with_capacity(input.len() + input.len() / 8);
+        let mut lines = input.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            quoted_printable_line(line.trim_end_matches('\r'), &mut encoded);
+            if lines.peek().is_some() {
+                encoded.push_str("\r\n");
+            }
+        }
+        Ok(Value::scalar(encoded))
+    }
+}
+
+/// Whether `text` can be used in a header as is.
+fn is_plain_header(text: &str) -> bool {
+    !text.contains("=?")
+        && text
+            .bytes()
+            .all(|b| b == b' ' || b == b'\t' || (33..=126).contains(&b))
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "mime_header",
+    description = "Encodes a header value, like a subject, as RFC 2047 encoded words when it isn't plain ASCII. Encoded words are folded onto separate lines.",
+    parsed(MimeHeaderFilter)
+)]
+pub struct MimeHeader;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "mime_header"]
+struct MimeHeaderFilter;
+
+impl Filter for MimeHeaderFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let input = input.to_kstr();
+        if is_plain_header(&input) {
+            return Ok(Value::scalar(input.into_owned()));
+        }
+
+        let payload_length =
+            ENCODED_WORD_LENGTH - ENCODED_WORD_START.len() - ENCODED_WORD_END.len();
+        let mut words = Vec::new();
+        let mut word = String::new();
+        for c in input.chars() {
+            // A character's UTF-8 sequence must not be split across encoded words.
+            let mut token = String::new();
+            match c {
+                ' ' => token.push('_'),
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '!' | '*' | '+' | '-' | '/' => token.push(c),
+                _ => {
+                    let mut buffer = [0; 4];
+                    for &byte in c.encode_utf8(&mut buffer).as_bytes() {
+                        push_hex(&mut token, byte);
+                    }
+                }
+            }
+            if word.len() + token.len() > payload_length {
+                words.push(word.clone());
+                word.clear();
+            }
+            word.push_str(&token);
+        }
+        words.push(word);
+
+        let words: Vec<_> = words
+            .iter()
+            .map(|word| format!("{}{}{}", ENCODED_WORD_START, word, ENCODED_WORD_END))
+            .collect();
+        Ok(Value::scalar(words.join("\r\n ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_quoted_printable() {
+        assert_eq!(
+            liquid_core::call_filter!(QuotedPrintable, "Caf\u{e9} = 5\u{20ac} \nBye\t").unwrap(),
+            liquid_core::value!("Caf=C3=A9 =3D 5=E2=82=AC=20\r\nBye=09")
+        );
+
+        let long = "a".repeat(80);
+        assert_eq!(
+            liquid_core::call_filter!(QuotedPrintable, long).unwrap(),
+            liquid_core::value!(format!("{}=\r\n{}", "a".repeat(75), "a".repeat(5)))
+        );
+
+        let long = format!("{}\u{e9}", "a".repeat(73));
+        assert_eq!(
+            liquid_core::call_filter!(QuotedPrintable, long).unwrap(),
+            liquid_core::value!(format!("{}=\r\n=C3=A9", "a".repeat(73)))
+        );
+    }
+
+    #[test]
+    fn unit_mime_header() {
+        assert_eq!(
+            liquid_core::call_filter!(MimeHeader, "Your order #42").unwrap(),
+            liquid_core::value!("Your order #42")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(MimeHeader, "Caf\u{e9} ouvert").unwrap(),
+            liquid_core::value!("=?UTF-8?Q?Caf=C3=A9_ouvert?=")
+        );
+
+        let long = "\u{e9}".repeat(20);
+        let encoded = liquid_core::call_filter!(MimeHeader, long).unwrap();
+        let encoded = encoded.to_kstr();
+        let words: Vec<_> = encoded.split("\r\n ").collect();
+        assert_eq!(words.len(), 2);
+        assert!(words.iter().all(|w| w.len() <= ENCODED_WORD_LENGTH));
+        assert_eq!(words[0], format!("=?UTF-8?Q?{}?=", "=C3=A9".repeat(10)));
+    }
+}
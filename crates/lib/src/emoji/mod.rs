@@ -0,0 +1,59 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+use regex::{Captures, Regex};
+
+mod table;
+
+static SHORTCODE: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r":([a-z0-9_+\-]+):").unwrap());
+
+/// The emoji for `shortcode`, like `tada`, if known.
+pub fn lookup(shortcode: &str) -> Option<&'static str> {
+    table::EMOJI
+        .binary_search_by_key(&shortcode, |&(code, _)| code)
+        .ok()
+        .map(|i| table::EMOJI[i].1)
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "emojify",
+    description = "Convert shortcodes like `:tada:` into emoji. Unknown shortcodes are left as is.",
+    parsed(EmojifyFilter)
+)]
+pub struct Emojify;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "emojify"]
+struct EmojifyFilter;
+
+impl Filter for EmojifyFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let input = input.to_kstr();
+        let output = SHORTCODE.replace_all(input.as_str(), |captures: &Captures<'_>| {
+            lookup(&captures[1]).unwrap_or(&captures[0]).to_owned()
+        });
+        Ok(Value::scalar(output.into_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted() {
+        assert!(table::EMOJI.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn unit_emojify() {
+        assert_eq!(
+            liquid_core::call_filter!(Emojify, "Released :tada: :+1: at 10:30:00 :unknown:")
+                .unwrap(),
+            liquid_core::value!("Released 🎉 👍 at 10:30:00 :unknown:")
+        );
+    }
+}
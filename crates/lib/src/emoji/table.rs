@@ -0,0 +1,176 @@
+/// Emoji by shortcode, sorted by shortcode for binary search.
+///
+/// A subset of the GitHub shortcodes, covering those common in changelogs and chat.
+pub(super) const EMOJI: &[(&str, &str)] = &[
+    ("+1", "\u{1f44d}"),                         // 👍
+    ("-1", "\u{1f44e}"),                         // 👎
+    ("100", "\u{1f4af}"),                        // 💯
+    ("1234", "\u{1f522}"),                       // 🔢
+    ("alarm_clock", "\u{23f0}"),                 // ⏰
+    ("alien", "\u{1f47d}"),                      // 👽
+    ("angry", "\u{1f620}"),                      // 😠
+    ("apple", "\u{1f34e}"),                      // 🍎
+    ("arrow_down", "\u{2b07}\u{fe0f}"),          // ⬇️
+    ("arrow_left", "\u{2b05}\u{fe0f}"),          // ⬅️
+    ("arrow_right", "\u{27a1}\u{fe0f}"),         // ➡️
+    ("arrow_up", "\u{2b06}\u{fe0f}"),            // ⬆️
+    ("art", "\u{1f3a8}"),                        // 🎨
+    ("baby", "\u{1f476}"),                       // 👶
+    ("balloon", "\u{1f388}"),                    // 🎈
+    ("bang", "\u{1f4a5}"),                       // 💥
+    ("beer", "\u{1f37a}"),                       // 🍺
+    ("beers", "\u{1f37b}"),                      // 🍻
+    ("bell", "\u{1f514}"),                       // 🔔
+    ("bike", "\u{1f6b2}"),                       // 🚲
+    ("birthday", "\u{1f382}"),                   // 🎂
+    ("blush", "\u{1f60a}"),                      // 😊
+    ("bomb", "\u{1f4a3}"),                       // 💣
+    ("book", "\u{1f4d6}"),                       // 📖
+    ("bookmark", "\u{1f516}"),                   // 🔖
+    ("books", "\u{1f4da}"),                      // 📚
+    ("boom", "\u{1f4a5}"),                       // 💥
+    ("bow", "\u{1f647}"),                        // 🙇
+    ("broken_heart", "\u{1f494}"),               // 💔
+    ("bug", "\u{1f41b}"),                        // 🐛
+    ("bulb", "\u{1f4a1}"),                       // 💡
+    ("bus", "\u{1f68c}"),                        // 🚌
+    ("cake", "\u{1f370}"),                       // 🍰
+    ("calendar", "\u{1f4c6}"),                   // 📆
+    ("camera", "\u{1f4f7}"),                     // 📷
+    ("car", "\u{1f697}"),                        // 🚗
+    ("cat", "\u{1f431}"),                        // 🐱
+    ("chart_with_downwards_trend", "\u{1f4c9}"), // 📉
+    ("chart_with_upwards_trend", "\u{1f4c8}"),   // 📈
+    ("check", "\u{2714}\u{fe0f}"),               // ✔️
+    ("checkered_flag", "\u{1f3c1}"),             // 🏁
+    ("clap", "\u{1f44f}"),                       // 👏
+    ("clipboard", "\u{1f4cb}"),                  // 📋
+    ("clock1", "\u{1f550}"),                     // 🕐
+    ("cloud", "\u{2601}\u{fe0f}"),               // ☁️
+    ("coffee", "\u{2615}"),                      // ☕
+    ("computer", "\u{1f4bb}"),                   // 💻
+    ("confetti_ball", "\u{1f38a}"),              // 🎊
+    ("confused", "\u{1f615}"),                   // 😕
+    ("construction", "\u{1f6a7}"),               // 🚧
+    ("cool", "\u{1f192}"),                       // 🆒
+    ("cry", "\u{1f622}"),                        // 😢
+    ("dog", "\u{1f436}"),                        // 🐶
+    ("dollar", "\u{1f4b5}"),                     // 💵
+    ("door", "\u{1f6aa}"),                       // 🚪
+    ("earth_americas", "\u{1f30e}"),             // 🌎
+    ("egg", "\u{1f95a}"),                        // 🥚
+    ("email", "\u{1f4e7}"),                      // 📧
+    ("envelope", "\u{2709}\u{fe0f}"),            // ✉️
+    ("exclamation", "\u{2757}"),                 // ❗
+    ("eyes", "\u{1f440}"),                       // 👀
+    ("facepunch", "\u{1f44a}"),                  // 👊
+    ("fire", "\u{1f525}"),                       // 🔥
+    ("fireworks", "\u{1f386}"),                  // 🎆
+    ("fist", "\u{270a}"),                        // ✊
+    ("flashlight", "\u{1f526}"),                 // 🔦
+    ("floppy_disk", "\u{1f4be}"),                // 💾
+    ("gear", "\u{2699}\u{fe0f}"),                // ⚙️
+    ("gem", "\u{1f48e}"),                        // 💎
+    ("ghost", "\u{1f47b}"),                      // 👻
+    ("gift", "\u{1f381}"),                       // 🎁
+    ("globe_with_meridians", "\u{1f310}"),       // 🌐
+    ("grin", "\u{1f601}"),                       // 😁
+    ("grinning", "\u{1f600}"),                   // 😀
+    ("hammer", "\u{1f528}"),                     // 🔨
+    ("hammer_and_wrench", "\u{1f6e0}\u{fe0f}"),  // 🛠️
+    ("hand", "\u{270b}"),                        // ✋
+    ("handshake", "\u{1f91d}"),                  // 🤝
+    ("heart", "\u{2764}\u{fe0f}"),               // ❤️
+    ("heart_eyes", "\u{1f60d}"),                 // 😍
+    ("heavy_check_mark", "\u{2714}\u{fe0f}"),    // ✔️
+    ("heavy_minus_sign", "\u{2796}"),            // ➖
+    ("heavy_plus_sign", "\u{2795}"),             // ➕
+    ("hourglass", "\u{231b}"),                   // ⌛
+    ("house", "\u{1f3e0}"),                      // 🏠
+    ("hugs", "\u{1f917}"),                       // 🤗
+    ("information_source", "\u{2139}\u{fe0f}"),  // ℹ️
+    ("joy", "\u{1f602}"),                        // 😂
+    ("key", "\u{1f511}"),                        // 🔑
+    ("kiss", "\u{1f48b}"),                       // 💋
+    ("laughing", "\u{1f606}"),                   // 😆
+    ("link", "\u{1f517}"),                       // 🔗
+    ("lock", "\u{1f512}"),                       // 🔒
+    ("loudspeaker", "\u{1f4e2}"),                // 📢
+    ("mag", "\u{1f50d}"),                        // 🔍
+    ("mailbox", "\u{1f4eb}"),                    // 📫
+    ("memo", "\u{1f4dd}"),                       // 📝
+    ("moon", "\u{1f314}"),                       // 🌔
+    ("muscle", "\u{1f4aa}"),                     // 💪
+    ("mute", "\u{1f507}"),                       // 🔇
+    ("new", "\u{1f195}"),                        // 🆕
+    ("no_entry", "\u{26d4}"),                    // ⛔
+    ("no_entry_sign", "\u{1f6ab}"),              // 🚫
+    ("ok", "\u{1f197}"),                         // 🆗
+    ("ok_hand", "\u{1f44c}"),                    // 👌
+    ("open_mouth", "\u{1f62e}"),                 // 😮
+    ("package", "\u{1f4e6}"),                    // 📦
+    ("page_facing_up", "\u{1f4c4}"),             // 📄
+    ("paperclip", "\u{1f4ce}"),                  // 📎
+    ("partying_face", "\u{1f973}"),              // 🥳
+    ("pencil", "\u{1f4dd}"),                     // 📝
+    ("pencil2", "\u{270f}\u{fe0f}"),             // ✏️
+    ("penguin", "\u{1f427}"),                    // 🐧
+    ("phone", "\u{260e}\u{fe0f}"),               // ☎️
+    ("pizza", "\u{1f355}"),                      // 🍕
+    ("point_down", "\u{1f447}"),                 // 👇
+    ("point_left", "\u{1f448}"),                 // 👈
+    ("point_right", "\u{1f449}"),                // 👉
+    ("point_up", "\u{261d}\u{fe0f}"),            // ☝️
+    ("poop", "\u{1f4a9}"),                       // 💩
+    ("pray", "\u{1f64f}"),                       // 🙏
+    ("pushpin", "\u{1f4cc}"),                    // 📌
+    ("question", "\u{2753}"),                    // ❓
+    ("rainbow", "\u{1f308}"),                    // 🌈
+    ("raised_hands", "\u{1f64c}"),               // 🙌
+    ("recycle", "\u{267b}\u{fe0f}"),             // ♻️
+    ("red_circle", "\u{1f534}"),                 // 🔴
+    ("rocket", "\u{1f680}"),                     // 🚀
+    ("rofl", "\u{1f923}"),                       // 🤣
+    ("rose", "\u{1f339}"),                       // 🌹
+    ("rotating_light", "\u{1f6a8}"),             // 🚨
+    ("scissors", "\u{2702}\u{fe0f}"),            // ✂️
+    ("see_no_evil", "\u{1f648}"),                // 🙈
+    ("shield", "\u{1f6e1}\u{fe0f}"),             // 🛡️
+    ("shipit", "\u{1f43f}\u{fe0f}"),             // 🐿️
+    ("skull", "\u{1f480}"),                      // 💀
+    ("sleeping", "\u{1f634}"),                   // 😴
+    ("slightly_smiling_face", "\u{1f642}"),      // 🙂
+    ("smile", "\u{1f604}"),                      // 😄
+    ("smiley", "\u{1f603}"),                     // 😃
+    ("smirk", "\u{1f60f}"),                      // 😏
+    ("snowflake", "\u{2744}\u{fe0f}"),           // ❄️
+    ("sob", "\u{1f62d}"),                        // 😭
+    ("sparkles", "\u{2728}"),                    // ✨
+    ("speech_balloon", "\u{1f4ac}"),             // 💬
+    ("star", "\u{2b50}"),                        // ⭐
+    ("star2", "\u{1f31f}"),                      // 🌟
+    ("stop_sign", "\u{1f6d1}"),                  // 🛑
+    ("sun_with_face", "\u{1f31e}"),              // 🌞
+    ("sunglasses", "\u{1f60e}"),                 // 😎
+    ("sunny", "\u{2600}\u{fe0f}"),               // ☀️
+    ("sweat_smile", "\u{1f605}"),                // 😅
+    ("tada", "\u{1f389}"),                       // 🎉
+    ("thinking", "\u{1f914}"),                   // 🤔
+    ("thumbsdown", "\u{1f44e}"),                 // 👎
+    ("thumbsup", "\u{1f44d}"),                   // 👍
+    ("tired_face", "\u{1f62b}"),                 // 😫
+    ("trophy", "\u{1f3c6}"),                     // 🏆
+    ("truck", "\u{1f69a}"),                      // 🚚
+    ("umbrella", "\u{2614}"),                    // ☔
+    ("unlock", "\u{1f513}"),                     // 🔓
+    ("v", "\u{270c}\u{fe0f}"),                   // ✌️
+    ("warning", "\u{26a0}\u{fe0f}"),             // ⚠️
+    ("wave", "\u{1f44b}"),                       // 👋
+    ("white_check_mark", "\u{2705}"),            // ✅
+    ("wink", "\u{1f609}"),                       // 😉
+    ("wrench", "\u{1f527}"),                     // 🔧
+    ("x", "\u{274c}"),                           // ❌
+    ("yum", "\u{1f60b}"),                        // 😋
+    ("zap", "\u{26a1}"),                         // ⚡
+    ("zzz", "\u{1f4a4}"),                        // 💤
+];
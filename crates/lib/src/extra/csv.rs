@@ -0,0 +1,71 @@
+use liquid_core::interpreter::{CsvFieldEncoder, OutputEncoder};
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use crate::invalid_input;
+
+/// Serialize `fields` as a CSV line, without the line break.
+pub(crate) fn csv_line<'v, I>(fields: I) -> String
+where
+    I: Iterator<Item = &'v dyn ValueView>,
+{
+    let fields: Vec<_> = fields
+        .map(|field| {
+            if field.is_nil() {
+                String::new()
+            } else {
+                CsvFieldEncoder.encode(&field.to_kstr()).into_owned()
+            }
+        })
+        .collect();
+    fields.join(",")
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "csv_row",
+    description = "Serializes an array as a CSV line, quoting fields with commas, quotes or line breaks. Nil elements become empty fields.",
+    parsed(CsvRowFilter)
+)]
+pub struct CsvRow;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "csv_row"]
+struct CsvRowFilter;
+
+impl Filter for CsvRowFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let input = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+        Ok(Value::scalar(csv_line(input.values())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_csv_row() {
+        assert_eq!(
+            liquid_core::call_filter!(CsvRow, liquid_core::value!(["a", 1, nil, true])).unwrap(),
+            liquid_core::value!("a,1,,true")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(
+                CsvRow,
+                liquid_core::value!(["Smith, Ann", "say \"hi\"", "two\nlines"])
+            )
+            .unwrap(),
+            liquid_core::value!("\"Smith, Ann\",\"say \"\"hi\"\"\",\"two\nlines\"")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(CsvRow, liquid_core::value!([])).unwrap(),
+            liquid_core::value!("")
+        );
+        liquid_core::call_filter!(CsvRow, "a,b").unwrap_err();
+    }
+}
@@ -0,0 +1,219 @@
+use std::io::Write;
+
+use itertools;
+use liquid_core::compiler::TagToken;
+use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::ValueView;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+use liquid_core::{Error, Result};
+
+use super::csv::csv_line;
+
+#[derive(Debug)]
+struct Csv {
+    headers: Vec<Expression>,
+    template: Template,
+}
+
+impl Csv {
+    fn trace(&self) -> String {
+        format!("{{% csv {} %}}", itertools::join(self.headers.iter(), ", "))
+    }
+
+    fn header_line(&self, runtime: &Runtime<'_>) -> Result<(String, usize)> {
+        let values = self
+            .headers
+            .iter()
+            .map(|header| header.evaluate(runtime))
+            .collect::<Result<Vec<_>>>()?;
+        // A single array, like `{% csv columns %}`, lists the headers itself.
+        if let [ref value] = values[..] {
+            if let Some(array) = value.as_array() {
+                return Ok((csv_line(array.values()), array.size() as usize));
+            }
+        }
+        Ok((csv_line(values.iter().map(|v| v.as_view())), values.len()))
+    }
+}
+
+/// Split `text` into CSV records, each with its number of fields.
+///
+/// Line breaks and commas inside quoted fields don't split.
+fn records(text: &str) -> Vec<(&str, usize)> {
+    let mut records = Vec::new();
+    let mut start = 0;
+    let mut fields = 1;
+    let mut quoted = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields += 1,
+            '\n' if !quoted => {
+                records.push((&text[start..i], fields));
+                start = i + 1;
+                fields = 1;
+            }
+            _ => {}
+        }
+    }
+    records.push((&text[start..], fields));
+    records
+}
+
+impl Renderable for Csv {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        let (header, columns) = self
+            .header_line(runtime)
+            .trace_with(|| self.trace().into())?;
+
+        let mut body = Vec::new();
+        self.template
+            .render_to(&mut body, runtime)
+            .trace_with(|| self.trace().into())?;
+        let body = String::from_utf8(body).expect("render only writes UTF-8");
+
+        writeln!(writer, "{}", header).replace("Failed to render")?;
+        let rows = records(&body)
+            .into_iter()
+            .map(|(record, fields)| (record.trim(), fields))
+            .filter(|&(record, _)| !record.is_empty());
+        for (row, (record, fields)) in rows.enumerate() {
+            if fields != columns {
+                return Error::with_msg("CSV row has the wrong number of columns")
+                    .context("row", (row + 1).to_string())
+                    .context("expected", columns.to_string())
+                    .context("actual", fields.to_string())
+                    .trace(self.trace())
+                    .into_err();
+            }
+            writeln!(writer, "{}", record).replace("Failed to render")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CsvBlock;
+
+impl CsvBlock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BlockReflection for CsvBlock {
+    fn start_tag(&self) -> &str {
+        "csv"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endcsv"
+    }
+
+    fn description(&self) -> &str {
+        "Writes a CSV header line followed by the non-blank lines of the body, failing when a row doesn't have one field per header."
+    }
+}
+
+impl ParseBlock for CsvBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let mut headers = vec![arguments
+            .expect_next("Header expected")?
+            .expect_value()
+            .into_result()?];
+        loop {
+            let next = arguments.next();
+            match next.as_ref().map(TagToken::as_str) {
+                Some(",") => headers.push(
+                    arguments
+                        .expect_next("Header expected")?
+                        .expect_value()
+                        .into_result()?,
+                ),
+                None => break,
+                Some(_) => {
+                    return next
+                        .expect("is some")
+                        .raise_custom_error("\",\" expected.")
+                        .into_err();
+                }
+            }
+        }
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| "{% csv %}".into())?,
+        );
+
+        tokens.assert_empty();
+        Ok(Box::new(Csv { headers, template }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::compiler;
+    use liquid_core::interpreter;
+    use liquid_core::Value;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options.blocks.register("csv".to_string(), CsvBlock.into());
+        options
+    }
+
+    fn render(text: &str, rows: Value) -> Result<String> {
+        let template = compiler::parse(text, &options()).map(interpreter::Template::new)?;
+        let mut runtime = Runtime::new();
+        runtime.stack_mut().set_global("rows", rows);
+        template.render(&mut runtime)
+    }
+
+    #[test]
+    fn test_csv() {
+        let text = concat!(
+            "{% csv 'name', 'note' %}\n",
+            "  {{ rows[0][0] }},{{ rows[0][1] }}\n",
+            "\n",
+            "  {{ rows[1][0] }},{{ rows[1][1] }}\n",
+            "{% endcsv %}"
+        );
+        let rows = liquid_core::value!([["Ann", "\"a, b\""], ["Bob", "\"two\nlines\""]]);
+        assert_eq!(
+            render(text, rows).unwrap(),
+            "name,note\nAnn,\"a, b\"\nBob,\"two\nlines\"\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_headers_array() {
+        let text = "{% csv rows %}1,2{% endcsv %}";
+        let rows = liquid_core::value!(["a, 1", "b"]);
+        assert_eq!(render(text, rows).unwrap(), "\"a, 1\",b\n1,2\n");
+    }
+
+    #[test]
+    fn test_csv_wrong_columns() {
+        let text = "{% csv 'a', 'b' %}1,2\n3\n{% endcsv %}";
+        let error = render(text, Value::Nil).unwrap_err();
+        assert_eq!(error.message(), "CSV row has the wrong number of columns");
+        assert_eq!(error.context_value("row"), Some("2"));
+    }
+}
@@ -7,7 +7,7 @@ use liquid_core::{
 };
 use liquid_core::{Value, ValueView};
 
-use crate::invalid_input;
+use crate::{invalid_input, to_date_time};
 
 // liquid-rust proprietary
 
@@ -42,10 +42,8 @@ impl Filter for DateInTzFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let date = input
-            .as_scalar()
-            .and_then(|s| s.to_date_time())
-            .ok_or_else(|| invalid_input("Invalid date format"))?;
+        let date =
+            to_date_time(input, runtime).ok_or_else(|| invalid_input("Invalid date format"))?;
 
         let timezone = FixedOffset::east(args.timezone * 3600);
 
@@ -0,0 +1,131 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// Content lines longer than this many bytes must be folded, see RFC 5545 section 3.1.
+const ICS_LINE_LENGTH: usize = 75;
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "ics_escape",
+    description = "Escapes text for an iCalendar property value: backslashes, semicolons and commas are escaped and line breaks become `\\n`.",
+    parsed(IcsEscapeFilter)
+)]
+pub struct IcsEscape;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "ics_escape"]
+struct IcsEscapeFilter;
+
+impl Filter for IcsEscapeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let input = input.to_kstr();
+        let mut escaped = String::with_capacity(input.len());
+        let mut chars = input.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                ';' => escaped.push_str("\\;"),
+                ',' => escaped.push_str("\\,"),
+                '\r' => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    escaped.push_str("\\n");
+                }
+                '\n' => escaped.push_str("\\n"),
+                _ => escaped.push(c),
+            }
+        }
+        Ok(Value::scalar(escaped))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "ics_fold",
+    description = "Folds iCalendar content lines longer than 75 bytes, continuing them on the next line after a space. Lines are ended with CRLF as iCalendar requires.",
+    parsed(IcsFoldFilter)
+)]
+pub struct IcsFold;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "ics_fold"]
+struct IcsFoldFilter;
+
+fn fold_line(line: &str, folded: &mut String) {
+    let mut length = 0;
+    for c in line.chars() {
+        // Never split a character's UTF-8 sequence across lines.
+        if length + c.len_utf8() > ICS_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation's length.
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+}
+
+impl Filter for IcsFoldFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let input = input.to_kstr();
+        let mut folded = String::with_capacity(input.len() + input.len() / ICS_LINE_LENGTH * 3);
+        let mut lines = input.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            let line = line.trim_end_matches('\r');
+            fold_line(line, &mut folded);
+            if lines.peek().is_some() {
+                folded.push_str("\r\n");
+            }
+        }
+        Ok(Value::scalar(folded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_ics_escape() {
+        assert_eq!(
+            liquid_core::call_filter!(IcsEscape, "Meet; bring cake, tea\\coffee\r\nRoom 2\nEOD")
+                .unwrap(),
+            liquid_core::value!("Meet\\; bring cake\\, tea\\\\coffee\\nRoom 2\\nEOD")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(IcsEscape, "plain").unwrap(),
+            liquid_core::value!("plain")
+        );
+    }
+
+    #[test]
+    fn unit_ics_fold() {
+        let short = "SUMMARY:Lunch";
+        assert_eq!(
+            liquid_core::call_filter!(IcsFold, short).unwrap(),
+            liquid_core::value!(short)
+        );
+
+        let long = format!("DESCRIPTION:{}\nEND:VEVENT", "a".repeat(100));
+        let expected = format!(
+            "DESCRIPTION:{}\r\n {}\r\nEND:VEVENT",
+            "a".repeat(63),
+            "a".repeat(37)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(IcsFold, long).unwrap(),
+            liquid_core::value!(expected)
+        );
+
+        let multibyte = format!("X:{}", "é".repeat(40));
+        let folded = liquid_core::call_filter!(IcsFold, multibyte).unwrap();
+        let folded = folded.to_kstr();
+        let lines: Vec<_> = folded.split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), 74);
+        assert!(lines[1].starts_with(' '));
+    }
+}
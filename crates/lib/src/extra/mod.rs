@@ -1,3 +1,9 @@
+mod csv;
+mod csv_block;
 mod date;
+mod ics;
 
+pub use self::csv::*;
+pub use self::csv_block::*;
 pub use self::date::*;
+pub use self::ics::*;
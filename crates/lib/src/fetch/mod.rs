@@ -0,0 +1,165 @@
+use std::io::Write;
+
+use liquid_core::error::{Error, ResultLiquidExt};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Value;
+use liquid_core::ValueView;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+#[derive(Debug)]
+struct Fetch {
+    url: Expression,
+    dst: String,
+}
+
+impl Fetch {
+    fn trace(&self) -> String {
+        format!("{{% fetch {} into {} %}}", self.url, self.dst)
+    }
+}
+
+impl Renderable for Fetch {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        let url = self
+            .url
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .to_kstr()
+            .into_owned();
+
+        let body = runtime
+            .fetcher()
+            .ok_or_else(|| Error::with_msg("Fetching is not enabled for this render"))
+            .and_then(|fetcher| fetcher.fetch(url.as_str()))
+            .trace_with(|| self.trace().into())?;
+        let value: Value = serde_json::from_slice(&body)
+            .map_err(|e| {
+                Error::with_msg("Invalid JSON")
+                    .context("url", url.clone())
+                    .context("cause", e.to_string())
+            })
+            .trace_with(|| self.trace().into())?;
+
+        runtime
+            .stack_mut()
+            .try_set_global(self.dst.to_owned(), value)
+            .trace_with(|| self.trace().into())?;
+        Ok(())
+    }
+}
+
+/// Fetches JSON at render time, through the `FetchSandbox` of the render.
+///
+/// This is not part of the standard library; register it with the parser to opt in.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FetchTag;
+
+impl FetchTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for FetchTag {
+    fn tag(&self) -> &'static str {
+        "fetch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fetches JSON from an allowed URL, like `{% fetch 'https://status.example.com/api' into status %}`, and assigns it."
+    }
+}
+
+impl ParseTag for FetchTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let url = arguments
+            .expect_next("URL expected.")?
+            .expect_value()
+            .into_result()?;
+
+        arguments
+            .expect_next("\"into\" expected.")?
+            .expect_str("into")
+            .into_result_custom_msg("\"into\" expected.")?;
+
+        let dst = arguments
+            .expect_next("Identifier expected.")?
+            .expect_identifier()
+            .into_result()?
+            .to_string();
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Fetch { url, dst }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::Duration;
+
+    use liquid_core::compiler;
+    use liquid_core::interpreter;
+    use liquid_core::interpreter::{FetchSandbox, HttpFetcher, RuntimeBuilder};
+
+    #[derive(Debug)]
+    struct Status;
+
+    impl HttpFetcher for Status {
+        fn fetch(&self, _url: &str, _timeout: Duration, _max_bytes: usize) -> Result<Vec<u8>> {
+            Ok(br#"{"status": "ok", "builds": [1, 2]}"#.to_vec())
+        }
+    }
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options.tags.register("fetch".to_string(), FetchTag.into());
+        options
+    }
+
+    #[test]
+    fn fetch() {
+        let options = options();
+        let template = compiler::parse(
+            "{% fetch 'https://ci.example.com/api' into ci %}{{ ci.status }} {{ ci.builds[1] }}",
+            &options,
+        )
+        .map(interpreter::Template::new)
+        .unwrap();
+
+        let sandbox = FetchSandbox::new(Status).allow("https://ci.example.com/");
+        let mut runtime = RuntimeBuilder::new().set_fetcher(&sandbox).build();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "ok 2");
+    }
+
+    #[test]
+    fn fetch_is_sandboxed() {
+        let options = options();
+        let template = compiler::parse("{% fetch 'https://example.com/' into data %}", &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        assert!(template.render(&mut runtime).is_err());
+
+        let sandbox = FetchSandbox::new(Status).allow("https://ci.example.com/");
+        let mut runtime = RuntimeBuilder::new().set_fetcher(&sandbox).build();
+        assert!(template.render(&mut runtime).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+/// Separator between the excerpt and the rest, like Jekyll's `excerpt_separator`.
+const DEFAULT_SEPARATOR: &str = "\n\n";
+
+/// Elements without a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Byte offset of the first `separator` outside of a tag.
+fn find_separator(content: &str, separator: &str) -> Option<usize> {
+    let mut in_tag = false;
+    for (i, c) in content.char_indices() {
+        if !in_tag && content[i..].starts_with(separator) {
+            return Some(i);
+        }
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ => (),
+        }
+    }
+    None
+}
+
+fn tag_name(tag: &str) -> &str {
+    tag.split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or("")
+}
+
+/// Append closing tags for the elements `html` leaves open.
+fn close_tags(html: &str) -> String {
+    let mut open: Vec<&str> = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let tag = &rest[start + 1..];
+        let end = match tag.find('>') {
+            Some(end) => end,
+            None => break,
+        };
+        let inner = &tag[..end];
+        rest = &tag[end + 1..];
+
+        if inner.starts_with('!') || inner.starts_with('?') || inner.ends_with('/') {
+            continue;
+        }
+        if inner.starts_with('/') {
+            let name = tag_name(&inner[1..]);
+            if let Some(i) = open.iter().rposition(|o| o.eq_ignore_ascii_case(name)) {
+                open.truncate(i);
+            }
+        } else {
+            let name = tag_name(inner);
+            if !name.is_empty() && !VOID_ELEMENTS.iter().any(|v| v.eq_ignore_ascii_case(name)) {
+                open.push(name);
+            }
+        }
+    }
+
+    let mut closed = html.to_owned();
+    for name in open.iter().rev() {
+        closed.push_str("</");
+        closed.push_str(name);
+        closed.push('>');
+    }
+    closed
+}
+
+#[derive(Debug, FilterParameters)]
+struct ExcerptArgs {
+    #[parameter(
+        description = "Where the excerpt ends. Defaults to a blank line.",
+        arg_type = "str"
+    )]
+    separator: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "excerpt",
+    description = "The content before the first separator, like the first paragraph. Separators inside HTML tags are skipped and elements left open are closed.",
+    parameters(ExcerptArgs),
+    parsed(ExcerptFilter)
+)]
+pub struct Excerpt;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "excerpt"]
+struct ExcerptFilter {
+    #[parameters]
+    args: ExcerptArgs,
+}
+
+impl Filter for ExcerptFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let content = input.to_kstr();
+        let separator = args
+            .separator
+            .as_ref()
+            .map(|s| s.as_str())
+            .unwrap_or(DEFAULT_SEPARATOR);
+        let excerpt = if separator.is_empty() {
+            content.as_str()
+        } else {
+            let content = content.as_str().trim_start();
+            match find_separator(content, separator) {
+                Some(end) => &content[..end],
+                None => content,
+            }
+        };
+        Ok(Value::scalar(close_tags(excerpt)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt_first_paragraph() {
+        assert_eq!(
+            liquid_core::call_filter!(Excerpt, "\nFirst\nline.\n\nSecond.").unwrap(),
+            liquid_core::value!("First\nline.")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Excerpt, "Only one.").unwrap(),
+            liquid_core::value!("Only one.")
+        );
+    }
+
+    #[test]
+    fn excerpt_separator() {
+        assert_eq!(
+            liquid_core::call_filter!(Excerpt, "Intro <!--more--> Body", "<!--more-->").unwrap(),
+            liquid_core::value!("Intro ")
+        );
+    }
+
+    #[test]
+    fn excerpt_html() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                Excerpt,
+                "<div><p title=\"a\n\nb\">One<br>\n\n<em>two</em></p></div>"
+            )
+            .unwrap(),
+            liquid_core::value!("<div><p title=\"a\n\nb\">One<br></p></div>")
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+use super::slugify::{slugify, SlugifyMode};
+use crate::invalid_argument;
+
+/// Anchor used for headings without any letters or digits.
+const FALLBACK_ANCHOR: &str = "section";
+
+/// A heading recorded for `{% toc %}`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Heading {
+    pub(crate) level: i32,
+    pub(crate) title: String,
+    pub(crate) anchor: String,
+}
+
+/// Anchors handed out so far in the render.
+#[derive(Debug, Default)]
+pub(crate) struct Anchors {
+    used: HashSet<String>,
+    pub(crate) headings: Vec<Heading>,
+}
+
+impl Anchors {
+    /// An anchor for `heading`, unique within the render.
+    pub(crate) fn unique(&mut self, heading: &str) -> String {
+        let mut slug = slugify(heading, SlugifyMode::Def);
+        if slug.is_empty() {
+            slug = FALLBACK_ANCHOR.to_owned();
+        }
+        let mut anchor = slug.clone();
+        let mut n = 0;
+        while self.used.contains(&anchor) {
+            n += 1;
+            anchor = format!("{}-{}", slug, n);
+        }
+        self.used.insert(anchor.clone());
+        anchor
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct HeadingAnchorArgs {
+    #[parameter(
+        description = "The level of the heading, like 2 for `<h2>`. When given, the heading is listed by `{% toc %}`.",
+        arg_type = "integer",
+        mode = "keyword"
+    )]
+    level: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "heading_anchor",
+    description = "Convert a heading into an `id` for linking to it, unique within the render by appending `-1`, `-2`, ... to repeats.",
+    parameters(HeadingAnchorArgs),
+    parsed(HeadingAnchorFilter)
+)]
+pub struct HeadingAnchor;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "heading_anchor"]
+struct HeadingAnchorFilter {
+    #[parameters]
+    args: HeadingAnchorArgs,
+}
+
+impl Filter for HeadingAnchorFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        if let Some(level) = args.level {
+            if !(1..=6).contains(&level) {
+                return invalid_argument("level", "Number from 1 to 6 expected").into_err();
+            }
+        }
+
+        let title = input.to_kstr();
+        let anchor = runtime.with_render_register(|anchors: &mut Anchors| {
+            let anchor = anchors.unique(&title);
+            if let Some(level) = args.level {
+                anchors.headings.push(Heading {
+                    level,
+                    title: title.as_str().to_owned(),
+                    anchor: anchor.clone(),
+                });
+            }
+            anchor
+        })?;
+        Ok(Value::scalar(anchor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_anchors() {
+        let mut anchors = Anchors::default();
+        assert_eq!(anchors.unique("Getting Started"), "getting-started");
+        assert_eq!(anchors.unique("Getting started!"), "getting-started-1");
+        assert_eq!(anchors.unique("Getting Started 1"), "getting-started-1-1");
+        assert_eq!(anchors.unique("Getting Started"), "getting-started-2");
+        assert_eq!(anchors.unique("???"), "section");
+    }
+
+    #[test]
+    fn heading_anchor() {
+        assert_eq!(
+            liquid_core::call_filter!(HeadingAnchor, "Install & Setup").unwrap(),
+            liquid_core::value!("install-setup")
+        );
+    }
+}
@@ -1,7 +1,17 @@
 mod array;
+mod excerpt;
+mod heading_anchor;
 mod include_tag;
+mod reading_time;
 mod slugify;
+mod smartify;
+mod toc_tag;
 
 pub use self::array::*;
+pub use self::excerpt::*;
+pub use self::heading_anchor::*;
 pub use self::include_tag::*;
+pub use self::reading_time::*;
 pub use self::slugify::*;
+pub use self::smartify::*;
+pub use self::toc_tag::*;
@@ -0,0 +1,116 @@
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+use crate::invalid_argument;
+
+/// Typical adult reading speed for prose.
+const DEFAULT_WORDS_PER_MINUTE: i32 = 200;
+
+/// Whether `c` is written without spaces between words, so each one counts as a word.
+fn is_cjk(c: char) -> bool {
+    match c {
+        '\u{3040}'..='\u{30ff}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4dbf}' // CJK Extension A
+        | '\u{4e00}'..='\u{9fff}' // CJK Unified Ideographs
+        | '\u{f900}'..='\u{faff}' // CJK Compatibility Ideographs
+        | '\u{20000}'..='\u{2fa1f}' => true, // CJK Extensions B-F, Compatibility Supplement
+        _ => false,
+    }
+}
+
+/// Number of words in `content`, skipping HTML tags.
+fn count_words(content: &str) -> usize {
+    let mut words = 0;
+    let mut in_word = false;
+    let mut in_tag = false;
+    for c in content.chars() {
+        if in_tag {
+            in_tag = c != '>';
+            continue;
+        }
+        if c == '<' {
+            in_tag = true;
+            in_word = false;
+        } else if is_cjk(c) {
+            words += 1;
+            in_word = false;
+        } else if c.is_whitespace() {
+            in_word = false;
+        } else if !in_word {
+            words += 1;
+            in_word = true;
+        }
+    }
+    words
+}
+
+#[derive(Debug, FilterParameters)]
+struct ReadingTimeArgs {
+    #[parameter(
+        description = "Reading speed. Defaults to 200 words per minute.",
+        arg_type = "integer",
+        mode = "keyword"
+    )]
+    words_per_minute: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "reading_time",
+    description = "Estimated minutes to read the content, rounded up. HTML tags are skipped and each CJK character counts as a word.",
+    parameters(ReadingTimeArgs),
+    parsed(ReadingTimeFilter)
+)]
+pub struct ReadingTime;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "reading_time"]
+struct ReadingTimeFilter {
+    #[parameters]
+    args: ReadingTimeArgs,
+}
+
+impl Filter for ReadingTimeFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let words_per_minute = args.words_per_minute.unwrap_or(DEFAULT_WORDS_PER_MINUTE);
+        if words_per_minute < 1 {
+            return invalid_argument("words_per_minute", "Positive number expected").into_err();
+        }
+        let words_per_minute = words_per_minute as usize;
+
+        let words = count_words(input.to_kstr().as_str());
+        let minutes = (words + words_per_minute - 1) / words_per_minute;
+        Ok(Value::scalar(minutes as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_words_skips_tags() {
+        assert_eq!(
+            count_words("<p class=\"lead\">Hello, <em>big</em> world!</p>"),
+            3
+        );
+        assert_eq!(count_words("日本語のテキスト and more"), 10);
+        assert_eq!(count_words(""), 0);
+    }
+
+    #[test]
+    fn reading_time() {
+        let content = "word ".repeat(401);
+        assert_eq!(
+            liquid_core::call_filter!(ReadingTime, content).unwrap(),
+            liquid_core::value!(3)
+        );
+    }
+}
@@ -9,7 +9,7 @@ use liquid_core::{Value, ValueView};
 use regex::Regex;
 
 #[derive(PartialEq)]
-enum SlugifyMode {
+pub(crate) enum SlugifyMode {
     No,
     Def,
     Raw,
@@ -41,6 +41,27 @@ static SLUG_INVALID_CHARS_PRETTY: once_cell::sync::Lazy<Regex> = once_cell::sync
 static SLUG_INVALID_CHARS_ASCII: once_cell::sync::Lazy<Regex> =
     once_cell::sync::Lazy::new(|| Regex::new(r"([^a-zA-Z0-9]+)").unwrap());
 
+/// Slug of `s` in the given mode.
+pub(crate) fn slugify(s: &str, mode: SlugifyMode) -> String {
+    let s = if mode == SlugifyMode::Latin {
+        deunicode::deunicode_with_tofu(&s.trim(), "-")
+    } else {
+        s.trim().to_string()
+    };
+
+    let result = match mode {
+        SlugifyMode::No => s,
+        SlugifyMode::Def => SLUG_INVALID_CHARS_DEFAULT.replace_all(&s, "-").to_string(),
+        SlugifyMode::Raw => SLUG_INVALID_CHARS_RAW.replace_all(&s, "-").to_string(),
+        SlugifyMode::Pretty => SLUG_INVALID_CHARS_PRETTY.replace_all(&s, "-").to_string(),
+        SlugifyMode::Ascii | SlugifyMode::Latin => {
+            SLUG_INVALID_CHARS_ASCII.replace_all(&s, "-").to_string()
+        }
+    };
+
+    result.trim_matches('-').to_lowercase()
+}
+
 #[derive(Debug, FilterParameters)]
 struct SlugifyArgs {
     #[parameter(
@@ -76,23 +97,7 @@ impl Filter for SlugifyFilter {
             .map(|mode| SlugifyMode::new(mode.as_str()))
             .unwrap_or(SlugifyMode::Def);
 
-        let s = if mode == SlugifyMode::Latin {
-            deunicode::deunicode_with_tofu(&s.trim(), "-")
-        } else {
-            s.trim().to_string()
-        };
-
-        let result = match mode {
-            SlugifyMode::No => s,
-            SlugifyMode::Def => SLUG_INVALID_CHARS_DEFAULT.replace_all(&s, "-").to_string(),
-            SlugifyMode::Raw => SLUG_INVALID_CHARS_RAW.replace_all(&s, "-").to_string(),
-            SlugifyMode::Pretty => SLUG_INVALID_CHARS_PRETTY.replace_all(&s, "-").to_string(),
-            SlugifyMode::Ascii | SlugifyMode::Latin => {
-                SLUG_INVALID_CHARS_ASCII.replace_all(&s, "-").to_string()
-            }
-        };
-
-        Ok(Value::scalar(result.trim_matches('-').to_lowercase()))
+        Ok(Value::scalar(slugify(&s, mode)))
     }
 }
 
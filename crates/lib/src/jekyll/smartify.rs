@@ -0,0 +1,135 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// Elements whose content is shown as written.
+const VERBATIM_ELEMENTS: &[&str] = &["code", "kbd", "pre", "samp", "script", "style", "tt"];
+
+/// Whether a quote after `prev` opens a quotation, rather than closing one or being an
+/// apostrophe.
+fn opens_quote(prev: Option<char>) -> bool {
+    match prev {
+        None => true,
+        Some(c) => c.is_whitespace() || "([{\u{2014}\u{2013}-".contains(c),
+    }
+}
+
+/// Replace straight quotes, dashes and ellipses with typographic ones, outside of HTML tags and
+/// verbatim elements like `code`.
+fn smartify(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    // Last character of text, ignoring tags.
+    let mut prev = None;
+    let mut verbatim = 0usize;
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            if let Some(end) = rest.find('>') {
+                let tag = &rest[1..end];
+                let (closing, name) = if tag.starts_with('/') {
+                    (true, &tag[1..])
+                } else {
+                    (false, tag)
+                };
+                let name = name
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or("");
+                if VERBATIM_ELEMENTS
+                    .iter()
+                    .any(|v| v.eq_ignore_ascii_case(name))
+                {
+                    if closing {
+                        verbatim = verbatim.saturating_sub(1);
+                    } else if !tag.ends_with('/') {
+                        verbatim += 1;
+                    }
+                }
+                output.push_str(&rest[..=end]);
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+
+        let (replacement, len) = if verbatim > 0 {
+            (None, c.len_utf8())
+        } else if rest.starts_with("---") {
+            (Some('\u{2014}'), 3)
+        } else if rest.starts_with("--") {
+            (Some('\u{2013}'), 2)
+        } else if rest.starts_with("...") {
+            (Some('\u{2026}'), 3)
+        } else if c == '"' {
+            let quote = if opens_quote(prev) {
+                '\u{201c}'
+            } else {
+                '\u{201d}'
+            };
+            (Some(quote), 1)
+        } else if c == '\'' {
+            let quote = if opens_quote(prev) {
+                '\u{2018}'
+            } else {
+                '\u{2019}'
+            };
+            (Some(quote), 1)
+        } else {
+            (None, c.len_utf8())
+        };
+        match replacement {
+            Some(replacement) => {
+                output.push(replacement);
+                prev = Some(replacement);
+            }
+            None => {
+                output.push_str(&rest[..len]);
+                prev = Some(c);
+            }
+        }
+        rest = &rest[len..];
+    }
+    output
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "smartify",
+    description = "Convert straight quotes, dashes and ellipses into their typographic equivalents. HTML tags and the content of elements like `code` and `pre` are left as is.",
+    parsed(SmartifyFilter)
+)]
+pub struct Smartify;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "smartify"]
+struct SmartifyFilter;
+
+impl Filter for SmartifyFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        Ok(Value::scalar(smartify(input.to_kstr().as_str())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smartify_text() {
+        assert_eq!(
+            liquid_core::call_filter!(Smartify, "\"Don't,\" she said -- 'wait'... 1999---2000")
+                .unwrap(),
+            liquid_core::value!("“Don’t,” she said – ‘wait’… 1999—2000")
+        );
+    }
+
+    #[test]
+    fn smartify_html() {
+        assert_eq!(
+            smartify(
+                "<a title=\"x\">\"Hi\"</a> <code>a--b \"c\"</code> <pre><code>'d'</code></pre> 'e'"
+            ),
+            "<a title=\"x\">“Hi”</a> <code>a--b \"c\"</code> <pre><code>'d'</code></pre> ‘e’"
+        );
+    }
+}
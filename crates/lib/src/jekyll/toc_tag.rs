@@ -0,0 +1,157 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+use super::heading_anchor::{Anchors, Heading};
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Nested `<ul>` lists linking to `headings`, deeper levels nested in the item before them.
+fn render_toc(headings: &[Heading]) -> String {
+    let mut toc = String::new();
+    // Levels of the open lists, outermost first.
+    let mut levels: Vec<i32> = Vec::new();
+    for heading in headings {
+        while levels.len() > 1 && heading.level < *levels.last().expect("length checked") {
+            toc.push_str("</li></ul>");
+            levels.pop();
+        }
+        match levels.last() {
+            Some(&level) if heading.level <= level => toc.push_str("</li>"),
+            _ => {
+                toc.push_str("<ul>");
+                levels.push(heading.level);
+            }
+        }
+        toc.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a>",
+            heading.anchor,
+            escape_html(&heading.title)
+        ));
+    }
+    for _ in levels {
+        toc.push_str("</li></ul>");
+    }
+    toc
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Toc;
+
+impl Renderable for Toc {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        let toc =
+            runtime.with_render_register(|anchors: &mut Anchors| render_toc(&anchors.headings))?;
+        write!(writer, "{}", toc).replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+/// Lists the headings passed to `heading_anchor` with a `level` so far in the render.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TocTag;
+
+impl TocTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for TocTag {
+    fn tag(&self) -> &'static str {
+        "toc"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renders a nested list linking to the headings given a `level` by `heading_anchor` earlier in the render."
+    }
+}
+
+impl ParseTag for TocTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        // no arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+        Ok(Box::new(Toc))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::compiler;
+    use liquid_core::interpreter;
+
+    use crate::jekyll::HeadingAnchor;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options.tags.register("toc".to_string(), TocTag.into());
+        options
+            .filters
+            .register("heading_anchor".to_string(), Box::new(HeadingAnchor));
+        options
+    }
+
+    #[test]
+    fn toc() {
+        let options = options();
+        let template = compiler::parse(
+            "{{ 'Install' | heading_anchor: level: 2 }} \
+             {{ 'Linux' | heading_anchor: level: 3 }} \
+             {{ 'Mac & Windows' | heading_anchor: level: 3 }} \
+             {{ 'Usage' | heading_anchor: level: 2 }} \
+             {{ 'Install' | heading_anchor: level: 2 }} \
+             {{ 'Unlisted' | heading_anchor }} {% toc %}",
+            &options,
+        )
+        .map(interpreter::Template::new)
+        .unwrap();
+
+        let mut runtime = Runtime::new();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(
+            output,
+            "install linux mac-windows usage install-1 unlisted \
+             <ul><li><a href=\"#install\">Install</a>\
+             <ul><li><a href=\"#linux\">Linux</a></li>\
+             <li><a href=\"#mac-windows\">Mac &amp; Windows</a></li></ul></li>\
+             <li><a href=\"#usage\">Usage</a></li>\
+             <li><a href=\"#install-1\">Install</a></li></ul>"
+        );
+    }
+
+    #[test]
+    fn toc_nests_skipped_levels() {
+        let heading = |level, title: &str| Heading {
+            level,
+            title: title.to_owned(),
+            anchor: title.to_owned(),
+        };
+        let headings = [heading(2, "a"), heading(4, "b"), heading(3, "c")];
+        assert_eq!(
+            render_toc(&headings),
+            "<ul><li><a href=\"#a\">a</a>\
+             <ul><li><a href=\"#b\">b</a></li></ul>\
+             <ul><li><a href=\"#c\">c</a></li></ul></li></ul>"
+        );
+    }
+}
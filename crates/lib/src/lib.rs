@@ -1,13 +1,30 @@
+#[cfg(feature = "email")]
+pub mod email;
+#[cfg(feature = "emoji")]
+pub mod emoji;
 #[cfg(feature = "extra")]
 pub mod extra;
+#[cfg(feature = "fetch")]
+pub mod fetch;
 #[cfg(feature = "jekyll")]
 pub mod jekyll;
+#[cfg(feature = "qr")]
+pub mod qr;
 #[cfg(feature = "shopify")]
 pub mod shopify;
 #[cfg(feature = "stdlib")]
 pub mod stdlib;
 
+mod locale;
+
+pub use crate::locale::Locale;
+
 use liquid_core::Error;
+#[cfg(any(feature = "stdlib", feature = "extra"))]
+use liquid_core::{
+    value::{DateFormats, DateTime},
+    Runtime, ValueView,
+};
 
 pub(crate) fn invalid_input<S>(cause: S) -> Error
 where
@@ -24,3 +41,13 @@ where
         .context("argument", argument)
         .context("cause", cause)
 }
+
+/// Interpret `input` as a date time, parsing strings with the render's `DateFormats`.
+#[cfg(any(feature = "stdlib", feature = "extra"))]
+pub(crate) fn to_date_time(input: &dyn ValueView, runtime: &Runtime<'_>) -> Option<DateTime> {
+    let scalar = input.as_scalar()?;
+    match runtime.get_register::<DateFormats>() {
+        Some(formats) => scalar.to_date_time_with(formats),
+        None => scalar.to_date_time(),
+    }
+}
@@ -0,0 +1,523 @@
+// Only the standard library's filters use the name tables so far.
+#![cfg_attr(not(feature = "stdlib"), allow(dead_code))]
+
+use chrono::Weekday;
+use kstring::KString;
+use liquid_core::Runtime;
+
+/// Language and region conventions for formatting filters, like `date`, `number_format`, `money`
+/// and `pluralize`.
+///
+/// Filters that accept a locale argument fall back to the `Locale` registered with the
+/// `Runtime`, like with `RenderOptions::locale`, and then to `en`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Locale {
+    tag: KString,
+    currency: Option<KString>,
+    decimal_separator: char,
+    grouping_separator: char,
+    first_day_of_week: Weekday,
+}
+
+impl Locale {
+    /// Create a `Locale` from a language tag, like `de` or `pt-BR`.
+    ///
+    /// The other conventions are defaulted from the language and region.
+    pub fn new<S: Into<KString>>(tag: S) -> Self {
+        let tag = tag.into();
+        let language = language(tag.as_str()).to_ascii_lowercase();
+        // Empty when there's no region.
+        let region = region(tag.as_str())
+            .map(|r| r.to_ascii_uppercase())
+            .unwrap_or_default();
+
+        let (decimal_separator, grouping_separator) = match language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" => (',', '.'),
+            "fr" => (',', '\u{202f}'),
+            _ => ('.', ','),
+        };
+        let first_day_of_week = match (language.as_str(), region.as_str()) {
+            (_, "US") | (_, "CA") | (_, "BR") | (_, "JP") => Weekday::Sun,
+            ("en", "") => Weekday::Sun,
+            _ => Weekday::Mon,
+        };
+        let currency = region_currency(region.as_str()).map(KString::from_static);
+
+        Self {
+            tag,
+            currency,
+            decimal_separator,
+            grouping_separator,
+            first_day_of_week,
+        }
+    }
+
+    /// Set the ISO 4217 currency code, like `EUR`.
+    pub fn with_currency<S: Into<KString>>(mut self, currency: S) -> Self {
+        self.currency = Some(currency.into());
+        self
+    }
+
+    /// Set the separator between the whole and fractional parts of a number.
+    pub fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Set the separator between groups of thousands.
+    pub fn with_grouping_separator(mut self, separator: char) -> Self {
+        self.grouping_separator = separator;
+        self
+    }
+
+    /// Set the day weeks start on.
+    pub fn with_first_day_of_week(mut self, weekday: Weekday) -> Self {
+        self.first_day_of_week = weekday;
+        self
+    }
+
+    /// The full language tag.
+    pub fn tag(&self) -> &str {
+        self.tag.as_str()
+    }
+
+    /// The language subtag, like `pt` for `pt-BR`.
+    pub fn language(&self) -> &str {
+        language(self.tag.as_str())
+    }
+
+    /// The region subtag, like `BR` for `pt-BR`.
+    pub fn region(&self) -> Option<&str> {
+        region(self.tag.as_str())
+    }
+
+    /// The ISO 4217 currency code, like `EUR`, if known.
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_ref().map(|c| c.as_str())
+    }
+
+    /// The separator between the whole and fractional parts of a number.
+    pub fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    /// The separator between groups of thousands.
+    pub fn grouping_separator(&self) -> char {
+        self.grouping_separator
+    }
+
+    /// The day weeks start on, like for the week of the year, `%U`, of `date`.
+    pub fn first_day_of_week(&self) -> Weekday {
+        self.first_day_of_week
+    }
+
+    /// Whether `n` takes the singular form.
+    pub fn is_singular(&self, n: i64) -> bool {
+        match self.language() {
+            "fr" | "pt" => n == 0 || n == 1,
+            _ => n == 1,
+        }
+    }
+
+    /// Format `value` with a fixed number of fractional digits and grouped thousands.
+    pub fn format_number(&self, value: f64, precision: usize) -> String {
+        // Round half away from zero, rather than `format!`'s half to even.
+        let factor = 10f64.powi(precision as i32);
+        let rounded = (value.abs() * factor).round() / factor;
+        let formatted = format!("{:.*}", precision, rounded);
+        let mut parts = formatted.splitn(2, '.');
+        let whole = parts.next().unwrap_or("");
+        let fraction = parts.next();
+
+        let mut number = String::with_capacity(formatted.len() + whole.len() / 3 + 1);
+        let is_zero = formatted.chars().all(|c| c == '0' || c == '.');
+        if value.is_sign_negative() && !is_zero {
+            number.push('-');
+        }
+        for (i, digit) in whole.chars().enumerate() {
+            if 0 < i && (whole.len() - i) % 3 == 0 {
+                number.push(self.grouping_separator);
+            }
+            number.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            number.push(self.decimal_separator);
+            number.push_str(fraction);
+        }
+        number
+    }
+
+    /// Format `amount` in `currency`, or the locale's currency.
+    ///
+    /// Without a currency, only the number is formatted.
+    pub fn format_money(&self, amount: f64, currency: Option<&str>) -> String {
+        let currency = match currency.or_else(|| self.currency()) {
+            Some(currency) => currency,
+            None => return self.format_number(amount, 2),
+        };
+        let (symbol, precision) = currency_symbol(currency);
+        let number = self.format_number(amount, precision);
+        match self.language() {
+            "en" => match number.chars().next() {
+                Some('-') => format!("-{}{}", symbol, &number[1..]),
+                _ => format!("{}{}", symbol, number),
+            },
+            "pt" | "nl" => format!("{}\u{a0}{}", symbol, number),
+            _ => format!("{}\u{a0}{}", number, symbol),
+        }
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+fn language(tag: &str) -> &str {
+    tag.split(&['-', '_'][..]).next().unwrap_or(tag)
+}
+
+fn region(tag: &str) -> Option<&str> {
+    tag.split(&['-', '_'][..])
+        .skip(1)
+        .find(|subtag| subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+fn region_currency(region: &str) -> Option<&'static str> {
+    let currency = match region {
+        "US" => "USD",
+        "CA" => "CAD",
+        "AU" => "AUD",
+        "GB" => "GBP",
+        "CH" => "CHF",
+        "BR" => "BRL",
+        "JP" => "JPY",
+        "AT" | "BE" | "DE" | "ES" | "FI" | "FR" | "IE" | "IT" | "NL" | "PT" => "EUR",
+        _ => return None,
+    };
+    Some(currency)
+}
+
+/// The symbol and number of fractional digits for a currency.
+fn currency_symbol(currency: &str) -> (&str, usize) {
+    match currency {
+        "USD" | "CAD" | "AUD" => ("$", 2),
+        "EUR" => ("€", 2),
+        "GBP" => ("£", 2),
+        "BRL" => ("R$", 2),
+        "JPY" => ("¥", 0),
+        _ => (currency, 2),
+    }
+}
+
+/// The locale named by a filter's argument, or else the render's `Locale`.
+pub(crate) fn filter_locale(argument: Option<&str>, runtime: &Runtime<'_>) -> Locale {
+    match argument {
+        Some(tag) => Locale::new(KString::from_ref(tag)),
+        None => runtime
+            .get_register::<Locale>()
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
+
+/// Month and weekday names for a language.
+#[derive(Debug)]
+pub(crate) struct DateNames {
+    pub(crate) language: &'static str,
+    pub(crate) months: [&'static str; 12],
+    pub(crate) months_abbr: [&'static str; 12],
+    /// Starting with Sunday.
+    pub(crate) weekdays: [&'static str; 7],
+    /// Starting with Sunday.
+    pub(crate) weekdays_abbr: [&'static str; 7],
+}
+
+/// Looks up the names for a language tag, like `de` or `pt-BR`.
+pub(crate) fn date_names(tag: &str) -> Option<&'static DateNames> {
+    let language = language(tag);
+    DATE_NAMES
+        .iter()
+        .find(|names| names.language.eq_ignore_ascii_case(language))
+}
+
+const DATE_NAMES: &[DateNames] = &[
+    DateNames {
+        language: "en",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_abbr: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekdays: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        weekdays_abbr: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+    },
+    DateNames {
+        language: "de",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_abbr: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekdays: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        weekdays_abbr: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+    },
+    DateNames {
+        language: "es",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        months_abbr: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        weekdays_abbr: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+    },
+    DateNames {
+        language: "fr",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_abbr: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        weekdays_abbr: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+    },
+    DateNames {
+        language: "it",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        months_abbr: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        weekdays_abbr: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+    },
+    DateNames {
+        language: "nl",
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        months_abbr: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "zondag",
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+        ],
+        weekdays_abbr: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+    },
+    DateNames {
+        language: "pt",
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        months_abbr: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekdays: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        weekdays_abbr: ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"],
+    },
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locale_language() {
+        assert_eq!(Locale::new("pt-BR").language(), "pt");
+        assert_eq!(Locale::new("de_AT").language(), "de");
+        assert_eq!(Locale::new("fr").language(), "fr");
+    }
+
+    #[test]
+    fn locale_conventions() {
+        let locale = Locale::new("pt-BR");
+        assert_eq!(locale.region(), Some("BR"));
+        assert_eq!(locale.currency(), Some("BRL"));
+        assert_eq!(locale.first_day_of_week(), Weekday::Sun);
+
+        let locale = Locale::new("de-DE");
+        assert_eq!(locale.currency(), Some("EUR"));
+        assert_eq!(locale.decimal_separator(), ',');
+        assert_eq!(locale.first_day_of_week(), Weekday::Mon);
+
+        let locale = Locale::new("en");
+        assert_eq!(locale.region(), None);
+        assert_eq!(locale.currency(), None);
+    }
+
+    #[test]
+    fn locale_format_number() {
+        let locale = Locale::new("en");
+        assert_eq!(locale.format_number(1_234_567.891, 2), "1,234,567.89");
+        assert_eq!(locale.format_number(-999.5, 0), "-1,000");
+        assert_eq!(locale.format_number(-0.001, 2), "0.00");
+        assert_eq!(locale.format_number(12.0, 0), "12");
+
+        let locale = Locale::new("de");
+        assert_eq!(locale.format_number(1_234.5, 2), "1.234,50");
+    }
+
+    #[test]
+    fn locale_format_money() {
+        assert_eq!(
+            Locale::new("en-US").format_money(-1234.5, None),
+            "-$1,234.50"
+        );
+        assert_eq!(
+            Locale::new("de-DE").format_money(1234.5, None),
+            "1.234,50\u{a0}€"
+        );
+        assert_eq!(
+            Locale::new("en").format_money(1234.5, Some("JPY")),
+            "¥1,235"
+        );
+        assert_eq!(Locale::new("en").format_money(1234.5, None), "1,234.50");
+    }
+
+    #[test]
+    fn locale_is_singular() {
+        assert!(!Locale::new("en").is_singular(0));
+        assert!(Locale::new("fr").is_singular(0));
+        assert!(Locale::new("pt-BR").is_singular(1));
+    }
+
+    #[test]
+    fn date_names_lookup() {
+        assert_eq!(date_names("de-CH").map(|n| n.months[2]), Some("März"));
+        assert_eq!(date_names("EN").map(|n| n.weekdays[0]), Some("Sunday"));
+        assert!(date_names("xx").is_none());
+    }
+}
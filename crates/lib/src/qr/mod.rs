@@ -0,0 +1,171 @@
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+use qrcode::EcLevel;
+
+use crate::{invalid_argument, invalid_input};
+
+/// Default minimum width and height of the rendered code, in pixels.
+const DEFAULT_SIZE: u32 = 200;
+
+/// Largest `size` accepted, bounding the memory a template can make a render allocate.
+const MAX_SIZE: i32 = 2048;
+
+fn parse_ec_level(level: &str) -> Option<EcLevel> {
+    match level {
+        "L" | "l" => Some(EcLevel::L),
+        "M" | "m" => Some(EcLevel::M),
+        "Q" | "q" => Some(EcLevel::Q),
+        "H" | "h" => Some(EcLevel::H),
+        _ => None,
+    }
+}
+
+fn svg_data_uri(code: &qrcode::QrCode, size: u32) -> String {
+    let svg = code
+        .render::<qrcode::render::svg::Color<'_>>()
+        .min_dimensions(size, size)
+        .build();
+    format!("data:image/svg+xml;base64,{}", base64::encode(svg))
+}
+
+fn png_data_uri(code: &qrcode::QrCode, size: u32) -> Result<String> {
+    let image = code
+        .render::<image::Luma<u8>>()
+        .min_dimensions(size, size)
+        .build();
+    let mut png = Vec::new();
+    image::DynamicImage::ImageLuma8(image)
+        .write_to(&mut png, image::ImageOutputFormat::Png)
+        .map_err(|e| invalid_input(e.to_string()))?;
+    Ok(format!("data:image/png;base64,{}", base64::encode(png)))
+}
+
+#[derive(Debug, FilterParameters)]
+struct QrCodeArgs {
+    #[parameter(
+        description = "The minimum width and height of the image, in pixels, up to 2048. Defaults to 200.",
+        arg_type = "integer",
+        mode = "keyword"
+    )]
+    size: Option<Expression>,
+
+    #[parameter(
+        description = "The error correction level, one of `L`, `M`, `Q` or `H`. Defaults to `M`.",
+        arg_type = "str",
+        mode = "keyword"
+    )]
+    error_correction: Option<Expression>,
+
+    #[parameter(
+        description = "The image format, `svg` or `png`. Defaults to `svg`.",
+        arg_type = "str",
+        mode = "keyword"
+    )]
+    format: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "qr_code",
+    description = "Encodes a string as a QR code image, returned as a `data:` URI suitable for an `<img>` tag's `src`.",
+    parameters(QrCodeArgs),
+    parsed(QrCodeFilter)
+)]
+pub struct QrCode;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "qr_code"]
+struct QrCodeFilter {
+    #[parameters]
+    args: QrCodeArgs,
+}
+
+impl Filter for QrCodeFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let size = match args.size {
+            Some(size) if size < 1 => {
+                return invalid_argument("size", "Positive number expected").into_err();
+            }
+            Some(size) if MAX_SIZE < size => {
+                return invalid_argument("size", "At most 2048 expected").into_err();
+            }
+            Some(size) => size as u32,
+            None => DEFAULT_SIZE,
+        };
+        let level = match args.error_correction {
+            Some(level) => parse_ec_level(level.as_str())
+                .ok_or_else(|| invalid_argument("error_correction", "L, M, Q or H expected"))?,
+            None => EcLevel::M,
+        };
+
+        let input = input.to_kstr();
+        let code = qrcode::QrCode::with_error_correction_level(input.as_bytes(), level)
+            .map_err(|e| invalid_input(e.to_string()))?;
+
+        let uri = match args.format.as_ref().map(|f| f.as_str()) {
+            None | Some("svg") => svg_data_uri(&code, size),
+            Some("png") => png_data_uri(&code, size)?,
+            Some(_) => return invalid_argument("format", "svg or png expected").into_err(),
+        };
+        Ok(Value::scalar(uri))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_qr_code(size: i32, format: &str) -> Result<Value> {
+        let args = liquid_core::compiler::FilterArguments {
+            positional: Box::new(Vec::new().into_iter()),
+            keyword: Box::new(
+                vec![
+                    ("size", Expression::Literal(Value::scalar(size))),
+                    (
+                        "format",
+                        Expression::Literal(Value::scalar(format.to_owned())),
+                    ),
+                ]
+                .into_iter(),
+            ),
+        };
+        let runtime = Runtime::default();
+        QrCode
+            .parse(args)?
+            .evaluate(&Value::scalar("TICKET-0042"), &runtime)
+    }
+
+    #[test]
+    fn unit_qr_code() {
+        let uri = liquid_core::call_filter!(QrCode, "TICKET-0042").unwrap();
+        let uri = uri.to_kstr();
+        let prefix = "data:image/svg+xml;base64,";
+        assert!(uri.starts_with(prefix));
+        let svg = String::from_utf8(base64::decode(&uri[prefix.len()..]).unwrap()).unwrap();
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn unit_qr_code_png() {
+        let uri = call_qr_code(64, "png").unwrap();
+        let uri = uri.to_kstr();
+        let prefix = "data:image/png;base64,";
+        assert!(uri.starts_with(prefix));
+        let png = base64::decode(&uri[prefix.len()..]).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn unit_qr_code_size_limit() {
+        assert!(call_qr_code(MAX_SIZE, "png").is_ok());
+        call_qr_code(MAX_SIZE + 1, "png").unwrap_err();
+        call_qr_code(MAX_SIZE + 1, "svg").unwrap_err();
+    }
+}
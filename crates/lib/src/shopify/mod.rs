@@ -1,3 +1,5 @@
+mod money;
 mod pluralize;
 
+pub use self::money::*;
 pub use self::pluralize::*;
@@ -0,0 +1,87 @@
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+use crate::invalid_input;
+use crate::locale::filter_locale;
+
+// shopify-specific
+
+#[derive(Debug, FilterParameters)]
+struct MoneyArgs {
+    #[parameter(
+        description = "The ISO 4217 currency code, like `EUR`. Defaults to the locale's currency.",
+        arg_type = "str"
+    )]
+    currency: Option<Expression>,
+    #[parameter(
+        description = "The locale for separators and symbol placement, like `de`. Defaults to the render's `Locale`.",
+        arg_type = "str"
+    )]
+    locale: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "money",
+    description = "Formats an amount in cents as a price, like `$1,234.50`.",
+    parameters(MoneyArgs),
+    parsed(MoneyFilter)
+)]
+pub struct Money;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "money"]
+struct MoneyFilter {
+    #[parameters]
+    args: MoneyArgs,
+}
+
+impl Filter for MoneyFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let cents = input
+            .as_scalar()
+            .and_then(|s| s.to_float())
+            .ok_or_else(|| invalid_input("Number expected"))?;
+
+        let locale = filter_locale(args.locale.as_ref().map(|l| l.as_str()), runtime);
+        let currency = args.currency.as_ref().map(|c| c.as_str());
+        Ok(Value::scalar(locale.format_money(cents / 100.0, currency)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_money() {
+        assert_eq!(
+            liquid_core::call_filter!(Money, 123_450i32, "USD").unwrap(),
+            liquid_core::value!("$1,234.50")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Money, 123_450i32).unwrap(),
+            liquid_core::value!("1,234.50")
+        );
+    }
+
+    #[test]
+    fn unit_money_locale() {
+        assert_eq!(
+            liquid_core::call_filter!(Money, 123_450i32, "EUR", "de").unwrap(),
+            liquid_core::value!("1.234,50\u{a0}€")
+        );
+    }
+
+    #[test]
+    fn unit_money_not_number() {
+        liquid_core::call_filter!(Money, "free").unwrap_err();
+    }
+}
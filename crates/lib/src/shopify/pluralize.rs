@@ -7,6 +7,7 @@ use liquid_core::{
 use liquid_core::{Value, ValueView};
 
 use crate::invalid_input;
+use crate::locale::filter_locale;
 
 // shopify-specific
 
@@ -16,6 +17,11 @@ struct PluralizeArgs {
     singular: Expression,
     #[parameter(description = "The plural version of the string.")]
     plural: Expression,
+    #[parameter(
+        description = "The locale whose plural rule to use, like `fr`. Defaults to the render's `Locale`.",
+        arg_type = "str"
+    )]
+    locale: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -43,7 +49,8 @@ impl Filter for PluralizeFilter {
             .and_then(|s| s.to_integer())
             .ok_or_else(|| invalid_input("Whole number expected"))?;
 
-        if (n as isize) == 1 {
+        let locale = filter_locale(args.locale.as_ref().map(|l| l.as_str()), runtime);
+        if locale.is_singular(i64::from(n)) {
             Ok(args.singular.to_value())
         } else {
             Ok(args.plural.to_value())
@@ -77,4 +84,16 @@ mod tests {
             liquid_core::value!("many")
         );
     }
+
+    #[test]
+    fn unit_pluralize_locale() {
+        assert_eq!(
+            liquid_core::call_filter!(Pluralize, 0i32, "un", "plusieurs", "fr").unwrap(),
+            liquid_core::value!("un")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Pluralize, 2i32, "un", "plusieurs", "fr").unwrap(),
+            liquid_core::value!("plusieurs")
+        );
+    }
 }
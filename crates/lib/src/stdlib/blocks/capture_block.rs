@@ -31,7 +31,8 @@ impl Renderable for Capture {
         let output = String::from_utf8(captured).expect("render only writes UTF-8");
         runtime
             .stack_mut()
-            .set_global(self.id.to_owned(), Value::scalar(output));
+            .try_set_global(self.id.to_owned(), Value::scalar(output))
+            .trace_with(|| self.trace().into())?;
         Ok(())
     }
 }
@@ -99,6 +99,8 @@ fn evaluate_attr(attr: &Option<Expression>, runtime: &mut Runtime<'_>) -> Result
 #[derive(Debug)]
 struct For {
     var_name: kstring::KString,
+    // Where `var_name` is declared, when parsed from a template.
+    position: Option<String>,
     range: Range,
     item_template: Template,
     else_template: Option<Template>,
@@ -174,10 +176,14 @@ impl Renderable for For {
 
             range_len => {
                 runtime.run_in_scope(|mut scope| -> Result<()> {
+                    if let Some(ref position) = self.position {
+                        scope.stack_mut().declare(self.var_name.clone(), position);
+                    }
                     let mut helper_vars = Object::new();
                     helper_vars.insert("length".into(), Value::scalar(range_len as i32));
 
                     for (i, v) in range.into_iter().enumerate() {
+                        scope.count_iteration()?;
                         helper_vars.insert("index0".into(), Value::scalar(i as i32));
                         helper_vars.insert("index".into(), Value::scalar((i + 1) as i32));
                         helper_vars
@@ -237,6 +243,26 @@ fn trace_for_tag(
     )
 }
 
+/// Create a `{% for %}` block, rendering `item_template` for each of `items` as `var_name` and
+/// `else_template` when there are none.
+pub fn for_loop(
+    var_name: &str,
+    items: Expression,
+    item_template: Template,
+    else_template: Option<Template>,
+) -> Box<dyn Renderable> {
+    Box::new(For {
+        var_name: kstring::KString::from_ref(var_name),
+        position: None,
+        range: Range::Array(items),
+        item_template,
+        else_template,
+        limit: None,
+        offset: None,
+        reversed: false,
+    })
+}
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct ForBlock;
 
@@ -267,10 +293,9 @@ impl ParseBlock for ForBlock {
         mut tokens: TagBlock<'_, '_>,
         options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let var_name = arguments
-            .expect_next("Identifier expected.")?
-            .expect_identifier()
-            .into_result()?;
+        let var_name = arguments.expect_next("Identifier expected.")?;
+        let (line, column) = var_name.line_col();
+        let var_name = var_name.expect_identifier().into_result()?;
 
         arguments
             .expect_next("\"in\" expected.")?
@@ -331,6 +356,7 @@ impl ParseBlock for ForBlock {
         tokens.assert_empty();
         Ok(Box::new(For {
             var_name: kstring::KString::from_ref(var_name),
+            position: Some(format!("{}:{}", line, column)),
             range,
             item_template,
             else_template,
@@ -410,6 +436,7 @@ impl Renderable for TableRow {
             helper_vars.insert("length".into(), Value::scalar(range_len as i32));
 
             for (i, v) in range.into_iter().enumerate() {
+                scope.count_iteration()?;
                 let (col_index, row_index) = match cols {
                     Some(cols) => (i % cols, i / cols),
                     None => (i, 0),
@@ -4,7 +4,7 @@ use std::io::Write;
 use liquid_core::compiler::BlockElement;
 use liquid_core::compiler::TagToken;
 use liquid_core::error::ResultLiquidExt;
-use liquid_core::value::{ValueView, ValueViewCmp};
+use liquid_core::value::{ValueCow, ValueView, ValueViewCmp};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -52,6 +52,36 @@ impl ComparisonOperator {
             _ => Err(()),
         }
     }
+
+    fn from_alias(s: &str) -> ::std::result::Result<Self, ()> {
+        match s {
+            "eq" => Ok(ComparisonOperator::Equals),
+            "ne" => Ok(ComparisonOperator::NotEquals),
+            "lt" => Ok(ComparisonOperator::LessThan),
+            "gt" => Ok(ComparisonOperator::GreaterThan),
+            "le" => Ok(ComparisonOperator::LessThanEquals),
+            "ge" => Ok(ComparisonOperator::GreaterThanEquals),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Evaluate one side of a comparison, treating an undefined variable as `nil` when compared
+/// against `nil`, `empty` or `blank`, like `missing == blank`.
+fn evaluate_operand<'c>(
+    operand: &'c Expression,
+    other: &Expression,
+    runtime: &'c Runtime<'_>,
+) -> Result<ValueCow<'c>> {
+    let nil_like = other
+        .as_literal()
+        .map(|other| other.is_nil() || other.is_state())
+        .unwrap_or(false);
+    if nil_like {
+        Ok(operand.lookup(runtime)?.unwrap_or_nil())
+    } else {
+        operand.evaluate(runtime)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -62,20 +92,26 @@ struct BinaryCondition {
 }
 
 impl BinaryCondition {
-    pub fn evaluate(&self, runtime: &Runtime<'_>) -> Result<bool> {
-        let a = self.lh.evaluate(runtime)?;
+    pub fn evaluate(&self, runtime: &Runtime<'_>, strict: bool) -> Result<bool> {
+        let a = evaluate_operand(&self.lh, &self.rh, runtime)?;
         let ca = ValueViewCmp::new(a.as_view());
-        let b = self.rh.evaluate(runtime)?;
+        let b = evaluate_operand(&self.rh, &self.lh, runtime)?;
         let cb = ValueViewCmp::new(b.as_view());
 
         let result = match self.comparison {
             ComparisonOperator::Equals => ca == cb,
             ComparisonOperator::NotEquals => ca != cb,
+            ComparisonOperator::Contains => contains_check(a.as_view(), b.as_view())?,
+            _ if strict && ca.partial_cmp(&cb).is_none() => {
+                return Error::with_msg("Cannot compare values")
+                    .context("left", a.type_name())
+                    .context("right", b.type_name())
+                    .into_err();
+            }
             ComparisonOperator::LessThan => ca < cb,
             ComparisonOperator::GreaterThan => ca > cb,
             ComparisonOperator::LessThanEquals => ca <= cb,
             ComparisonOperator::GreaterThanEquals => ca >= cb,
-            ComparisonOperator::Contains => contains_check(a.as_view(), b.as_view())?,
         };
 
         Ok(result)
@@ -94,9 +130,12 @@ struct ExistenceCondition {
 }
 
 impl ExistenceCondition {
-    pub fn evaluate(&self, runtime: &Runtime<'_>) -> Result<bool> {
-        let a = self.lh.try_evaluate(runtime);
-        let a = a.unwrap_or_default();
+    pub fn evaluate(&self, runtime: &Runtime<'_>, strict: bool) -> Result<bool> {
+        let a = if strict {
+            self.lh.evaluate(runtime)?
+        } else {
+            self.lh.try_evaluate(runtime).unwrap_or_default()
+        };
         let is_truthy = a.query_state(liquid_core::value::State::Truthy);
         Ok(is_truthy)
     }
@@ -117,15 +156,15 @@ enum Condition {
 }
 
 impl Condition {
-    pub fn evaluate(&self, runtime: &Runtime<'_>) -> Result<bool> {
+    pub fn evaluate(&self, runtime: &Runtime<'_>, strict: bool) -> Result<bool> {
         match *self {
-            Condition::Binary(ref c) => c.evaluate(runtime),
-            Condition::Existence(ref c) => c.evaluate(runtime),
+            Condition::Binary(ref c) => c.evaluate(runtime, strict),
+            Condition::Existence(ref c) => c.evaluate(runtime, strict),
             Condition::Conjunction(ref left, ref right) => {
-                Ok(left.evaluate(runtime)? && right.evaluate(runtime)?)
+                Ok(left.evaluate(runtime, strict)? && right.evaluate(runtime, strict)?)
             }
             Condition::Disjunction(ref left, ref right) => {
-                Ok(left.evaluate(runtime)? || right.evaluate(runtime)?)
+                Ok(left.evaluate(runtime, strict)? || right.evaluate(runtime, strict)?)
             }
         }
     }
@@ -142,6 +181,79 @@ impl fmt::Display for Condition {
     }
 }
 
+/// A condition, as accepted by `{% if %}`, for evaluating outside of a template.
+#[derive(Clone, Debug)]
+pub struct IfCondition {
+    condition: Condition,
+}
+
+impl IfCondition {
+    /// Parse the arguments of an `{% if %}` tag.
+    pub fn parse(arguments: TagTokenIter<'_>, options: &Language) -> Result<Self> {
+        let condition = parse_condition(arguments, options)?;
+        Ok(Self { condition })
+    }
+
+    /// A condition that `lh` is truthy.
+    pub fn exists(lh: Expression) -> Self {
+        let condition = Condition::Existence(ExistenceCondition { lh });
+        Self { condition }
+    }
+
+    /// A condition comparing `lh` and `rh` with an operator, like `==` or `contains`.
+    pub fn compare(lh: Expression, operator: &str, rh: Expression) -> Result<Self> {
+        let comparison = ComparisonOperator::from_str(operator)
+            .or_else(|_| ComparisonOperator::from_alias(operator))
+            .map_err(|_| {
+                Error::with_msg("Invalid comparison operator")
+                    .context("operator", operator.to_owned())
+            })?;
+        let condition = Condition::Binary(BinaryCondition { lh, comparison, rh });
+        Ok(Self { condition })
+    }
+
+    /// A condition that both `self` and `other` hold.
+    pub fn and(self, other: Self) -> Self {
+        let condition = Condition::Conjunction(Box::new(self.condition), Box::new(other.condition));
+        Self { condition }
+    }
+
+    /// A condition that either `self` or `other` holds.
+    pub fn or(self, other: Self) -> Self {
+        let condition = Condition::Disjunction(Box::new(self.condition), Box::new(other.condition));
+        Self { condition }
+    }
+
+    /// Create an `{% if %}` block, rendering `if_true` when the condition holds and `if_false`
+    /// otherwise.
+    pub fn into_block(self, if_true: Template, if_false: Option<Template>) -> Box<dyn Renderable> {
+        Box::new(Conditional {
+            tag_name: "if".to_owned(),
+            condition: self.condition,
+            mode: true,
+            if_true,
+            if_false,
+        })
+    }
+
+    /// Evaluate with the same semantics as `{% if %}`.
+    pub fn evaluate(&self, runtime: &Runtime<'_>) -> Result<bool> {
+        self.condition.evaluate(runtime, false)
+    }
+
+    /// Evaluate, raising an error for unknown variables and for ordering values that can't be
+    /// compared, like a string and a number.
+    pub fn evaluate_strict(&self, runtime: &Runtime<'_>) -> Result<bool> {
+        self.condition.evaluate(runtime, true)
+    }
+}
+
+impl fmt::Display for IfCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.condition)
+    }
+}
+
 #[derive(Debug)]
 struct Conditional {
     tag_name: String,
@@ -178,7 +290,7 @@ fn contains_check(a: &dyn ValueView, b: &dyn ValueView) -> Result<bool> {
 
 impl Conditional {
     fn compare(&self, runtime: &Runtime<'_>) -> Result<bool> {
-        let result = self.condition.evaluate(runtime)?;
+        let result = self.condition.evaluate(runtime, false)?;
 
         Ok(result == self.mode)
     }
@@ -239,16 +351,25 @@ impl<'a> PeekableTagTokenIter<'a> {
     }
 }
 
-fn parse_atom_condition(arguments: &mut PeekableTagTokenIter<'_>) -> Result<Condition> {
+fn parse_atom_condition(
+    arguments: &mut PeekableTagTokenIter<'_>,
+    options: &Language,
+) -> Result<Condition> {
     let lh = arguments
         .expect_next("Value expected.")?
         .expect_value()
         .into_result()?;
-    let cond = match arguments
-        .peek()
-        .map(TagToken::as_str)
-        .and_then(|op| ComparisonOperator::from_str(op).ok())
-    {
+    let cond = match arguments.peek().map(TagToken::as_str).and_then(|op| {
+        ComparisonOperator::from_str(op)
+            .or_else(|_| {
+                if options.keyword_aliases {
+                    ComparisonOperator::from_alias(op)
+                } else {
+                    Err(())
+                }
+            })
+            .ok()
+    }) {
         Some(op) => {
             arguments.next();
             let rh = arguments
@@ -267,12 +388,20 @@ fn parse_atom_condition(arguments: &mut PeekableTagTokenIter<'_>) -> Result<Cond
     Ok(cond)
 }
 
-fn parse_conjunction_chain(arguments: &mut PeekableTagTokenIter<'_>) -> Result<Condition> {
-    let mut lh = parse_atom_condition(arguments)?;
-
-    while let Some("and") = arguments.peek().map(TagToken::as_str) {
+fn parse_conjunction_chain(
+    arguments: &mut PeekableTagTokenIter<'_>,
+    options: &Language,
+) -> Result<Condition> {
+    let mut lh = parse_atom_condition(arguments, options)?;
+
+    loop {
+        match arguments.peek().map(TagToken::as_str) {
+            Some("and") => {}
+            Some("&&") if options.keyword_aliases => {}
+            _ => break,
+        }
         arguments.next();
-        let rh = parse_atom_condition(arguments)?;
+        let rh = parse_atom_condition(arguments, options)?;
         lh = Condition::Conjunction(Box::new(lh), Box::new(rh));
     }
 
@@ -280,19 +409,21 @@ fn parse_conjunction_chain(arguments: &mut PeekableTagTokenIter<'_>) -> Result<C
 }
 
 /// Common parsing for "if" and "unless" condition
-fn parse_condition(arguments: TagTokenIter<'_>) -> Result<Condition> {
+fn parse_condition(arguments: TagTokenIter<'_>, options: &Language) -> Result<Condition> {
     let mut arguments = PeekableTagTokenIter {
         iter: arguments,
         peeked: None,
     };
-    let mut lh = parse_conjunction_chain(&mut arguments)?;
+    let mut lh = parse_conjunction_chain(&mut arguments, options)?;
 
     while let Some(token) = arguments.next() {
-        token
-            .expect_str("or")
-            .into_result_custom_msg("\"and\" or \"or\" expected.")?;
+        if !(options.keyword_aliases && token.as_str() == "||") {
+            token
+                .expect_str("or")
+                .into_result_custom_msg("\"and\" or \"or\" expected.")?;
+        }
 
-        let rh = parse_conjunction_chain(&mut arguments)?;
+        let rh = parse_conjunction_chain(&mut arguments, options)?;
         lh = Condition::Disjunction(Box::new(lh), Box::new(rh));
     }
 
@@ -329,7 +460,7 @@ impl ParseBlock for UnlessBlock {
         mut tokens: TagBlock<'_, '_>,
         options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let condition = parse_condition(arguments)?;
+        let condition = parse_condition(arguments, options)?;
 
         let mut if_true = Vec::new();
         let mut if_false = None;
@@ -371,7 +502,7 @@ fn parse_if(
     tokens: &mut TagBlock<'_, '_>,
     options: &Language,
 ) -> Result<Box<dyn Renderable>> {
-    let condition = parse_condition(arguments)?;
+    let condition = parse_condition(arguments, options)?;
 
     let mut if_true = Vec::new();
     let mut if_false = None;
@@ -615,6 +746,27 @@ mod test {
         assert_eq!(output, "yep, not also truthy");
     }
 
+    #[test]
+    fn undefined_comparison() {
+        let text = "{% if missing == blank %}blank{% endif %}{% if nothing != nil %}set{% endif %}";
+        let template = compiler::parse(text, &options())
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        runtime.stack_mut().set_global("nothing", Value::Nil);
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "blank");
+
+        let text = "{% if missing == 1 %}one{% endif %}";
+        let template = compiler::parse(text, &options())
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        assert!(template.render(&mut runtime).is_err());
+    }
+
     #[test]
     fn multiple_elif_blocks() {
         let text = concat!(
@@ -816,4 +968,59 @@ mod test {
         let output = template.render(&mut runtime).unwrap();
         assert_eq!(output, "if true");
     }
+
+    #[test]
+    fn keyword_aliases() {
+        let mut options = options();
+        options.keyword_aliases = true;
+
+        let text = "{% if 1 eq 1 || 2 ne 2 && 3 lt 3 %}if true{% else %}if false{% endif %}";
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "if true");
+
+        let text = "{% unless 2 ge 3 && 2 le 1 %}unless true{% endunless %}";
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "unless true");
+    }
+
+    #[test]
+    fn keyword_aliases_disabled() {
+        let text = "{% if 1 == 1 && 2 == 2 %}if true{% endif %}";
+        compiler::parse(text, &options()).unwrap_err();
+
+        let text = "{% if 1 == 1 || 2 == 2 %}if true{% endif %}";
+        compiler::parse(text, &options()).unwrap_err();
+
+        let text = "{% if 1 eq 1 %}if true{% endif %}";
+        compiler::parse(text, &options()).unwrap_err();
+    }
+
+    #[test]
+    fn standalone_condition() {
+        let arguments = compiler::parse_tag_arguments("a > 1 and b").unwrap();
+        let condition = IfCondition::parse(arguments, &options()).unwrap();
+        assert_eq!(condition.to_string(), "a > 1 and b");
+
+        let mut runtime = Runtime::new();
+        runtime.stack_mut().set_global("a", Value::scalar(2));
+        assert!(!condition.evaluate(&runtime).unwrap());
+        condition.evaluate_strict(&runtime).unwrap_err();
+
+        runtime.stack_mut().set_global("b", Value::scalar(true));
+        assert!(condition.evaluate_strict(&runtime).unwrap());
+
+        runtime.stack_mut().set_global("a", Value::scalar("2"));
+        assert!(!condition.evaluate(&runtime).unwrap());
+        condition.evaluate_strict(&runtime).unwrap_err();
+    }
 }
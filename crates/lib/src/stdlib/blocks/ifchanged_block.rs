@@ -27,7 +27,7 @@ impl Renderable for IfChanged {
             .trace_with(|| self.trace().into())?;
 
         let rendered = String::from_utf8(rendered).expect("render only writes UTF-8");
-        if runtime.get_register_mut::<State>().has_changed(&rendered) {
+        if runtime.with_render_register(|state: &mut State| state.has_changed(&rendered))? {
             write!(writer, "{}", rendered).replace("Failed to render")?;
         }
 
@@ -9,9 +9,11 @@ mod raw_block;
 pub use self::capture_block::CaptureBlock;
 pub use self::case_block::CaseBlock;
 pub use self::comment_block::CommentBlock;
+pub use self::for_block::for_loop;
 pub use self::for_block::ForBlock;
 pub use self::for_block::TableRowBlock;
 pub use self::if_block::IfBlock;
+pub use self::if_block::IfCondition;
 pub use self::if_block::UnlessBlock;
 pub use self::ifchanged_block::IfChangedBlock;
 pub use self::raw_block::RawBlock;
@@ -1,13 +1,14 @@
 use std::cmp;
+use std::convert::TryFrom;
 
-use liquid_core::value::ValueViewCmp;
+use liquid_core::value::{ArrayView, ValueViewCmp};
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
 use liquid_core::{
     Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
 };
-use liquid_core::{Value, ValueCow, ValueView};
+use liquid_core::{ObjectView, Value, ValueCow, ValueView};
 
 use crate::{invalid_argument, invalid_input};
 
@@ -28,12 +29,18 @@ struct JoinArgs {
         arg_type = "str"
     )]
     separator: Option<Expression>,
+
+    #[parameter(
+        description = "The property of each object to join instead of the objects themselves.",
+        arg_type = "str"
+    )]
+    property: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "join",
-    description = "Combines the items in an array into a single string using the argument as a separator.",
+    description = "Combines the items in an array into a single string using the argument as a separator. Nil items, and objects missing `property`, join as empty strings.",
     parameters(JoinArgs),
     parsed(JoinFilter)
 )]
@@ -55,7 +62,22 @@ impl Filter for JoinFilter {
         let input = input
             .as_array()
             .ok_or_else(|| invalid_input("Array of strings expected"))?;
-        let input = input.values().map(|x| x.to_kstr());
+        let input: Vec<_> = if let Some(property) = &args.property {
+            if !input.values().all(|v| v.is_object()) {
+                return Err(invalid_input("Array of objects expected"));
+            }
+            input
+                .values()
+                .map(|v| {
+                    v.as_object()
+                        .and_then(|obj| obj.get(property.as_str()))
+                        .map(|v| v.to_kstr().into_owned())
+                        .unwrap_or_default()
+                })
+                .collect()
+        } else {
+            input.values().map(|v| v.to_kstr().into_owned()).collect()
+        };
 
         Ok(Value::scalar(itertools::join(input, separator.as_str())))
     }
@@ -194,15 +216,280 @@ impl Filter for SortNaturalFilter {
     }
 }
 
+#[derive(Debug, FilterParameters)]
+struct ByPropertyArgs {
+    #[parameter(
+        description = "The property of the elements to compare by.",
+        arg_type = "str"
+    )]
+    property: Expression,
+}
+
+/// Select the element whose `property` orders as `wanted` relative to all others.
+///
+/// Elements missing the property (or with a nil value) are skipped.  Ties resolve to the first
+/// element.  An empty array, or one where no element has the property, results in nil.
+fn select_by_property(
+    input: &dyn ValueView,
+    property: &str,
+    wanted: cmp::Ordering,
+) -> Result<Value> {
+    let input: Vec<_> = as_sequence(input).collect();
+    if !input.iter().all(|v| v.is_object()) {
+        return Err(invalid_input("Array of objects expected"));
+    }
+
+    let mut selected: Option<(&dyn ValueView, &dyn ValueView)> = None;
+    for element in input {
+        let key = match element.as_object().and_then(|obj| obj.get(property)) {
+            Some(key) if !key.is_nil() => key,
+            _ => continue,
+        };
+        let replace = match selected {
+            Some((_, current)) => nil_safe_compare(key, current) == Some(wanted),
+            None => true,
+        };
+        if replace {
+            selected = Some((element, key));
+        }
+    }
+
+    Ok(selected.map_or(Value::Nil, |(element, _)| element.to_value()))
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "min_by",
+    description = "Returns the element of an array of objects with the smallest `property`. Returns nil for an empty array.",
+    parameters(ByPropertyArgs),
+    parsed(MinByFilter)
+)]
+pub struct MinBy;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "min_by"]
+struct MinByFilter {
+    #[parameters]
+    args: ByPropertyArgs,
+}
+
+impl Filter for MinByFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        select_by_property(input, &args.property, cmp::Ordering::Less)
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "max_by",
+    description = "Returns the element of an array of objects with the largest `property`. Returns nil for an empty array.",
+    parameters(ByPropertyArgs),
+    parsed(MaxByFilter)
+)]
+pub struct MaxBy;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "max_by"]
+struct MaxByFilter {
+    #[parameters]
+    args: ByPropertyArgs,
+}
+
+impl Filter for MaxByFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        select_by_property(input, &args.property, cmp::Ordering::Greater)
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "average",
+    description = "Returns the arithmetic mean of an array of numbers, or of `property` in an array of objects. Nil values are skipped and an empty array results in nil.",
+    parameters(PropertyArgs),
+    parsed(AverageFilter)
+)]
+pub struct Average;
+
+#[derive(Debug, Default, FromFilterParameters, Display_filter)]
+#[name = "average"]
+struct AverageFilter {
+    #[parameters]
+    args: PropertyArgs,
+}
+
+impl Filter for AverageFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input: Vec<_> = as_sequence(input).collect();
+        let values: Vec<&dyn ValueView> = if let Some(property) = &args.property {
+            if !input.iter().all(|v| v.is_object()) {
+                return Err(invalid_input("Array of objects expected"));
+            }
+            input
+                .iter()
+                .filter_map(|v| v.as_object().and_then(|obj| obj.get(property.as_str())))
+                .collect()
+        } else {
+            input
+        };
+
+        let mut sum = 0f64;
+        let mut count = 0usize;
+        for value in values.into_iter().filter(|v| !v.is_nil()) {
+            let value = value
+                .as_scalar()
+                .and_then(|s| s.to_float())
+                .ok_or_else(|| invalid_input("Array of numbers expected"))?;
+            sum += value;
+            count += 1;
+        }
+
+        if count == 0 {
+            Ok(Value::Nil)
+        } else {
+            Ok(Value::scalar(sum / count as f64))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum WhereOperator {
+    Equals,
+    NotEquals,
+    LessThan,
+    GreaterThan,
+    LessThanEquals,
+    GreaterThanEquals,
+    Contains,
+}
+
+impl WhereOperator {
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "==" => Some(WhereOperator::Equals),
+            "!=" | "<>" => Some(WhereOperator::NotEquals),
+            "<" => Some(WhereOperator::LessThan),
+            ">" => Some(WhereOperator::GreaterThan),
+            "<=" => Some(WhereOperator::LessThanEquals),
+            ">=" => Some(WhereOperator::GreaterThanEquals),
+            "contains" => Some(WhereOperator::Contains),
+            _ => None,
+        }
+    }
+
+    fn compare(self, value: &dyn ValueView, target: &dyn ValueView) -> bool {
+        let cmp_value = ValueViewCmp::new(value);
+        let cmp_target = ValueViewCmp::new(target);
+        match self {
+            WhereOperator::Equals => cmp_value == cmp_target,
+            WhereOperator::NotEquals => cmp_value != cmp_target,
+            WhereOperator::LessThan => cmp_value < cmp_target,
+            WhereOperator::GreaterThan => cmp_value > cmp_target,
+            WhereOperator::LessThanEquals => cmp_value <= cmp_target,
+            WhereOperator::GreaterThanEquals => cmp_value >= cmp_target,
+            WhereOperator::Contains => {
+                if let Some(array) = value.as_array() {
+                    array.values().any(|v| ValueViewCmp::new(v) == cmp_target)
+                } else if let Some(object) = value.as_object() {
+                    object.contains_key(target.to_kstr().as_str())
+                } else if let Some(scalar) = value.as_scalar() {
+                    scalar.to_kstr().contains(target.to_kstr().as_str())
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// How an element's property is tested by `where` and `reject`.
+enum PropertyCondition<'v> {
+    Truthy,
+    Compare(WhereOperator, ValueCow<'v>),
+}
+
+impl<'v> PropertyCondition<'v> {
+    fn new(
+        target_value: Option<ValueCow<'v>>,
+        comparison_value: Option<ValueCow<'v>>,
+    ) -> Result<Self> {
+        let condition = match (target_value, comparison_value) {
+            (None, _) => PropertyCondition::Truthy,
+            (Some(target_value), None) => {
+                PropertyCondition::Compare(WhereOperator::Equals, target_value)
+            }
+            (Some(operator), Some(comparison_value)) => {
+                let operator =
+                    WhereOperator::from_str(operator.to_kstr().as_str()).ok_or_else(|| {
+                        invalid_argument(
+                            "operator",
+                            "One of `==`, `!=`, `<>`, `<`, `>`, `<=`, `>=`, `contains` expected",
+                        )
+                    })?;
+                PropertyCondition::Compare(operator, comparison_value)
+            }
+        };
+        Ok(condition)
+    }
+
+    fn matches(&self, object: &dyn ObjectView, property: &str) -> bool {
+        match self {
+            PropertyCondition::Truthy => object
+                .get(property)
+                .map_or(false, |v| v.query_state(liquid_core::value::State::Truthy)),
+            PropertyCondition::Compare(operator, target_value) => {
+                object.get(property).map_or(false, |value| {
+                    operator.compare(value, target_value.as_view())
+                })
+            }
+        }
+    }
+}
+
+fn filter_by_property(
+    input: &dyn ValueView,
+    property: &str,
+    condition: &PropertyCondition<'_>,
+    keep_matches: bool,
+) -> Result<Value> {
+    if let Some(array) = input.as_array() {
+        if !array.values().all(|v| v.is_object()) {
+            return Ok(Value::Nil);
+        }
+    } else if !input.is_object() {
+        return Err(invalid_input(
+            "Array of objects or a single object expected",
+        ));
+    }
+
+    let array: Vec<_> = as_sequence(input)
+        .filter_map(|v| v.as_object())
+        .filter(|object| condition.matches(*object, property) == keep_matches)
+        .map(|object| object.to_value())
+        .collect();
+    Ok(Value::array(array))
+}
+
 #[derive(Debug, FilterParameters)]
 struct WhereArgs {
     #[parameter(description = "The property being matched", arg_type = "str")]
     property: Expression,
     #[parameter(
-        description = "The value the property is matched with",
+        description = "The value the property is matched with, or the comparison operator \
+                       (`==`, `!=`, `<>`, `<`, `>`, `<=`, `>=`, `contains`) when a third argument is given",
         arg_type = "any"
     )]
     target_value: Option<Expression>,
+    #[parameter(
+        description = "The value the property is compared with using the operator",
+        arg_type = "any"
+    )]
+    comparison_value: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -225,42 +512,33 @@ struct WhereFilter {
 impl Filter for WhereFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
-        let property: &str = &args.property;
-        let target_value: Option<ValueCow<'_>> = args.target_value;
+        let condition = PropertyCondition::new(args.target_value, args.comparison_value)?;
+        filter_by_property(input, &args.property, &condition, true)
+    }
+}
 
-        if let Some(array) = input.as_array() {
-            if !array.values().all(|v| v.is_object()) {
-                return Ok(Value::Nil);
-            }
-        } else if !input.is_object() {
-            return Err(invalid_input(
-                "Array of objects or a single object expected",
-            ));
-        }
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "reject",
+    description = "Filter the elements of an array to those without a certain property value. \
+                   By default the target is any truthy value.",
+    parameters(WhereArgs),
+    parsed(RejectFilter)
+)]
+pub struct Reject;
 
-        let input = as_sequence(input);
-        let array: Vec<_> = match target_value {
-            None => input
-                .filter_map(|v| v.as_object())
-                .filter(|object| {
-                    object
-                        .get(property)
-                        .map_or(false, |v| v.query_state(liquid_core::value::State::Truthy))
-                })
-                .map(|object| object.to_value())
-                .collect(),
-            Some(target_value) => input
-                .filter_map(|v| v.as_object())
-                .filter(|object| {
-                    object.get(property).map_or(false, |value| {
-                        let value = ValueViewCmp::new(value);
-                        target_value == value
-                    })
-                })
-                .map(|object| object.to_value())
-                .collect(),
-        };
-        Ok(Value::array(array))
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "reject"]
+struct RejectFilter {
+    #[parameters]
+    args: WhereArgs,
+}
+
+impl Filter for RejectFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        let condition = PropertyCondition::new(args.target_value, args.comparison_value)?;
+        filter_by_property(input, &args.property, &condition, false)
     }
 }
 
@@ -463,6 +741,135 @@ impl Filter for ConcatFilter {
     }
 }
 
+#[derive(Debug, FilterParameters)]
+struct FlattenArgs {
+    #[parameter(
+        description = "The maximum number of levels to flatten. Flattens fully when omitted.",
+        arg_type = "integer"
+    )]
+    depth: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "flatten",
+    description = "Flattens nested arrays into a single array. Non-array elements, including objects, are kept as-is.",
+    parameters(FlattenArgs),
+    parsed(FlattenFilter)
+)]
+pub struct Flatten;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "flatten"]
+struct FlattenFilter {
+    #[parameters]
+    args: FlattenArgs,
+}
+
+fn flatten_into(array: &dyn ArrayView, depth: Option<u32>, result: &mut Vec<Value>) {
+    for value in array.values() {
+        match value.as_array() {
+            Some(nested) if depth != Some(0) => {
+                flatten_into(nested, depth.map(|d| d - 1), result);
+            }
+            _ => result.push(value.to_value()),
+        }
+    }
+}
+
+impl Filter for FlattenFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+
+        let depth = args
+            .depth
+            .map(|d| {
+                u32::try_from(d)
+                    .map_err(|_| invalid_argument("depth", "Non-negative integer expected"))
+            })
+            .transpose()?;
+
+        let mut result = Vec::new();
+        flatten_into(input, depth, &mut result);
+        Ok(Value::array(result))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct IndexOfArgs {
+    #[parameter(description = "The value to search for.")]
+    value: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "index_of",
+    description = "Returns the index of the first element of an array equal to `value`, or nil if there is none.",
+    parameters(IndexOfArgs),
+    parsed(IndexOfFilter)
+)]
+pub struct IndexOf;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "index_of"]
+struct IndexOfFilter {
+    #[parameters]
+    args: IndexOfArgs,
+}
+
+impl Filter for IndexOfFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let array = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+
+        let target = ValueViewCmp::new(args.value.as_view());
+        let index = array.values().position(|v| ValueViewCmp::new(v) == target);
+        Ok(index.map_or(Value::Nil, |i| Value::scalar(i as i32)))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "last_index_of",
+    description = "Returns the index of the last element of an array equal to `value`, or nil if there is none.",
+    parameters(IndexOfArgs),
+    parsed(LastIndexOfFilter)
+)]
+pub struct LastIndexOf;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "last_index_of"]
+struct LastIndexOfFilter {
+    #[parameters]
+    args: IndexOfArgs,
+}
+
+impl Filter for LastIndexOfFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let array = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+
+        let target = ValueViewCmp::new(args.value.as_view());
+        let index = array
+            .values()
+            .enumerate()
+            .filter(|(_, v)| ValueViewCmp::new(*v) == target)
+            .map(|(i, _)| i)
+            .last();
+        Ok(index.map_or(Value::Nil, |i| Value::scalar(i as i32)))
+    }
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "first",
@@ -754,4 +1161,250 @@ mod tests {
             desired_result
         );
     }
+
+    #[test]
+    fn unit_where_operator() {
+        let input = liquid_core::value!([
+            {"name": "a", "price": 50},
+            {"name": "b", "price": 100},
+            {"name": "c", "price": 150},
+            {"name": "d"},
+        ]);
+        let desired_result = liquid_core::value!([{"name": "c", "price": 150}]);
+        assert_eq!(
+            liquid_core::call_filter!(Where, input, "price", ">", 100).unwrap(),
+            desired_result
+        );
+        let desired_result = liquid_core::value!([
+            {"name": "a", "price": 50},
+            {"name": "b", "price": 100},
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Where, input, "price", "<=", 100).unwrap(),
+            desired_result
+        );
+        let desired_result = liquid_core::value!([
+            {"name": "a", "price": 50},
+            {"name": "c", "price": 150},
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Where, input, "price", "!=", 100).unwrap(),
+            desired_result
+        );
+    }
+
+    #[test]
+    fn unit_where_contains() {
+        let input = liquid_core::value!([
+            {"title": "Liquid", "tags": ["rust", "template"]},
+            {"title": "Jekyll", "tags": ["ruby"]},
+        ]);
+        let desired_result =
+            liquid_core::value!([{"title": "Liquid", "tags": ["rust", "template"]}]);
+        assert_eq!(
+            liquid_core::call_filter!(Where, input, "tags", "contains", "rust").unwrap(),
+            desired_result
+        );
+        let desired_result = liquid_core::value!([{"title": "Jekyll", "tags": ["ruby"]}]);
+        assert_eq!(
+            liquid_core::call_filter!(Where, input, "title", "contains", "kyl").unwrap(),
+            desired_result
+        );
+    }
+
+    #[test]
+    fn unit_where_unknown_operator() {
+        let input = liquid_core::value!([{"price": 50}]);
+        liquid_core::call_filter!(Where, input, "price", "~=", 100).unwrap_err();
+    }
+
+    #[test]
+    fn unit_reject() {
+        let input = liquid_core::value!([
+            {"name": "a", "ok": true},
+            {"name": "b", "ok": false},
+            {"name": "c"},
+        ]);
+        let desired_result = liquid_core::value!([{"name": "b", "ok": false}, {"name": "c"}]);
+        assert_eq!(
+            liquid_core::call_filter!(Reject, input, "ok").unwrap(),
+            desired_result
+        );
+        let desired_result = liquid_core::value!([{"name": "a", "ok": true}, {"name": "c"}]);
+        assert_eq!(
+            liquid_core::call_filter!(Reject, input, "ok", false).unwrap(),
+            desired_result
+        );
+    }
+
+    #[test]
+    fn unit_reject_operator() {
+        let input = liquid_core::value!([{"price": 50}, {"price": 150}]);
+        let desired_result = liquid_core::value!([{"price": 50}]);
+        assert_eq!(
+            liquid_core::call_filter!(Reject, input, "price", ">=", 100).unwrap(),
+            desired_result
+        );
+    }
+
+    #[test]
+    fn unit_min_by() {
+        let input = liquid_core::value!([
+            {"name": "a", "price": 20},
+            {"name": "b", "price": 10},
+            {"name": "c"},
+            {"name": "d", "price": 10},
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(MinBy, input, "price").unwrap(),
+            liquid_core::value!({"name": "b", "price": 10})
+        );
+    }
+
+    #[test]
+    fn unit_max_by() {
+        let input = liquid_core::value!([
+            {"name": "a", "price": 20},
+            {"name": "b", "price": 30},
+            {"name": "c", "price": nil},
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(MaxBy, input, "price").unwrap(),
+            liquid_core::value!({"name": "b", "price": 30})
+        );
+    }
+
+    #[test]
+    fn unit_max_by_empty() {
+        assert_eq!(
+            liquid_core::call_filter!(MaxBy, liquid_core::value!([]), "price").unwrap(),
+            Value::Nil
+        );
+        assert_eq!(
+            liquid_core::call_filter!(MaxBy, liquid_core::value!([{"name": "a"}]), "price")
+                .unwrap(),
+            Value::Nil
+        );
+    }
+
+    #[test]
+    fn unit_max_by_not_objects() {
+        liquid_core::call_filter!(MaxBy, liquid_core::value!([1, 2]), "price").unwrap_err();
+    }
+
+    #[test]
+    fn unit_average() {
+        assert_eq!(
+            liquid_core::call_filter!(Average, liquid_core::value!([1, 2, nil, 4.5])).unwrap(),
+            Value::scalar(2.5f64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Average, liquid_core::value!([])).unwrap(),
+            Value::Nil
+        );
+    }
+
+    #[test]
+    fn unit_average_property() {
+        let input = liquid_core::value!([{"rating": 4}, {"rating": 5}, {"name": "x"}]);
+        assert_eq!(
+            liquid_core::call_filter!(Average, input, "rating").unwrap(),
+            Value::scalar(4.5f64)
+        );
+    }
+
+    #[test]
+    fn unit_average_not_numbers() {
+        liquid_core::call_filter!(Average, liquid_core::value!(["a", "b"])).unwrap_err();
+    }
+
+    #[test]
+    fn unit_flatten() {
+        let input = liquid_core::value!([1, [2, [3, [4]]], {"a": [5]}]);
+        assert_eq!(
+            liquid_core::call_filter!(Flatten, input).unwrap(),
+            liquid_core::value!([1, 2, 3, 4, {"a": [5]}])
+        );
+    }
+
+    #[test]
+    fn unit_flatten_depth() {
+        let input = liquid_core::value!([1, [2, [3, [4]]]]);
+        assert_eq!(
+            liquid_core::call_filter!(Flatten, input.clone(), 0).unwrap(),
+            input
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Flatten, input.clone(), 1).unwrap(),
+            liquid_core::value!([1, 2, [3, [4]]])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Flatten, input.clone()).unwrap(),
+            liquid_core::value!([1, 2, 3, 4])
+        );
+        liquid_core::call_filter!(Flatten, input, -1).unwrap_err();
+    }
+
+    #[test]
+    fn unit_flatten_not_array() {
+        liquid_core::call_filter!(Flatten, "abc").unwrap_err();
+    }
+
+    #[test]
+    fn unit_index_of() {
+        let input = liquid_core::value!(["a", "b", "a"]);
+        assert_eq!(
+            liquid_core::call_filter!(IndexOf, input.clone(), "a").unwrap(),
+            Value::scalar(0)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(LastIndexOf, input.clone(), "a").unwrap(),
+            Value::scalar(2)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(IndexOf, input, "z").unwrap(),
+            Value::Nil
+        );
+    }
+
+    #[test]
+    fn unit_index_of_numbers() {
+        let input = liquid_core::value!([1, 2.0, 3]);
+        assert_eq!(
+            liquid_core::call_filter!(IndexOf, input, 2).unwrap(),
+            Value::scalar(1)
+        );
+    }
+
+    #[test]
+    fn unit_index_of_not_array() {
+        liquid_core::call_filter!(IndexOf, "abc", "a").unwrap_err();
+    }
+
+    #[test]
+    fn unit_join_nil() {
+        let input = liquid_core::value!(["a", nil, 1]);
+        assert_eq!(
+            liquid_core::call_filter!(Join, input.clone()).unwrap(),
+            liquid_core::value!("a  1")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Join, input, ",").unwrap(),
+            liquid_core::value!("a,,1")
+        );
+    }
+
+    #[test]
+    fn unit_join_property() {
+        let input = liquid_core::value!([{"name": "a"}, {"id": 1}, {"name": "c"}]);
+        assert_eq!(
+            liquid_core::call_filter!(Join, input, ", ", "name").unwrap(),
+            liquid_core::value!("a, , c")
+        );
+    }
+
+    #[test]
+    fn unit_join_property_not_objects() {
+        liquid_core::call_filter!(Join, liquid_core::value!(["a"]), ", ", "name").unwrap_err();
+    }
 }
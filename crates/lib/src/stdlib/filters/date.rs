@@ -1,3 +1,8 @@
+use std::convert::TryFrom;
+
+use chrono::Weekday;
+use kstring::KString;
+use liquid_core::value::{parse_offset, DateTime, FixedOffset};
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
@@ -6,10 +11,23 @@ use liquid_core::{
 };
 use liquid_core::{Value, ValueView};
 
+use crate::locale::{date_names, DateNames};
+use crate::{invalid_argument, invalid_input, to_date_time, Locale};
+
 #[derive(Debug, FilterParameters)]
 struct DateArgs {
     #[parameter(description = "The format to return the date in.", arg_type = "str")]
     format: Expression,
+    #[parameter(
+        description = "The language for month and weekday names, and the first day of the week for `%U`, like `de` or `pt-BR`. Defaults to the render's `Locale`.",
+        arg_type = "str"
+    )]
+    locale: Option<Expression>,
+    #[parameter(
+        description = "The time zone to show the date in, like `+05:30` or, with the `time-zones` feature, `Europe/Paris`. Defaults to the date's own offset.",
+        arg_type = "str"
+    )]
+    time_zone: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -32,16 +50,355 @@ impl Filter for DateFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let date = input.as_scalar().and_then(|s| s.to_date_time());
+        let locale = match args.locale {
+            Some(ref locale) => {
+                date_names(locale.as_str())
+                    .ok_or_else(|| invalid_argument("locale", "Unknown locale"))?;
+                Some(Locale::new(KString::from_ref(locale.as_str())))
+            }
+            None => runtime.get_register::<Locale>().cloned(),
+        };
+        let localized = locale.and_then(|locale| {
+            date_names(locale.tag()).map(|names| (names, locale.first_day_of_week()))
+        });
+
+        let date = to_date_time(input, runtime);
+        let date = match (date, args.time_zone) {
+            (Some(date), Some(ref time_zone)) => Some(
+                time_zone_offset(time_zone.as_str(), &date)
+                    .map(|offset| date.with_timezone(&offset))
+                    .ok_or_else(|| invalid_argument("time_zone", "Unknown time zone"))?,
+            ),
+            (date, _) => date,
+        };
         match date {
             Some(date) if !args.format.is_empty() => {
-                Ok(Value::scalar(date.format(args.format.as_str()).to_string()))
+                let formatted = match localized {
+                    Some((names, first_day)) => date
+                        .format(&localize_format(
+                            args.format.as_str(),
+                            &date,
+                            names,
+                            first_day,
+                        ))
+                        .to_string(),
+                    None => date.format(args.format.as_str()).to_string(),
+                };
+                Ok(Value::scalar(formatted))
             }
             _ => Ok(input.to_value()),
         }
     }
 }
 
+/// The offset of `time_zone` at `date`, either a fixed offset or, with `time-zones`, an IANA
+/// name.
+fn time_zone_offset(time_zone: &str, date: &DateTime) -> Option<FixedOffset> {
+    parse_offset(time_zone).or_else(|| named_time_zone_offset(time_zone, date))
+}
+
+#[cfg(feature = "time-zones")]
+fn named_time_zone_offset(time_zone: &str, date: &DateTime) -> Option<FixedOffset> {
+    use chrono::{Offset, TimeZone};
+
+    let time_zone: chrono_tz::Tz = time_zone.parse().ok()?;
+    let offset = time_zone
+        .offset_from_utc_datetime(&date.to_chrono().naive_utc())
+        .fix();
+    Some(offset)
+}
+
+#[cfg(not(feature = "time-zones"))]
+fn named_time_zone_offset(_time_zone: &str, _date: &DateTime) -> Option<FixedOffset> {
+    None
+}
+
+/// Replace the month and weekday name specifiers in `format` with `date`'s names, and the week
+/// of the year, `%U`, with the week counted from the locale's `first_day` of the week.
+fn localize_format(format: &str, date: &DateTime, names: &DateNames, first_day: Weekday) -> String {
+    use chrono::Datelike;
+
+    let month = date.month0() as usize;
+    let weekday = date.weekday().num_days_from_sunday() as usize;
+    let mut localized = String::with_capacity(format.len());
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            localized.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('B') => localized.push_str(names.months[month]),
+            Some('b') | Some('h') => localized.push_str(names.months_abbr[month]),
+            Some('A') => localized.push_str(names.weekdays[weekday]),
+            Some('a') => localized.push_str(names.weekdays_abbr[weekday]),
+            Some('U') => {
+                // Days since the start of the week, like `%U` does for Sunday.
+                let days = (date.weekday().num_days_from_monday() + 7
+                    - first_day.num_days_from_monday())
+                    % 7;
+                let week = (date.ordinal0() + 7 - days) / 7;
+                localized.push_str(&format!("{:02}", week));
+            }
+            Some(other) => {
+                localized.push('%');
+                localized.push(other);
+            }
+            None => localized.push('%'),
+        }
+    }
+    localized
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum DateUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl DateUnit {
+    fn from_str(unit: &str) -> Result<Self> {
+        let unit = match unit {
+            "second" | "seconds" => DateUnit::Seconds,
+            "minute" | "minutes" => DateUnit::Minutes,
+            "hour" | "hours" => DateUnit::Hours,
+            "day" | "days" => DateUnit::Days,
+            "week" | "weeks" => DateUnit::Weeks,
+            "month" | "months" => DateUnit::Months,
+            "year" | "years" => DateUnit::Years,
+            _ => {
+                return Err(invalid_argument(
+                    "unit",
+                    "Expected one of seconds, minutes, hours, days, weeks, months or years",
+                ))
+            }
+        };
+        Ok(unit)
+    }
+
+    /// Length in seconds, for units of a fixed length.
+    fn seconds(self) -> Option<i64> {
+        match self {
+            DateUnit::Seconds => Some(1),
+            DateUnit::Minutes => Some(60),
+            DateUnit::Hours => Some(60 * 60),
+            DateUnit::Days => Some(24 * 60 * 60),
+            DateUnit::Weeks => Some(7 * 24 * 60 * 60),
+            DateUnit::Months | DateUnit::Years => None,
+        }
+    }
+
+    /// Length in months, for calendar units.
+    fn months(self) -> Option<i32> {
+        match self {
+            DateUnit::Months => Some(1),
+            DateUnit::Years => Some(12),
+            _ => None,
+        }
+    }
+}
+
+fn add_to_date(date: DateTime, amount: i32, unit: DateUnit) -> Option<DateTime> {
+    match (unit.seconds(), unit.months()) {
+        (Some(seconds), _) => {
+            date.checked_add_signed(chrono::Duration::seconds(i64::from(amount) * seconds))
+        }
+        (None, Some(months)) => date.checked_add_months(amount.checked_mul(months)?),
+        (None, None) => unreachable!("every unit has a length"),
+    }
+}
+
+/// Whole `unit`s from `other` to `date`, truncated towards zero.
+fn diff_dates(date: DateTime, other: DateTime, unit: DateUnit) -> Option<i64> {
+    match (unit.seconds(), unit.months()) {
+        (Some(seconds), _) => Some(date.signed_duration_since(other).num_seconds() / seconds),
+        (None, Some(months)) => {
+            use chrono::Datelike;
+            let mut diff =
+                (date.year() - other.year()) * 12 + date.month() as i32 - other.month() as i32;
+            // Calendar months are only complete once the day and time have also been reached.
+            let reached = other.checked_add_months(diff)?;
+            if 0 < diff && date < reached {
+                diff -= 1;
+            } else if diff < 0 && reached < date {
+                diff += 1;
+            }
+            Some(i64::from(diff / months))
+        }
+        (None, None) => unreachable!("every unit has a length"),
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct DateAddArgs {
+    #[parameter(
+        description = "The number of units to add. May be negative.",
+        arg_type = "integer"
+    )]
+    amount: Expression,
+    #[parameter(
+        description = "One of seconds, minutes, hours, days, weeks, months or years.",
+        arg_type = "str"
+    )]
+    unit: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "date_add",
+    description = "Adds an amount of time to a date.",
+    parameters(DateAddArgs),
+    parsed(DateAddFilter)
+)]
+pub struct DateAdd;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "date_add"]
+struct DateAddFilter {
+    #[parameters]
+    args: DateAddArgs,
+}
+
+impl Filter for DateAddFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let date = to_date_time(input, runtime).ok_or_else(|| invalid_input("Date expected"))?;
+        let unit = DateUnit::from_str(&args.unit)?;
+
+        let date = add_to_date(date, args.amount, unit)
+            .ok_or_else(|| invalid_argument("amount", "Date out of range"))?;
+        Ok(Value::scalar(date))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct DateDiffArgs {
+    #[parameter(description = "The date to measure from.", arg_type = "date_time")]
+    other: Expression,
+    #[parameter(
+        description = "One of seconds, minutes, hours, days, weeks, months or years.",
+        arg_type = "str"
+    )]
+    unit: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "date_diff",
+    description = "Counts the whole units of time from another date to the input, negative when the other date is later.",
+    parameters(DateDiffArgs),
+    parsed(DateDiffFilter)
+)]
+pub struct DateDiff;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "date_diff"]
+struct DateDiffFilter {
+    #[parameters]
+    args: DateDiffArgs,
+}
+
+impl Filter for DateDiffFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let date = to_date_time(input, runtime).ok_or_else(|| invalid_input("Date expected"))?;
+        let unit = DateUnit::from_str(&args.unit)?;
+
+        let diff = diff_dates(date, args.other, unit)
+            .and_then(|diff| i32::try_from(diff).ok())
+            .ok_or_else(|| invalid_argument("other", "Difference out of range"))?;
+        Ok(Value::scalar(diff))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "humanize_duration",
+    description = "Formats a duration, or a number of seconds, like `2h 15m`.",
+    parsed(HumanizeDurationFilter)
+)]
+pub struct HumanizeDuration;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "humanize_duration"]
+struct HumanizeDurationFilter;
+
+impl Filter for HumanizeDurationFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let duration = input
+            .as_scalar()
+            .and_then(|s| s.to_duration())
+            .ok_or_else(|| invalid_input("Duration expected"))?;
+
+        let seconds = duration.num_seconds();
+        let mut remaining = i128::from(seconds).abs();
+        let mut parts = Vec::new();
+        for &(unit, length) in [("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60), ("s", 1)].iter() {
+            let count = remaining / length;
+            remaining %= length;
+            if 0 < count {
+                parts.push(format!("{}{}", count, unit));
+            }
+        }
+
+        let humanized = if parts.is_empty() {
+            "0s".to_owned()
+        } else if seconds < 0 {
+            format!("-{}", parts.join(" "))
+        } else {
+            parts.join(" ")
+        };
+        Ok(Value::scalar(humanized))
+    }
+}
+
+#[cfg(feature = "time-zones")]
+#[derive(Debug, FilterParameters)]
+struct InTimeZoneArgs {
+    #[parameter(
+        description = "The IANA time zone to convert the date to, like `Europe/Paris`.",
+        arg_type = "str"
+    )]
+    time_zone: Expression,
+}
+
+#[cfg(feature = "time-zones")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "in_time_zone",
+    description = "Converts a date to the local time of a time zone, including daylight saving time.",
+    parameters(InTimeZoneArgs),
+    parsed(InTimeZoneFilter)
+)]
+pub struct InTimeZone;
+
+#[cfg(feature = "time-zones")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "in_time_zone"]
+struct InTimeZoneFilter {
+    #[parameters]
+    args: InTimeZoneArgs,
+}
+
+#[cfg(feature = "time-zones")]
+impl Filter for InTimeZoneFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let date = to_date_time(input, runtime).ok_or_else(|| invalid_input("Date expected"))?;
+        let offset = time_zone_offset(args.time_zone.as_str(), &date)
+            .ok_or_else(|| invalid_argument("time_zone", "Unknown time zone"))?;
+        Ok(Value::scalar(date.with_timezone(&offset)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,6 +419,131 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_date_locale() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                Date,
+                "2016-06-13 02:30:00 +0300",
+                "%A %d %B %Y, %a %b",
+                "de"
+            )
+            .unwrap(),
+            liquid_core::value!("Montag 13 Juni 2016, Mo Jun")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-08-13 02:30:00 +0300", "%d %B %%B", "pt-BR")
+                .unwrap(),
+            liquid_core::value!("13 agosto %B")
+        );
+    }
+
+    #[test]
+    fn unit_date_locale_week() {
+        // A Sunday, the first day of the week in the US but not in Germany.
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-01-10 02:30:00 +0300", "%U %W", "en-US").unwrap(),
+            liquid_core::value!("02 01")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-01-10 02:30:00 +0300", "%U %W", "de").unwrap(),
+            liquid_core::value!("01 01")
+        );
+    }
+
+    #[test]
+    fn unit_date_unknown_locale() {
+        liquid_core::call_filter!(Date, "2016-06-13 02:30:00 +0300", "%B", "xx").unwrap_err();
+    }
+
+    #[test]
+    fn unit_date_iso_8601() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-06-13T02:30:00+03:00", "%Y-%m-%d %H:%M").unwrap(),
+            liquid_core::value!("2016-06-13 02:30")
+        );
+    }
+
+    fn call_date_in(input: &str, format: &str, time_zone: &str) -> Result<Value> {
+        let args = liquid_core::compiler::FilterArguments {
+            positional: Box::new(
+                vec![Expression::Literal(Value::scalar(format.to_owned()))].into_iter(),
+            ),
+            keyword: Box::new(
+                vec![(
+                    "time_zone",
+                    Expression::Literal(Value::scalar(time_zone.to_owned())),
+                )]
+                .into_iter(),
+            ),
+        };
+        let runtime = Runtime::default();
+        Date.parse(args)?
+            .evaluate(&Value::scalar(input.to_owned()), &runtime)
+    }
+
+    #[test]
+    fn unit_date_time_zone() {
+        assert_eq!(
+            call_date_in("2016-06-13T02:30:00+03:00", "%Y-%m-%d %H:%M %z", "-05:00").unwrap(),
+            liquid_core::value!("2016-06-12 18:30 -0500")
+        );
+        call_date_in("2016-06-13T02:30:00+03:00", "%Y-%m-%d", "Mars/Olympus").unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "time-zones")]
+    fn unit_date_named_time_zone() {
+        assert_eq!(
+            call_date_in("2016-06-13 02:30:00 +0000", "%H:%M %z", "Europe/Paris").unwrap(),
+            liquid_core::value!("04:30 +0200")
+        );
+    }
+
+    #[test]
+    fn unit_date_rfc_2822() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, "Mon, 13 Jun 2016 02:30:00 +0300", "%Y-%m-%d").unwrap(),
+            liquid_core::value!("2016-06-13")
+        );
+    }
+
+    #[test]
+    fn unit_date_epoch() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, 1_465_785_000, "%Y-%m-%d %H:%M").unwrap(),
+            liquid_core::value!("2016-06-13 02:30")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, 1_465_785_000_000i64, "%Y-%m-%d %H:%M").unwrap(),
+            liquid_core::value!("2016-06-13 02:30")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016", "%Y-%m-%d").unwrap(),
+            liquid_core::value!("2016")
+        );
+    }
+
+    #[test]
+    fn unit_date_formats_register() {
+        let args = liquid_core::compiler::FilterArguments {
+            positional: Box::new(
+                vec![Expression::Literal(liquid_core::value!("%Y-%m-%d"))].into_iter(),
+            ),
+            keyword: Box::new(Vec::new().into_iter()),
+        };
+        let filter = Date.parse(args).unwrap();
+        let runtime = liquid_core::interpreter::RuntimeBuilder::new()
+            .set_register(liquid_core::value::DateFormats::new().format("%d.%m.%Y"))
+            .build();
+        assert_eq!(
+            filter
+                .evaluate(&liquid_core::value!("13.06.2016"), &runtime)
+                .unwrap(),
+            liquid_core::value!("2016-06-13")
+        );
+    }
+
     #[test]
     fn unit_date_bad_input_type() {
         assert_eq!(
@@ -103,4 +585,145 @@ mod tests {
     fn unit_date_extra_param() {
         liquid_core::call_filter!(Date, "13 Jun 2016 02:30:00 +0300", 0f64, 1f64).unwrap_err();
     }
+
+    #[test]
+    fn unit_date_add() {
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-06-13 02:30:00 +0300", 3, "days").unwrap(),
+            Value::scalar(DateTime::from_str("2016-06-16 02:30:00 +0300").unwrap())
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-06-13 02:30:00 +0300", -90, "minute").unwrap(),
+            Value::scalar(DateTime::from_str("2016-06-13 01:00:00 +0300").unwrap())
+        );
+    }
+
+    #[test]
+    fn unit_date_add_months() {
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-01-31 02:30:00 +0300", 1, "months").unwrap(),
+            Value::scalar(DateTime::from_str("2016-02-29 02:30:00 +0300").unwrap())
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DateAdd, "2016-02-29 02:30:00 +0300", 1, "year").unwrap(),
+            Value::scalar(DateTime::from_str("2017-02-28 02:30:00 +0300").unwrap())
+        );
+    }
+
+    #[test]
+    fn unit_date_add_bad_unit() {
+        liquid_core::call_filter!(DateAdd, "2016-06-13 02:30:00 +0300", 3, "fortnights")
+            .unwrap_err();
+    }
+
+    #[test]
+    fn unit_date_add_bad_input() {
+        liquid_core::call_filter!(DateAdd, "blah blah blah", 3, "days").unwrap_err();
+    }
+
+    #[test]
+    fn unit_date_diff() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                DateDiff,
+                "2016-06-13 02:30:00 +0300",
+                "2016-06-12 03:30:00 +0300",
+                "hours"
+            )
+            .unwrap(),
+            liquid_core::value!(23)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(
+                DateDiff,
+                "2016-06-12 03:30:00 +0300",
+                "2016-06-13 02:30:00 +0300",
+                "days"
+            )
+            .unwrap(),
+            liquid_core::value!(0)
+        );
+    }
+
+    #[test]
+    fn unit_date_diff_months() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                DateDiff,
+                "2016-06-13 02:30:00 +0300",
+                "2016-01-14 02:30:00 +0300",
+                "months"
+            )
+            .unwrap(),
+            liquid_core::value!(4)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(
+                DateDiff,
+                "2016-01-14 02:30:00 +0300",
+                "2018-06-13 02:30:00 +0300",
+                "years"
+            )
+            .unwrap(),
+            liquid_core::value!(-2)
+        );
+    }
+
+    #[test]
+    fn unit_humanize_duration() {
+        assert_eq!(
+            liquid_core::call_filter!(HumanizeDuration, "PT2H15M").unwrap(),
+            liquid_core::value!("2h 15m")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(HumanizeDuration, 93_784).unwrap(),
+            liquid_core::value!("1d 2h 3m 4s")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(HumanizeDuration, "-PT90S").unwrap(),
+            liquid_core::value!("-1m 30s")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(HumanizeDuration, 0).unwrap(),
+            liquid_core::value!("0s")
+        );
+    }
+
+    #[test]
+    fn unit_humanize_duration_native() {
+        let duration = liquid_core::value::Duration::from_seconds(150).unwrap();
+        assert_eq!(
+            liquid_core::call_filter!(HumanizeDuration, duration).unwrap(),
+            liquid_core::value!("2m 30s")
+        );
+    }
+
+    #[test]
+    fn unit_humanize_duration_bad_input() {
+        liquid_core::call_filter!(HumanizeDuration, "soon").unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "time-zones")]
+    fn unit_in_time_zone() {
+        assert_eq!(
+            liquid_core::call_filter!(InTimeZone, "2016-06-13 02:30:00 +0000", "Europe/Paris")
+                .unwrap()
+                .to_kstr(),
+            "2016-06-13 04:30:00 +0200"
+        );
+        assert_eq!(
+            liquid_core::call_filter!(InTimeZone, "2016-12-13 02:30:00 +0000", "Europe/Paris")
+                .unwrap()
+                .to_kstr(),
+            "2016-12-13 03:30:00 +0100"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "time-zones")]
+    fn unit_in_time_zone_unknown() {
+        liquid_core::call_filter!(InTimeZone, "2016-06-13 02:30:00 +0000", "Mars/Olympus")
+            .unwrap_err();
+    }
 }
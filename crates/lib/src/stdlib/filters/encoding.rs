@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// Bytes of the input, either native bytes or the input as a string.
+fn input_bytes<R>(input: &dyn ValueView, f: impl FnOnce(&[u8]) -> R) -> R {
+    if let Some(scalar) = input.as_scalar() {
+        if let Some(bytes) = scalar.as_bytes() {
+            return f(bytes);
+        }
+    }
+    f(input.to_kstr().as_bytes())
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "base64_encode",
+    description = "Encodes bytes or a string in Base64.",
+    parsed(Base64EncodeFilter)
+)]
+pub struct Base64Encode;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "base64_encode"]
+struct Base64EncodeFilter;
+
+impl Filter for Base64EncodeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        Ok(Value::scalar(input_bytes(input, base64::encode)))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "hex",
+    description = "Encodes bytes or a string as lowercase hexadecimal.",
+    parsed(HexFilter)
+)]
+pub struct Hex;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "hex"]
+struct HexFilter;
+
+impl Filter for HexFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        let result = input_bytes(input, |bytes| {
+            let mut result = String::with_capacity(bytes.len() * 2);
+            for byte in bytes {
+                write!(result, "{:02x}", byte).expect("Writing to a String can't fail");
+            }
+            result
+        });
+        Ok(Value::scalar(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_base64_encode() {
+        assert_eq!(
+            liquid_core::call_filter!(Base64Encode, "one two three").unwrap(),
+            liquid_core::value!("b25lIHR3byB0aHJlZQ==")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64Encode, Value::scalar(vec![0xffu8, 0, 0x10, 0x20]))
+                .unwrap(),
+            liquid_core::value!("/wAQIA==")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64Encode, "abc").unwrap(),
+            liquid_core::value!("YWJj")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64Encode, "").unwrap(),
+            liquid_core::value!("")
+        );
+    }
+
+    #[test]
+    fn unit_hex() {
+        assert_eq!(
+            liquid_core::call_filter!(Hex, Value::scalar(vec![0xdeu8, 0xad, 0x0b])).unwrap(),
+            liquid_core::value!("dead0b")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Hex, "Hi").unwrap(),
+            liquid_core::value!("4869")
+        );
+    }
+}
@@ -78,6 +78,10 @@ impl Filter for EscapeFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
         escape(input, false)
     }
+
+    fn output_encoding(&self) -> Option<&'static str> {
+        Some("html")
+    }
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -96,6 +100,10 @@ impl Filter for EscapeOnceFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
         escape(input, true)
     }
+
+    fn output_encoding(&self) -> Option<&'static str> {
+        Some("html")
+    }
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
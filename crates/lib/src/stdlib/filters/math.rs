@@ -1,3 +1,4 @@
+use liquid_core::value::ScalarCow;
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
@@ -6,6 +7,10 @@ use liquid_core::{
 };
 use liquid_core::{Value, ValueView};
 
+#[cfg(feature = "decimal")]
+use liquid_core::value::Decimal;
+
+use crate::locale::filter_locale;
 use crate::{invalid_argument, invalid_input};
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -26,8 +31,9 @@ impl Filter for AbsFilter {
             .as_scalar()
             .ok_or_else(|| invalid_input("Number expected"))?;
         input
-            .to_integer()
-            .map(|i| Value::scalar(i.abs()))
+            .to_big_integer()
+            .and_then(i128::checked_abs)
+            .map(Value::scalar)
             .or_else(|| input.to_float().map(|i| Value::scalar(i.abs())))
             .ok_or_else(|| invalid_input("Number expected"))
     }
@@ -69,8 +75,8 @@ impl Filter for AtLeastFilter {
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
         let result = input
-            .to_integer()
-            .and_then(|i| min.to_integer().map(|min| Value::scalar(i.max(min))))
+            .to_big_integer()
+            .and_then(|i| min.to_big_integer().map(|min| Value::scalar(i.max(min))))
             .or_else(|| {
                 input
                     .to_float()
@@ -118,8 +124,8 @@ impl Filter for AtMostFilter {
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
         let result = input
-            .to_integer()
-            .and_then(|i| max.to_integer().map(|max| Value::scalar(i.min(max))))
+            .to_big_integer()
+            .and_then(|i| max.to_big_integer().map(|max| Value::scalar(i.min(max))))
             .or_else(|| {
                 input
                     .to_float()
@@ -166,14 +172,20 @@ impl Filter for PlusFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
+        #[cfg(feature = "decimal")]
+        {
+            if let Some(result) = decimal_math(&input, &operand, Decimal::checked_add) {
+                return result;
+            }
+        }
+
+        if let Some(result) = integer_math(&input, &operand, i128::checked_add) {
+            return result;
+        }
+
         let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i + o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i + o)))
-            })
+            .to_float()
+            .and_then(|i| operand.to_float().map(|o| Value::scalar(i + o)))
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
         Ok(result)
@@ -215,14 +227,20 @@ impl Filter for MinusFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
+        #[cfg(feature = "decimal")]
+        {
+            if let Some(result) = decimal_math(&input, &operand, Decimal::checked_sub) {
+                return result;
+            }
+        }
+
+        if let Some(result) = integer_math(&input, &operand, i128::checked_sub) {
+            return result;
+        }
+
         let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i - o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i - o)))
-            })
+            .to_float()
+            .and_then(|i| operand.to_float().map(|o| Value::scalar(i - o)))
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
         Ok(result)
@@ -264,14 +282,20 @@ impl Filter for TimesFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
+        #[cfg(feature = "decimal")]
+        {
+            if let Some(result) = decimal_math(&input, &operand, Decimal::checked_mul) {
+                return result;
+            }
+        }
+
+        if let Some(result) = integer_math(&input, &operand, i128::checked_mul) {
+            return result;
+        }
+
         let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i * o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i * o)))
-            })
+            .to_float()
+            .and_then(|i| operand.to_float().map(|o| Value::scalar(i * o)))
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
         Ok(result)
@@ -313,14 +337,20 @@ impl Filter for DividedByFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
+        #[cfg(feature = "decimal")]
+        {
+            if let Some(result) = decimal_math(&input, &operand, Decimal::checked_div) {
+                return result;
+            }
+        }
+
+        if let Some(result) = integer_math(&input, &operand, i128::checked_div) {
+            return result;
+        }
+
         let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i / o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i / o)))
-            })
+            .to_float()
+            .and_then(|i| operand.to_float().map(|o| Value::scalar(i / o)))
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
         Ok(result)
@@ -362,20 +392,72 @@ impl Filter for ModuloFilter {
             .as_scalar()
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
+        #[cfg(feature = "decimal")]
+        {
+            if let Some(result) = decimal_math(&input, &operand, Decimal::checked_rem) {
+                return result;
+            }
+        }
+
+        if let Some(result) = integer_math(&input, &operand, i128::checked_rem) {
+            return result;
+        }
+
         let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i % o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i % o)))
-            })
+            .to_float()
+            .and_then(|i| operand.to_float().map(|o| Value::scalar(i % o)))
             .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
 
         Ok(result)
     }
 }
 
+/// Apply `op` when both sides are whole numbers, or `None` to fall back to floats.
+///
+/// Results beyond 128 bits fall back to floats too, like Ruby's `Integer` becoming a `Bignum`,
+/// only approximate.
+fn integer_math(
+    input: &ScalarCow<'_>,
+    operand: &ScalarCow<'_>,
+    op: fn(i128, i128) -> Option<i128>,
+) -> Option<Result<Value>> {
+    let input = input.to_big_integer()?;
+    let operand = operand.to_big_integer()?;
+    match op(input, operand) {
+        Some(result) => Some(Ok(Value::scalar(result))),
+        None if operand == 0 => Some(Err(invalid_argument("operand", "Cannot divide by zero"))),
+        None => None,
+    }
+}
+
+/// Apply `op` exactly when either side is a decimal, so amounts like prices keep their cents.
+#[cfg(feature = "decimal")]
+fn decimal_math(
+    input: &ScalarCow<'_>,
+    operand: &ScalarCow<'_>,
+    op: fn(Decimal, Decimal) -> Option<Decimal>,
+) -> Option<Result<Value>> {
+    if !input.is_decimal() && !operand.is_decimal() {
+        return None;
+    }
+    let input = match input.to_decimal() {
+        Some(input) => input,
+        None => return Some(Err(invalid_input("Number expected"))),
+    };
+    let operand = match operand.to_decimal() {
+        Some(operand) => operand,
+        None => return Some(Err(invalid_argument("operand", "Number expected"))),
+    };
+    let result = op(input, operand).map(Value::scalar).ok_or_else(|| {
+        if operand.is_zero() {
+            invalid_argument("operand", "Cannot divide by zero")
+        } else {
+            invalid_argument("operand", "Result is out of range")
+        }
+    });
+    Some(result)
+}
+
 #[derive(Debug, FilterParameters)]
 struct RoundArgs {
     #[parameter(
@@ -409,12 +491,17 @@ impl Filter for RoundFilter {
 
         let input = input
             .as_scalar()
-            .and_then(|s| s.to_float())
+            .ok_or_else(|| invalid_input("Number expected"))?;
+        if let Some(i) = input.to_big_integer() {
+            return Ok(Value::scalar(i));
+        }
+        let input = input
+            .to_float()
             .ok_or_else(|| invalid_input("Number expected"))?;
 
         match n.cmp(&0) {
-            std::cmp::Ordering::Equal => Ok(Value::scalar(input.round() as i32)),
-            std::cmp::Ordering::Less => Ok(Value::scalar(input.round() as i32)),
+            std::cmp::Ordering::Equal => Ok(Value::scalar(input.round() as i128)),
+            std::cmp::Ordering::Less => Ok(Value::scalar(input.round() as i128)),
             _ => {
                 let multiplier = 10.0_f64.powi(n);
                 Ok(Value::scalar((input * multiplier).round() / multiplier))
@@ -437,11 +524,16 @@ struct CeilFilter;
 
 impl Filter for CeilFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
-        let n = input
+        let input = input
             .as_scalar()
-            .and_then(|s| s.to_float())
             .ok_or_else(|| invalid_input("Number expected"))?;
-        Ok(Value::scalar(n.ceil() as i32))
+        if let Some(i) = input.to_big_integer() {
+            return Ok(Value::scalar(i));
+        }
+        let n = input
+            .to_float()
+            .ok_or_else(|| invalid_input("Number expected"))?;
+        Ok(Value::scalar(n.ceil() as i128))
     }
 }
 
@@ -459,11 +551,76 @@ struct FloorFilter;
 
 impl Filter for FloorFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| invalid_input("Number expected"))?;
+        if let Some(i) = input.to_big_integer() {
+            return Ok(Value::scalar(i));
+        }
         let n = input
+            .to_float()
+            .ok_or_else(|| invalid_input("Number expected"))?;
+        Ok(Value::scalar(n.floor() as i128))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct NumberFormatArgs {
+    #[parameter(
+        description = "The number of decimal places. At most 20, defaulting to 0 for whole numbers and 2 otherwise.",
+        arg_type = "integer"
+    )]
+    precision: Option<Expression>,
+    #[parameter(
+        description = "The locale for separators, like `de` or `pt-BR`. Defaults to the render's `Locale`.",
+        arg_type = "str"
+    )]
+    locale: Option<Expression>,
+}
+
+/// Most decimal places `number_format` formats, beyond the precision of a float.
+const MAX_PRECISION: i32 = 20;
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "number_format",
+    description = "Formats a number with the locale's decimal and thousands separators.",
+    parameters(NumberFormatArgs),
+    parsed(NumberFormatFilter)
+)]
+pub struct NumberFormat;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "number_format"]
+struct NumberFormatFilter {
+    #[parameters]
+    args: NumberFormatArgs,
+}
+
+impl Filter for NumberFormatFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input
             .as_scalar()
-            .and_then(|s| s.to_float())
             .ok_or_else(|| invalid_input("Number expected"))?;
-        Ok(Value::scalar(n.floor() as i32))
+        let n = input
+            .to_float()
+            .ok_or_else(|| invalid_input("Number expected"))?;
+        let precision = match args.precision {
+            Some(precision) if precision < 0 => {
+                return Err(invalid_argument("precision", "Positive number expected"));
+            }
+            Some(precision) if MAX_PRECISION < precision => {
+                return Err(invalid_argument("precision", "At most 20 expected"));
+            }
+            Some(precision) => precision as usize,
+            None if input.to_integer().is_some() => 0,
+            None => 2,
+        };
+
+        let locale = filter_locale(args.locale.as_ref().map(|l| l.as_str()), runtime);
+        Ok(Value::scalar(locale.format_number(n, precision)))
     }
 }
 
@@ -625,6 +782,28 @@ mod tests {
         liquid_core::call_filter!(DividedBy, 2.5).unwrap_err();
     }
 
+    #[test]
+    fn unit_big_integers() {
+        assert_eq!(
+            liquid_core::call_filter!(Plus, 2_147_483_647, 1).unwrap(),
+            Value::scalar(2_147_483_648i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Times, 9_007_199_254_740_993i64, 10).unwrap(),
+            Value::scalar(90_071_992_547_409_930i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Minus, "18446744073709551616", 1).unwrap(),
+            Value::scalar(18_446_744_073_709_551_615u64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Ceil, 9_007_199_254_740_993i64).unwrap(),
+            Value::scalar(9_007_199_254_740_993i64)
+        );
+        liquid_core::call_filter!(DividedBy, 5, 0).unwrap_err();
+        liquid_core::call_filter!(Modulo, 5, 0).unwrap_err();
+    }
+
     #[test]
     fn unit_ceil() {
         assert_eq!(
@@ -683,4 +862,75 @@ mod tests {
             Value::scalar(3.142f64)
         );
     }
+
+    #[test]
+    fn unit_number_format() {
+        assert_eq!(
+            liquid_core::call_filter!(NumberFormat, 1_234_567).unwrap(),
+            liquid_core::value!("1,234,567")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(NumberFormat, 1234.5f64).unwrap(),
+            liquid_core::value!("1,234.50")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(NumberFormat, "1234.5678", 3i32).unwrap(),
+            liquid_core::value!("1,234.568")
+        );
+    }
+
+    #[test]
+    fn unit_number_format_locale() {
+        assert_eq!(
+            liquid_core::call_filter!(NumberFormat, 1234.5f64, 2i32, "de").unwrap(),
+            liquid_core::value!("1.234,50")
+        );
+    }
+
+    #[test]
+    fn unit_number_format_default_locale() {
+        let args = liquid_core::compiler::FilterArguments {
+            positional: Box::new(Vec::new().into_iter()),
+            keyword: Box::new(Vec::new().into_iter()),
+        };
+        let filter = NumberFormat.parse(args).unwrap();
+        let runtime = liquid_core::interpreter::RuntimeBuilder::new()
+            .set_register(crate::Locale::new("es"))
+            .build();
+        assert_eq!(
+            filter
+                .evaluate(&Value::scalar(1234.5f64), &runtime)
+                .unwrap(),
+            liquid_core::value!("1.234,50")
+        );
+    }
+
+    #[test]
+    fn unit_number_format_not_number() {
+        liquid_core::call_filter!(NumberFormat, "abc").unwrap_err();
+        liquid_core::call_filter!(NumberFormat, 1f64, -1i32).unwrap_err();
+        liquid_core::call_filter!(NumberFormat, 1f64, 21i32).unwrap_err();
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn unit_decimal_math() {
+        fn call(filter: &dyn ParseFilter, input: Value, operand: Value) -> Result<Value> {
+            let args = liquid_core::compiler::FilterArguments {
+                positional: Box::new(vec![Expression::Literal(operand)].into_iter()),
+                keyword: Box::new(Vec::new().into_iter()),
+            };
+            let runtime = Runtime::default();
+            filter.parse(args)?.evaluate(&input, &runtime)
+        }
+        let price = Value::scalar("19.99".parse::<Decimal>().unwrap());
+
+        let total = call(&Times, price.clone(), Value::scalar(3)).unwrap();
+        assert_eq!(total.render().to_string(), "59.97");
+        let total = call(&Plus, price.clone(), Value::scalar(0.01)).unwrap();
+        assert_eq!(total.render().to_string(), "20.00");
+        let share = call(&DividedBy, Value::scalar(10), total).unwrap();
+        assert_eq!(share, Value::scalar("0.5".parse::<Decimal>().unwrap()));
+        call(&DividedBy, price, Value::scalar(0)).unwrap_err();
+    }
 }
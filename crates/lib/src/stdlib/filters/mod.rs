@@ -8,19 +8,26 @@ use liquid_core::{Value, ValueView};
 
 mod array;
 mod date;
+mod encoding;
 mod html;
 mod math;
 mod slice;
 mod string;
+mod unicode;
 mod url;
+pub mod v0_19;
 
 pub use self::array::{
-    Compact, Concat, First, Join, Last, Map, Reverse, Sort, SortNatural, Uniq, Where,
+    Average, Compact, Concat, First, Flatten, IndexOf, Join, Last, LastIndexOf, Map, MaxBy, MinBy,
+    Reject, Reverse, Sort, SortNatural, Uniq, Where,
 };
-pub use self::date::Date;
+#[cfg(feature = "time-zones")]
+pub use self::date::InTimeZone;
+pub use self::date::{Date, DateAdd, DateDiff, HumanizeDuration};
+pub use self::encoding::{Base64Encode, Hex};
 pub use self::html::{Escape, EscapeOnce, NewlineToBr, StripHtml};
 pub use self::math::{
-    Abs, AtLeast, AtMost, Ceil, DividedBy, Floor, Minus, Modulo, Plus, Round, Times,
+    Abs, AtLeast, AtMost, Ceil, DividedBy, Floor, Minus, Modulo, NumberFormat, Plus, Round, Times,
 };
 pub use self::slice::Slice;
 pub use self::string::case::{Capitalize, Downcase, Upcase};
@@ -33,7 +40,7 @@ pub use self::url::{UrlDecode, UrlEncode};
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "size",
-    description = "Returns the size of the input. For an array or object this is the number of elemets. For other values it's the lenght of its string representation.",
+    description = "Returns the size of the input. For an array or object this is the number of elemets. For other values it's the number of characters in its string representation.",
     parsed(SizeFilter)
 )]
 pub struct Size;
@@ -45,7 +52,7 @@ struct SizeFilter;
 impl Filter for SizeFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
         if let Some(x) = input.as_scalar() {
-            Ok(Value::scalar(x.to_kstr().len() as i32))
+            Ok(Value::scalar(unicode::char_count(&x.to_kstr()) as i32))
         } else if let Some(x) = input.as_array() {
             Ok(Value::scalar(x.size()))
         } else if let Some(x) = input.as_object() {
@@ -88,6 +95,10 @@ impl Filter for DefaultFilter {
             Ok(input.to_value())
         }
     }
+
+    fn accepts_undefined(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -105,6 +116,10 @@ mod tests {
             liquid_core::call_filter!(Size, "this has 22 characters").unwrap(),
             Value::scalar(22f64)
         );
+        assert_eq!(
+            liquid_core::call_filter!(Size, "déjà vu").unwrap(),
+            Value::scalar(7f64)
+        );
         assert_eq!(
             liquid_core::call_filter!(
                 Size,
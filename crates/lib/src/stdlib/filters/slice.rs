@@ -8,32 +8,34 @@ use liquid_core::{
 };
 use liquid_core::{Value, ValueView};
 
+use super::unicode;
 use crate::invalid_argument;
 
+/// Resolve `slice_offset` and `slice_length` against a sequence of `vec_length` elements.
+///
+/// Negative offsets count back from the end.  Returns `None` when the offset falls outside of the
+/// sequence.
 fn canonicalize_slice(
     slice_offset: isize,
     slice_length: isize,
     vec_length: usize,
-) -> (usize, usize) {
+) -> Option<(usize, usize)> {
     let vec_length = vec_length as isize;
 
-    // Cap slice_offset
-    let slice_offset = cmp::min(slice_offset, vec_length);
     // Reverse indexing
     let slice_offset = if slice_offset < 0 {
         slice_offset + vec_length
     } else {
         slice_offset
     };
+    if slice_offset < 0 || vec_length < slice_offset {
+        return None;
+    }
 
     // Cap slice_length
-    let slice_length = if slice_offset + slice_length > vec_length {
-        vec_length - slice_offset
-    } else {
-        slice_length
-    };
+    let slice_length = cmp::min(slice_length, vec_length - slice_offset);
 
-    (slice_offset as usize, slice_length as usize)
+    Some((slice_offset as usize, slice_length as usize))
 }
 
 #[derive(Debug, FilterParameters)]
@@ -43,6 +45,13 @@ struct SliceArgs {
 
     #[parameter(description = "The length of the slice.", arg_type = "integer")]
     length: Option<Expression>,
+
+    #[parameter(
+        description = "Slice strings by grapheme clusters rather than by characters.",
+        arg_type = "bool",
+        mode = "keyword"
+    )]
+    graphemes: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -73,7 +82,10 @@ impl Filter for SliceFilter {
         }
 
         if let Some(input) = input.as_array() {
-            let (offset, length) = canonicalize_slice(offset, length, input.size() as usize);
+            let (offset, length) = match canonicalize_slice(offset, length, input.size() as usize) {
+                Some(range) => range,
+                None => return Ok(Value::array(Vec::<Value>::new())),
+            };
             Ok(Value::array(
                 input
                     .values()
@@ -83,10 +95,16 @@ impl Filter for SliceFilter {
             ))
         } else {
             let input = input.to_kstr();
-            let (offset, length) = canonicalize_slice(offset, length, input.len());
-            Ok(Value::scalar(
-                input.chars().skip(offset).take(length).collect::<String>(),
-            ))
+            let parts = if args.graphemes.unwrap_or(false) {
+                unicode::graphemes(&input)
+            } else {
+                unicode::chars(&input)
+            };
+            let (offset, length) = match canonicalize_slice(offset, length, parts.len()) {
+                Some(range) => range,
+                None => return Ok(Value::scalar("")),
+            };
+            Ok(Value::scalar(parts[offset..offset + length].concat()))
         }
     }
 }
@@ -153,4 +171,32 @@ mod tests {
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn unit_slice_out_of_range() {
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "abc", 3).unwrap(),
+            liquid_core::value!("")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "abc", 4).unwrap(),
+            liquid_core::value!("")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "abc", -4, 2).unwrap(),
+            liquid_core::value!("")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, liquid_core::value!([1, 2]), -3).unwrap(),
+            liquid_core::value!([])
+        );
+    }
+
+    #[test]
+    fn unit_slice_multibyte() {
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "añb€", -2, 2).unwrap(),
+            liquid_core::value!("b€")
+        );
+    }
 }
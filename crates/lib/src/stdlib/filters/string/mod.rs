@@ -18,6 +18,12 @@ struct SplitArgs {
         arg_type = "str"
     )]
     pattern: Expression,
+
+    #[parameter(
+        description = "The maximum number of parts. When omitted or zero, trailing empty parts are removed.",
+        arg_type = "integer"
+    )]
+    limit: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -36,16 +42,75 @@ struct SplitFilter {
     args: SplitArgs,
 }
 
+/// Split `input` following Ruby's `String#split`.
+///
+/// - A single space separator splits on runs of whitespace, ignoring leading whitespace.
+/// - An empty separator splits into characters.
+/// - A positive `limit` caps the number of parts, the last part holding the remainder.
+/// - Without a positive or negative `limit`, trailing empty parts are removed.
+fn ruby_split<'s>(input: &'s str, pattern: &str, limit: i32) -> Vec<&'s str> {
+    if input.is_empty() {
+        return Vec::new();
+    }
+    let max_parts = if 0 < limit {
+        Some(limit as usize)
+    } else {
+        None
+    };
+    let at_limit = |parts: &Vec<&str>| max_parts == Some(parts.len() + 1);
+
+    let mut parts = Vec::new();
+    if pattern == " " {
+        let mut rest = input.trim_start();
+        loop {
+            if at_limit(&parts) {
+                parts.push(rest);
+                break;
+            }
+            match rest.find(char::is_whitespace) {
+                Some(index) => {
+                    parts.push(&rest[..index]);
+                    rest = rest[index..].trim_start();
+                }
+                None => {
+                    parts.push(rest);
+                    break;
+                }
+            }
+        }
+    } else if pattern.is_empty() {
+        for (index, c) in input.char_indices() {
+            if at_limit(&parts) {
+                parts.push(&input[index..]);
+                break;
+            }
+            parts.push(&input[index..index + c.len_utf8()]);
+        }
+    } else if let Some(max) = max_parts {
+        parts.extend(input.splitn(max, pattern));
+    } else {
+        parts.extend(input.split(pattern));
+    }
+
+    if limit == 0 {
+        while parts.last() == Some(&"") {
+            parts.pop();
+        }
+    }
+    parts
+}
+
 impl Filter for SplitFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
         let input = input.to_kstr();
+        let limit = args.limit.unwrap_or(0);
 
         // Split and construct resulting Array
         Ok(Value::Array(
-            input
-                .split(args.pattern.as_str())
+            ruby_split(input.as_str(), args.pattern.as_str(), limit)
+                .into_iter()
                 .map(|s| Value::scalar(s.to_owned()))
                 .collect(),
         ))
@@ -80,4 +145,56 @@ mod tests {
     fn unit_split_no_args() {
         liquid_core::call_filter!(Split, "a,b,c").unwrap_err();
     }
+
+    #[test]
+    fn unit_split_trailing_empty() {
+        assert_eq!(
+            liquid_core::call_filter!(Split, "a,b,,", ",").unwrap(),
+            liquid_core::value!(["a", "b"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Split, "a,b,,", ",", -1).unwrap(),
+            liquid_core::value!(["a", "b", "", ""])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Split, "", ",").unwrap(),
+            liquid_core::value!([])
+        );
+    }
+
+    #[test]
+    fn unit_split_limit() {
+        assert_eq!(
+            liquid_core::call_filter!(Split, "a,b,c", ",", 2).unwrap(),
+            liquid_core::value!(["a", "b,c"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Split, "a,b,c", ",", 1).unwrap(),
+            liquid_core::value!(["a,b,c"])
+        );
+    }
+
+    #[test]
+    fn unit_split_whitespace() {
+        assert_eq!(
+            liquid_core::call_filter!(Split, "  a \t b  c ", " ").unwrap(),
+            liquid_core::value!(["a", "b", "c"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Split, "a  b  c", " ", 2).unwrap(),
+            liquid_core::value!(["a", "b  c"])
+        );
+    }
+
+    #[test]
+    fn unit_split_empty_separator() {
+        assert_eq!(
+            liquid_core::call_filter!(Split, "añ€", "").unwrap(),
+            liquid_core::value!(["a", "ñ", "€"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Split, "añ€", "", 2).unwrap(),
+            liquid_core::value!(["a", "ñ€"])
+        );
+    }
 }
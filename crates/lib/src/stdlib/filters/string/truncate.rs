@@ -8,7 +8,8 @@ use liquid_core::{
     Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
 };
 use liquid_core::{Value, ValueView};
-use unicode_segmentation::UnicodeSegmentation;
+
+use crate::stdlib::filters::unicode;
 
 #[derive(Debug, FilterParameters)]
 struct TruncateArgs {
@@ -75,16 +76,12 @@ impl Filter for TruncateFilter {
 
         let truncate_string = args.ellipsis.unwrap_or_else(|| "...".into());
 
-        let l = cmp::max(lenght - truncate_string.len(), 0);
+        let l = lenght.saturating_sub(unicode::grapheme_count(&truncate_string));
 
         let input_string = input.to_kstr();
 
-        let result = if lenght < input_string.len() {
-            let result = UnicodeSegmentation::graphemes(input_string.as_str(), true)
-                .take(l)
-                .collect::<Vec<&str>>()
-                .join("")
-                + truncate_string.as_str();
+        let result = if lenght < unicode::grapheme_count(&input_string) {
+            let result = unicode::take_graphemes(&input_string, l).to_owned() + &truncate_string;
             Value::scalar(result)
         } else {
             input.to_value()
@@ -237,10 +234,15 @@ mod tests {
             liquid_core::value!("Here is an a\u{310}, e\u{301}, ...")
         );
 
-        // Note that the 🇷🇺🇸🇹 is treated as a single grapheme cluster.
+        // Note that each flag, like 🇷🇺, is treated as a single grapheme cluster, so the string
+        // is 19 characters long.
         assert_eq!(
             liquid_core::call_filter!(Truncate, "Here is a RUST: 🇷🇺🇸🇹.", 20i32).unwrap(),
-            liquid_core::value!("Here is a RUST: 🇷🇺...")
+            liquid_core::value!("Here is a RUST: 🇷🇺🇸🇹.")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "Here is a RUST: 🇷🇺🇸🇹.", 18i32, "…").unwrap(),
+            liquid_core::value!("Here is a RUST: 🇷🇺…")
         );
     }
 
@@ -299,4 +301,16 @@ mod tests {
             liquid_core::value!("")
         );
     }
+
+    #[test]
+    fn unit_truncate_multibyte() {
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "déjà vu", 7i32).unwrap(),
+            liquid_core::value!("déjà vu")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "déjà vu", 2i32, "...").unwrap(),
+            liquid_core::value!("...")
+        );
+    }
 }
@@ -0,0 +1,53 @@
+//! Unicode segmentation shared by filters.
+//!
+//! Filters measure and cut strings by characters, never by bytes, so that multi-byte text is not
+//! split mid-codepoint.  Filters about user-perceived characters, like `truncate`, use grapheme
+//! clusters so combining marks stay with their letter.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Number of codepoints in `s`.
+pub(crate) fn char_count(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Number of grapheme clusters in `s`.
+pub(crate) fn grapheme_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// `s` split into its codepoints.
+pub(crate) fn chars(s: &str) -> Vec<&str> {
+    s.char_indices()
+        .map(|(i, c)| &s[i..i + c.len_utf8()])
+        .collect()
+}
+
+/// `s` split into its grapheme clusters.
+pub(crate) fn graphemes(s: &str) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+/// The first `count` grapheme clusters of `s`.
+pub(crate) fn take_graphemes(s: &str, count: usize) -> &str {
+    match s.grapheme_indices(true).nth(count) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_segmentation() {
+        let s = "e\u{301}t\u{e9}";
+        assert_eq!(char_count(s), 4);
+        assert_eq!(grapheme_count(s), 3);
+        assert_eq!(chars(s), vec!["e", "\u{301}", "t", "\u{e9}"]);
+        assert_eq!(graphemes(s), vec!["e\u{301}", "t", "\u{e9}"]);
+        assert_eq!(take_graphemes(s, 1), "e\u{301}");
+        assert_eq!(take_graphemes(s, 5), s);
+    }
+}
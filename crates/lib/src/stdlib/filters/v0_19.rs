@@ -0,0 +1,338 @@
+//! Filters as they behaved in liquid 0.19, for `CompatLevel::V0_19`.
+//!
+//! Registered under the same names as their current counterparts, which they replace.
+
+use std::cmp;
+
+use liquid_core::value::DateTime;
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::invalid_argument;
+
+/// Formats strings were parsed with, besides `now`.
+const DATE_TIME_FORMATS: &[&str] = &["%d %B %Y %H:%M:%S %z", "%Y-%m-%d %H:%M:%S %z"];
+
+fn parse_date_time(s: &str) -> Option<DateTime> {
+    if s == "now" {
+        return Some(DateTime::now());
+    }
+    DATE_TIME_FORMATS
+        .iter()
+        .filter_map(|f| chrono::DateTime::parse_from_str(s, f).ok())
+        .map(DateTime::from_chrono)
+        .next()
+}
+
+#[derive(Debug, FilterParameters)]
+struct DateArgs {
+    #[parameter(description = "The format to return the date in.", arg_type = "str")]
+    format: Expression,
+}
+
+/// `date`, without epoch timestamps, locales, time zones or `DateFormats`.
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "date",
+    description = "Converts a timestamp into another date format.",
+    parameters(DateArgs),
+    parsed(DateFilter)
+)]
+pub struct Date;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "date"]
+struct DateFilter {
+    #[parameters]
+    args: DateArgs,
+}
+
+impl Filter for DateFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let date = input.as_scalar().and_then(|s| match s.type_name() {
+            "date time" => s.to_date_time(),
+            "string" => parse_date_time(s.into_string().as_str()),
+            _ => None,
+        });
+        match date {
+            Some(date) if !args.format.is_empty() => {
+                Ok(Value::scalar(date.format(args.format.as_str()).to_string()))
+            }
+            _ => Ok(input.to_value()),
+        }
+    }
+}
+
+/// `size`, counting the bytes of strings.
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "size",
+    description = "Returns the size of the input. For an array or object this is the number of elemets. For other values it's the lenght of its string representation.",
+    parsed(SizeFilter)
+)]
+pub struct Size;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "size"]
+struct SizeFilter;
+
+impl Filter for SizeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &Runtime<'_>) -> Result<Value> {
+        if let Some(x) = input.as_scalar() {
+            Ok(Value::scalar(x.to_kstr().len() as i32))
+        } else if let Some(x) = input.as_array() {
+            Ok(Value::scalar(x.size()))
+        } else if let Some(x) = input.as_object() {
+            Ok(Value::scalar(x.size()))
+        } else {
+            Ok(Value::scalar(0i32))
+        }
+    }
+}
+
+fn canonicalize_slice(
+    slice_offset: isize,
+    slice_length: isize,
+    vec_length: usize,
+) -> (usize, usize) {
+    let vec_length = vec_length as isize;
+
+    // Cap slice_offset
+    let slice_offset = cmp::min(slice_offset, vec_length);
+    // Reverse indexing
+    let slice_offset = if slice_offset < 0 {
+        slice_offset + vec_length
+    } else {
+        slice_offset
+    };
+
+    // Cap slice_length
+    let slice_length = if slice_offset + slice_length > vec_length {
+        vec_length - slice_offset
+    } else {
+        slice_length
+    };
+
+    (slice_offset as usize, slice_length as usize)
+}
+
+#[derive(Debug, FilterParameters)]
+struct SliceArgs {
+    #[parameter(description = "The offset of the slice.", arg_type = "integer")]
+    offset: Expression,
+
+    #[parameter(description = "The length of the slice.", arg_type = "integer")]
+    length: Option<Expression>,
+}
+
+/// `slice`, resolving offsets into strings against their length in bytes.
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "slice",
+    description = "Takes a slice of a given string or array.",
+    parameters(SliceArgs),
+    parsed(SliceFilter)
+)]
+pub struct Slice;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "slice"]
+struct SliceFilter {
+    #[parameters]
+    args: SliceArgs,
+}
+
+impl Filter for SliceFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let offset = args.offset as isize;
+        let length = args.length.unwrap_or(1) as isize;
+
+        if length < 1 {
+            return invalid_argument("length", "Positive number expected").into_err();
+        }
+
+        if let Some(input) = input.as_array() {
+            let (offset, length) = canonicalize_slice(offset, length, input.size() as usize);
+            Ok(Value::array(
+                input
+                    .values()
+                    .skip(offset)
+                    .take(length)
+                    .map(|s| s.to_value()),
+            ))
+        } else {
+            let input = input.to_kstr();
+            let (offset, length) = canonicalize_slice(offset, length, input.len());
+            Ok(Value::scalar(
+                input.chars().skip(offset).take(length).collect::<String>(),
+            ))
+        }
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct SplitArgs {
+    #[parameter(
+        description = "The separator between each element in the string.",
+        arg_type = "str"
+    )]
+    pattern: Expression,
+}
+
+/// `split`, keeping empty strings and without a limit.
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "split",
+    description = "Divides an input string into an array using the argument as a separator.",
+    parameters(SplitArgs),
+    parsed(SplitFilter)
+)]
+pub struct Split;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "split"]
+struct SplitFilter {
+    #[parameters]
+    args: SplitArgs,
+}
+
+impl Filter for SplitFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input.to_kstr();
+
+        Ok(Value::Array(
+            input
+                .split(args.pattern.as_str())
+                .map(|s| Value::scalar(s.to_owned()))
+                .collect(),
+        ))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct TruncateArgs {
+    #[parameter(
+        description = "The maximum lenght of the string, after which it will be truncated.",
+        arg_type = "integer"
+    )]
+    lenght: Option<Expression>,
+
+    #[parameter(
+        description = "The text appended to the end of the string if it is truncated. This text counts to the maximum lenght of the string. Defaults to \"...\".",
+        arg_type = "str"
+    )]
+    ellipsis: Option<Expression>,
+}
+
+/// `truncate`, comparing the length in bytes of the input and ellipsis against a length in
+/// grapheme clusters.
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "truncate",
+    description = "Shortens a string down to the number of characters passed as a parameter.",
+    parameters(TruncateArgs),
+    parsed(TruncateFilter)
+)]
+pub struct Truncate;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "truncate"]
+struct TruncateFilter {
+    #[parameters]
+    args: TruncateArgs,
+}
+
+impl Filter for TruncateFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let lenght = args.lenght.unwrap_or(50) as usize;
+
+        let truncate_string = args.ellipsis.unwrap_or_else(|| "...".into());
+
+        let l = lenght.saturating_sub(truncate_string.len());
+
+        let input_string = input.to_kstr();
+
+        let result = if lenght < input_string.len() {
+            let result = UnicodeSegmentation::graphemes(input_string.as_str(), true)
+                .take(l)
+                .collect::<Vec<&str>>()
+                .join("")
+                + truncate_string.as_str();
+            Value::scalar(result)
+        } else {
+            input.to_value()
+        };
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_ignores_integers() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, 0, "%Y").unwrap(),
+            liquid_core::value!(0)
+        );
+    }
+
+    #[test]
+    fn date_only_parses_0_19_formats() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-06-13 02:30:00 +0300", "%Y-%m-%d").unwrap(),
+            liquid_core::value!("2016-06-13")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-06-13", "%Y").unwrap(),
+            liquid_core::value!("2016-06-13")
+        );
+    }
+
+    #[test]
+    fn size_counts_bytes() {
+        assert_eq!(
+            liquid_core::call_filter!(Size, "été").unwrap(),
+            liquid_core::value!(5)
+        );
+    }
+
+    #[test]
+    fn slice_measures_bytes() {
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "añb€", -2, 2).unwrap(),
+            liquid_core::value!("")
+        );
+    }
+
+    #[test]
+    fn split_keeps_empty_strings() {
+        assert_eq!(
+            liquid_core::call_filter!(Split, "a,b,,", ",").unwrap(),
+            liquid_core::value!(["a", "b", "", ""])
+        );
+    }
+
+    #[test]
+    fn truncate_compares_bytes() {
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "été", 4).unwrap(),
+            liquid_core::value!("é...")
+        );
+    }
+}
@@ -11,6 +11,8 @@ use liquid_core::{ParseTag, TagReflection, TagTokenIter};
 #[derive(Debug)]
 struct Assign {
     dst: String,
+    // Where `dst` is assigned, for shadowing warnings.
+    position: String,
     src: FilterChain,
 }
 
@@ -27,7 +29,13 @@ impl Renderable for Assign {
             .evaluate(runtime)
             .trace_with(|| self.trace().into())?
             .into_owned();
-        runtime.stack_mut().set_global(self.dst.to_owned(), value);
+        runtime
+            .stack()
+            .warn_shadowing(self.dst.as_str(), self.position.as_str());
+        runtime
+            .stack_mut()
+            .try_set_global(self.dst.to_owned(), value)
+            .trace_with(|| self.trace().into())?;
         Ok(())
     }
 }
@@ -57,11 +65,9 @@ impl ParseTag for AssignTag {
         mut arguments: TagTokenIter<'_>,
         options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let dst = arguments
-            .expect_next("Identifier expected.")?
-            .expect_identifier()
-            .into_result()?
-            .to_string();
+        let dst = arguments.expect_next("Identifier expected.")?;
+        let (line, column) = dst.line_col();
+        let dst = dst.expect_identifier().into_result()?.to_string();
 
         arguments
             .expect_next("Assignment operator \"=\" expected.")?
@@ -76,7 +82,11 @@ impl ParseTag for AssignTag {
         // no more arguments should be supplied, trying to supply them is an error
         arguments.expect_nothing()?;
 
-        Ok(Box::new(Assign { dst, src }))
+        Ok(Box::new(Assign {
+            dst,
+            position: format!("{}:{}", line, column),
+            src,
+        }))
     }
 
     fn reflection(&self) -> &dyn TagReflection {
@@ -122,6 +132,32 @@ mod test {
         assert_eq!(output, "false");
     }
 
+    #[test]
+    fn assign_shadowing() {
+        let options = options();
+        let template = compiler::parse(
+            "{% for item in items %}{% assign item = 'x' %}{{ item }}{% endfor %}\n\
+             {% assign user = 'Bob' %}",
+            &options,
+        )
+        .map(interpreter::Template::new)
+        .unwrap();
+
+        let globals = liquid_core::object!({"items": [1, 2], "user": "Ann"});
+        let mut runtime = interpreter::RuntimeBuilder::new()
+            .set_globals(&globals)
+            .build();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "12\n");
+        assert_eq!(
+            runtime.stack().warnings(),
+            [
+                "Assigning `item` at 1:34 has no effect in the scope declaring it at 1:8",
+                "Assigning `user` at 2:11 hides the global of the same name",
+            ]
+        );
+    }
+
     #[test]
     fn assign_array_indexing() {
         let text = concat!("{% assign freestyle = tags[1] %}", "{{ freestyle }}");
@@ -169,6 +205,67 @@ mod test {
         assert_eq!(output, "alpha");
     }
 
+    #[test]
+    fn assign_collection_literals() {
+        let text = concat!(
+            r#"{% assign point = {"x": 1, "y": y} %}"#,
+            r#"{% assign list = ["a", point.y] %}"#,
+            "{{ point.x }},{{ point.y }},{{ list[0] }},{{ list[1] }}"
+        );
+        let options = options();
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        runtime.stack_mut().set_global("y", Value::scalar(2));
+
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "1,2,a,2");
+    }
+
+    #[test]
+    fn assign_interpolation() {
+        let text = concat!(
+            r#"{% assign greeting = "Hello #{user.name}, you have #{count} messages" %}"#,
+            "{{ greeting }}"
+        );
+        let mut options = options();
+        options.interpolation = true;
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        runtime.stack_mut().set_global(
+            "user",
+            Value::Object(
+                vec![("name".into(), Value::scalar("Ann"))]
+                    .into_iter()
+                    .collect(),
+            ),
+        );
+        runtime.stack_mut().set_global("count", Value::scalar(3));
+
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "Hello Ann, you have 3 messages");
+    }
+
+    #[test]
+    fn assign_interpolation_disabled() {
+        let text = r#"{% assign greeting = "Hello #{name}" %}{{ greeting }}"#;
+        let options = options();
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let mut runtime = Runtime::new();
+        runtime.stack_mut().set_global("name", Value::scalar("Ann"));
+
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "Hello #{name}");
+    }
+
     #[test]
     fn assign_in_loop_persists_on_loop_exit() {
         let text = concat!(
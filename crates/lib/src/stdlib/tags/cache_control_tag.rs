@@ -0,0 +1,208 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Object;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Value;
+use liquid_core::ValueView;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+use crate::invalid_argument;
+
+/// Key of the hints in `Runtime::metadata`.
+pub const CACHE_CONTROL: &str = "cache_control";
+
+const ATTRIBUTES: &[&str] = &["max_age", "vary", "private"];
+
+#[derive(Debug)]
+struct CacheControl {
+    attributes: Vec<(String, Expression)>,
+}
+
+impl CacheControl {
+    fn trace(&self) -> String {
+        let attributes = itertools::join(
+            self.attributes
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value)),
+            ", ",
+        );
+        format!("{{% cache_control {} %}}", attributes)
+    }
+}
+
+/// Merge a hint into `hints`, keeping the most restrictive of repeated hints.
+fn merge(hints: &mut Object, name: &str, value: &dyn ValueView) -> Result<()> {
+    match name {
+        "max_age" => {
+            let max_age = value
+                .as_scalar()
+                .and_then(|s| s.to_integer())
+                .filter(|n| *n >= 0)
+                .ok_or_else(|| invalid_argument("max_age", "Whole number of seconds expected"))?;
+            let max_age = match hints.get("max_age").and_then(|v| v.as_scalar()) {
+                Some(previous) => previous.to_integer().unwrap_or(max_age).min(max_age),
+                None => max_age,
+            };
+            hints.insert("max_age".into(), Value::scalar(max_age));
+        }
+        "vary" => {
+            let vary = hints
+                .entry("vary")
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(vary) = vary {
+                let path = Value::scalar(value.to_kstr().into_owned());
+                if !vary.contains(&path) {
+                    vary.push(path);
+                }
+            }
+        }
+        "private" => {
+            let private = value.query_state(liquid_core::value::State::Truthy)
+                || hints
+                    .get("private")
+                    .map(|v| v.query_state(liquid_core::value::State::Truthy))
+                    .unwrap_or(false);
+            hints.insert("private".into(), Value::scalar(private));
+        }
+        _ => unreachable!("attributes are checked when parsing"),
+    }
+    Ok(())
+}
+
+impl Renderable for CacheControl {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        let mut values = Vec::with_capacity(self.attributes.len());
+        for (name, value) in self.attributes.iter() {
+            let value = value
+                .evaluate(runtime)
+                .trace_with(|| self.trace().into())?
+                .into_owned();
+            values.push((name.as_str(), value));
+        }
+
+        let hints = runtime
+            .metadata_mut()
+            .entry(CACHE_CONTROL)
+            .or_insert_with(|| Value::Object(Object::new()));
+        if let Value::Object(hints) = hints {
+            for (name, value) in values {
+                merge(hints, name, &value).trace_with(|| self.trace().into())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CacheControlTag;
+
+impl CacheControlTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for CacheControlTag {
+    fn tag(&self) -> &'static str {
+        "cache_control"
+    }
+
+    fn description(&self) -> &'static str {
+        "Declares how long the output may be cached (`max_age`), which variables it depends on (`vary`) and whether it is specific to a user (`private`). Renders nothing; the hints are reported to the application."
+    }
+}
+
+impl ParseTag for CacheControlTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let mut attributes = Vec::new();
+        while let Some(token) = arguments.next() {
+            if token.as_str() == "," {
+                continue;
+            }
+
+            let name = token.expect_identifier().into_result()?;
+            if !ATTRIBUTES.contains(&name) {
+                return Err(arguments.raise_error(&format!(
+                    "Unknown attribute \"{}\", expected one of {}.",
+                    name,
+                    ATTRIBUTES.join(", ")
+                )));
+            }
+            arguments
+                .expect_next("\":\" expected.")?
+                .expect_str(":")
+                .into_result_custom_msg("\":\" expected.")?;
+            let value = arguments
+                .expect_next("Value expected.")?
+                .expect_value()
+                .into_result()?;
+            attributes.push((name.to_owned(), value));
+        }
+
+        if attributes.is_empty() {
+            return Err(arguments.raise_error("Attribute expected."));
+        }
+
+        Ok(Box::new(CacheControl { attributes }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::compiler;
+    use liquid_core::interpreter;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("cache_control".to_string(), CacheControlTag.into());
+        options
+    }
+
+    #[test]
+    fn cache_control() {
+        let options = options();
+        let template = compiler::parse(
+            "a{% cache_control max_age: 300, vary: 'user.locale' %}b\
+             {% cache_control max_age: 60, vary: 'user.locale', private: true %}c",
+            &options,
+        )
+        .map(interpreter::Template::new)
+        .unwrap();
+
+        let mut runtime = Runtime::new();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "abc");
+        assert_eq!(
+            runtime.metadata().get(CACHE_CONTROL).unwrap(),
+            &liquid_core::value!({
+                "max_age": 60,
+                "vary": ["user.locale"],
+                "private": true
+            })
+        );
+    }
+
+    #[test]
+    fn cache_control_unknown_attribute() {
+        let options = options();
+        let template = compiler::parse("{% cache_control ttl: 60 %}", &options);
+        assert!(template.is_err());
+    }
+}
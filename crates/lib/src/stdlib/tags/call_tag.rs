@@ -0,0 +1,199 @@
+use std::io::Write;
+
+use itertools;
+use liquid_core::error::{Error, ResultLiquidExt};
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Object;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+#[derive(Debug)]
+struct Call {
+    function: String,
+    args: Vec<(kstring::KString, Expression)>,
+    dst: String,
+}
+
+impl Call {
+    fn trace(&self) -> String {
+        let args = itertools::join(
+            self.args
+                .iter()
+                .map(|(name, arg)| format!("{}: {}", name, arg)),
+            ", ",
+        );
+        format!("{{% call {} {} into {} %}}", self.function, args, self.dst)
+    }
+}
+
+impl Renderable for Call {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        let mut args = Object::new();
+        for (name, arg) in self.args.iter() {
+            let value = arg
+                .evaluate(runtime)
+                .trace_with(|| self.trace().into())?
+                .into_owned();
+            args.insert(name.clone(), value);
+        }
+
+        let value = runtime
+            .functions()
+            .ok_or_else(|| Error::with_msg("No functions are available to call"))
+            .and_then(|functions| functions.call(&self.function, &args))
+            .trace_with(|| self.trace().into())?;
+        runtime
+            .stack_mut()
+            .try_set_global(self.dst.to_owned(), value)
+            .trace_with(|| self.trace().into())?;
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CallTag;
+
+impl CallTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for CallTag {
+    fn tag(&self) -> &'static str {
+        "call"
+    }
+
+    fn description(&self) -> &'static str {
+        "Calls a function provided by the application, like `{% call pricing.quote sku: item.sku into price %}`, and assigns its result."
+    }
+}
+
+impl ParseTag for CallTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let function = arguments.expect_next("Function name expected.")?;
+        let function_name = function.as_str().to_owned();
+        function.expect_variable().into_result()?;
+
+        let mut args = Vec::new();
+        let dst = loop {
+            let token = arguments.expect_next("\"into\" expected.")?;
+            if token.as_str() == "into" {
+                break arguments
+                    .expect_next("Identifier expected.")?
+                    .expect_identifier()
+                    .into_result()?
+                    .to_string();
+            }
+            if token.as_str() == "," {
+                continue;
+            }
+
+            let name = token.expect_identifier().into_result()?;
+            arguments
+                .expect_next("\":\" expected.")?
+                .expect_str(":")
+                .into_result_custom_msg("\":\" expected.")?;
+            let value = arguments
+                .expect_next("Value expected.")?
+                .expect_value()
+                .into_result()?;
+            args.push((kstring::KString::from_ref(name), value));
+        };
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Call {
+            function: function_name,
+            args,
+            dst,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::compiler;
+    use liquid_core::interpreter;
+    use liquid_core::interpreter::{FunctionRegistry, RuntimeBuilder};
+    use liquid_core::Value;
+    use liquid_core::ValueView;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options.tags.register("call".to_string(), CallTag.into());
+        options
+    }
+
+    fn functions() -> FunctionRegistry {
+        let mut functions = FunctionRegistry::new();
+        functions.register("pricing.quote", |args| {
+            let sku = args.get("sku").map(|v| v.render().to_string());
+            let quantity = args
+                .get("quantity")
+                .and_then(|v| v.as_scalar())
+                .and_then(|v| v.to_integer())
+                .unwrap_or(1);
+            Ok(Value::scalar(format!(
+                "{} x{}",
+                sku.unwrap_or_default(),
+                quantity
+            )))
+        });
+        functions.register("pricing.currency", |_args| Ok(Value::scalar("EUR")));
+        functions
+    }
+
+    #[test]
+    fn call() {
+        let options = options();
+        let template = compiler::parse(
+            "{% call pricing.quote sku: 'ABC', quantity: 3 into price %}{{ price }} \
+             {% call pricing.currency into currency %}{{ currency }}",
+            &options,
+        )
+        .map(interpreter::Template::new)
+        .unwrap();
+
+        let functions = functions();
+        let mut runtime = RuntimeBuilder::new().set_functions(&functions).build();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "ABC x3 EUR");
+    }
+
+    #[test]
+    fn call_unknown_function() {
+        let options = options();
+        let template = compiler::parse("{% call pricing.refund into result %}", &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let functions = functions();
+        let mut runtime = RuntimeBuilder::new().set_functions(&functions).build();
+        assert!(template.render(&mut runtime).is_err());
+
+        let mut runtime = Runtime::new();
+        assert!(template.render(&mut runtime).is_err());
+    }
+
+    #[test]
+    fn call_requires_into() {
+        let options = options();
+        let template = compiler::parse("{% call pricing.quote sku: 'ABC' %}", &options);
+        assert!(template.is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Value;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+#[derive(Debug)]
+struct Constant {
+    dst: String,
+    value: Value,
+}
+
+impl Renderable for Constant {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        runtime
+            .stack_mut()
+            .try_set_global(self.dst.to_owned(), self.value.clone())
+            .trace_with(|| format!("{{% constant {} %}}", self.dst).into())?;
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ConstantTag;
+
+impl ConstantTag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TagReflection for ConstantTag {
+    fn tag(&self) -> &'static str {
+        "constant"
+    }
+
+    fn description(&self) -> &'static str {
+        "Assigns a value computed once, when the template is parsed. The value may only be built from literals and filters."
+    }
+}
+
+impl ParseTag for ConstantTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let dst = arguments
+            .expect_next("Identifier expected.")?
+            .expect_identifier()
+            .into_result()?
+            .to_string();
+
+        arguments
+            .expect_next("Assignment operator \"=\" expected.")?
+            .expect_str("=")
+            .into_result_custom_msg("Assignment operator \"=\" expected.")?;
+
+        let src = arguments
+            .expect_next("FilterChain expected.")?
+            .expect_filter_chain(options)
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        // There are no variables at parse time, so anything but literals is reported here.
+        let runtime = Runtime::new();
+        let value = src
+            .evaluate(&runtime)
+            .trace_with(|| format!("{{% constant {} = {}%}}", dst, src).into())?
+            .into_owned();
+
+        Ok(Box::new(Constant { dst, value }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::compiler;
+    use liquid_core::interpreter;
+
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("constant".to_string(), ConstantTag.into());
+        options
+            .filters
+            .register("split".to_string(), Box::new(stdlib::Split));
+        options
+    }
+
+    #[test]
+    fn constant() {
+        let options = options();
+        let template = compiler::parse(
+            "{% constant letters = 'a,b,c' | split: ',' %}{{ letters[1] }}",
+            &options,
+        )
+        .map(interpreter::Template::new)
+        .unwrap();
+
+        for _ in 0..2 {
+            let mut runtime = Runtime::new();
+            let output = template.render(&mut runtime).unwrap();
+            assert_eq!(output, "b");
+        }
+    }
+
+    #[test]
+    fn constant_rejects_variables() {
+        let options = options();
+        let template = compiler::parse("{% constant letters = alphabet %}", &options);
+        assert!(template.is_err());
+    }
+}
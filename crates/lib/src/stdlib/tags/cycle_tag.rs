@@ -31,8 +31,8 @@ impl Cycle {
 impl Renderable for Cycle {
     fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
         let expr = runtime
-            .get_register_mut::<State>()
-            .cycle(&self.name, &self.values)
+            .with_render_register(|state: &mut State| state.cycle(&self.name, &self.values))
+            .and_then(|expr| expr)
             .trace_with(|| self.trace().into())?;
         let value = expr.evaluate(runtime).trace_with(|| self.trace().into())?;
         write!(writer, "{}", value.render()).replace("Failed to render")?;
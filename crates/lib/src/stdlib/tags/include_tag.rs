@@ -1,7 +1,8 @@
 use std::io::Write;
+use std::sync::Arc;
 
 use liquid_core::compiler::TryMatchToken;
-use liquid_core::error::ResultLiquidExt;
+use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -13,24 +14,70 @@ use liquid_core::{ParseTag, TagReflection, TagTokenIter};
 #[derive(Debug)]
 struct Include {
     partial: Expression,
+    fallback: Option<Expression>,
 }
 
-impl Renderable for Include {
-    fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
-        let name = self.partial.evaluate(runtime)?.render().to_string();
-        runtime.run_in_named_scope(name.clone(), |mut scope| -> Result<()> {
-            let partial = scope
-                .partials()
-                .get(&name)
-                .trace_with(|| format!("{{% include {} %}}", self.partial).into())?;
+impl Include {
+    fn get_partial(&self, name: &str, runtime: &Runtime<'_>) -> Result<Arc<dyn Renderable>> {
+        runtime
+            .partials()
+            .get(name)
+            .trace_with(|| format!("{{% include {} %}}", self.partial).into())
+    }
+
+    fn render_partial(
+        &self,
+        name: &str,
+        partial: &dyn Renderable,
+        writer: &mut dyn Write,
+        runtime: &mut Runtime<'_>,
+    ) -> Result<()> {
+        runtime.run_in_named_scope(name.to_owned(), |mut scope| -> Result<()> {
             partial
                 .render_to(writer, &mut scope)
                 .trace_with(|| format!("{{% include {} %}}", self.partial).into())
                 .context_key_with(|| self.partial.to_string().into())
                 .value_with(|| name.to_string().into())
-        })?;
+        })
+    }
+}
 
-        Ok(())
+impl Renderable for Include {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        let name = self.partial.evaluate(runtime)?.render().to_string();
+        let partial = self.get_partial(&name, runtime);
+        let fallback = match self.fallback {
+            Some(ref fallback) => fallback,
+            None => return self.render_partial(&name, partial?.as_ref(), writer, runtime),
+        };
+
+        // A missing partial always falls back, a failing one only when the render is lax.
+        match partial {
+            Ok(partial) if !runtime.is_lax() => {
+                return self.render_partial(&name, partial.as_ref(), writer, runtime);
+            }
+            Ok(partial) => {
+                // The partial's elements record their errors rather than failing the render.
+                let recorded = runtime.render_errors().len();
+                let mut buffer = Vec::new();
+                match self.render_partial(&name, partial.as_ref(), &mut buffer, runtime) {
+                    Ok(()) if runtime.render_errors().len() == recorded => {
+                        writer.write_all(&buffer).replace("Failed to render")?;
+                        return Ok(());
+                    }
+                    Ok(()) => {}
+                    Err(error) => runtime.record_error(error),
+                }
+            }
+            Err(error) => runtime.record_error(error),
+        }
+
+        let fallback = fallback.evaluate(runtime)?.render().to_string();
+        if fallback.is_empty() {
+            return Ok(());
+        }
+        let partial = self.get_partial(&fallback, runtime)?;
+        self.render_partial(&fallback, partial.as_ref(), writer, runtime)
     }
 }
 
@@ -69,12 +116,30 @@ impl ParseTag for IncludeTag {
             TryMatchToken::Fails(name) => name.as_str().to_string(),
         };
 
+        let fallback = match arguments.next() {
+            Some(token) => {
+                token
+                    .expect_str("fallback")
+                    .into_result_custom_msg("\"fallback\" expected.")?;
+                arguments
+                    .expect_next("\":\" expected.")?
+                    .expect_str(":")
+                    .into_result_custom_msg("\":\" expected.")?;
+                let fallback = arguments
+                    .expect_next("Value expected.")?
+                    .expect_value()
+                    .into_result()?;
+                Some(fallback)
+            }
+            None => None,
+        };
+
         // no more arguments should be supplied, trying to supply them is an error
         arguments.expect_nothing()?;
 
         let partial = Expression::with_literal(name);
 
-        Ok(Box::new(Include { partial }))
+        Ok(Box::new(Include { partial, fallback }))
     }
 
     fn reflection(&self) -> &dyn TagReflection {
@@ -112,6 +177,7 @@ mod test {
 
         fn try_get<'a>(&'a self, name: &str) -> Option<borrow::Cow<'a, str>> {
             match name {
+                "failing.txt" => Some("a{% include 'file_does_not_exist.liquid' %}b".into()),
                 "example.txt" => Some(r#"{{'whooo' | size}}{%comment%}What happens{%endcomment%} {%if num < numTwo%}wat{%else%}wot{%endif%} {%if num > numTwo%}wat{%else%}wot{%endif%}"#.into()),
                 _ => None
             }
@@ -228,4 +294,76 @@ mod test {
         let output = template.render(&mut runtime);
         assert!(output.is_err());
     }
+
+    #[test]
+    fn missing_with_fallback() {
+        let text = "{% include 'file_does_not_exist.liquid' fallback: 'example.txt' %}";
+        let mut options = options();
+        options
+            .filters
+            .register("size".to_string(), Box::new(SizeFilterParser));
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let mut runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        runtime.stack_mut().set_global("num", Value::scalar(5f64));
+        runtime
+            .stack_mut()
+            .set_global("numTwo", Value::scalar(10f64));
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "5 wat wot");
+        assert_eq!(runtime.render_errors().len(), 1);
+    }
+
+    #[test]
+    fn failing_with_fallback() {
+        let text = "{% include 'failing.txt' fallback: 'example.txt' %}";
+        let mut options = options();
+        options
+            .filters
+            .register("size".to_string(), Box::new(SizeFilterParser));
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let mut runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .lax()
+            .build();
+        runtime.stack_mut().set_global("num", Value::scalar(5f64));
+        runtime
+            .stack_mut()
+            .set_global("numTwo", Value::scalar(10f64));
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "5 wat wot");
+        assert_eq!(runtime.render_errors().len(), 1);
+    }
+
+    #[test]
+    fn missing_with_empty_fallback() {
+        let text = "a{% include 'file_does_not_exist.liquid' fallback: '' %}b";
+        let options = options();
+        let template = compiler::parse(text, &options)
+            .map(interpreter::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let mut runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&mut runtime).unwrap();
+        assert_eq!(output, "ab");
+        assert_eq!(runtime.render_errors().len(), 1);
+    }
 }
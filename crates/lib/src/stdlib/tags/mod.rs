@@ -1,10 +1,16 @@
 mod assign_tag;
+mod cache_control_tag;
+mod call_tag;
+mod constant_tag;
 mod cycle_tag;
 mod include_tag;
 mod increment_tags;
 mod interrupt_tags;
 
 pub use self::assign_tag::AssignTag;
+pub use self::cache_control_tag::{CacheControlTag, CACHE_CONTROL};
+pub use self::call_tag::CallTag;
+pub use self::constant_tag::ConstantTag;
 pub use self::cycle_tag::CycleTag;
 pub use self::include_tag::IncludeTag;
 pub use self::increment_tags::DecrementTag;
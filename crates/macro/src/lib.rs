@@ -0,0 +1,67 @@
+//! Validation of templates at compile time.
+//!
+//! Templates are still parsed at runtime, once, so this saves no startup cost; it reports
+//! template errors when the crate is compiled rather than when the template is first used.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_quote::quote;
+use syn::parse_macro_input;
+use syn::LitStr;
+
+/// Creates a `&'static liquid::Template` from a string literal, validating it when the crate is
+/// compiled.
+///
+/// The template is parsed with the standard library's tags, blocks and filters only, so it can't
+/// use custom plugins or partials; errors, like an unknown filter or an unclosed block, are
+/// reported as compile errors.
+///
+/// The compile-time parse is only validation: no compiled representation is embedded in the
+/// binary.  The expansion parses the template text again on first use and keeps the `Template`
+/// in a `static`, so later evaluations share it.
+///
+/// The check uses the `liquid` this macro was built with, whose features can differ from the
+/// calling crate's.  A template relying on a feature only the caller enables fails the check,
+/// and one relying on a feature only the macro's `liquid` enables passes it but panics on first
+/// use.
+///
+/// # Examples
+///
+/// ```rust
+/// let template = liquid_macro::liquid_template!("Hello {{ name | upcase }}");
+/// let output = template.render(&liquid::object!({"name": "world"})).unwrap();
+/// assert_eq!(output, "Hello WORLD");
+/// ```
+///
+/// ```rust,compile_fail
+/// let template = liquid_macro::liquid_template!("{% if true %}Unclosed");
+/// ```
+#[proc_macro]
+pub fn liquid_template(input: TokenStream) -> TokenStream {
+    let text = parse_macro_input!(input as LitStr);
+
+    let checked = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .and_then(|parser| parser.parse(&text.value()));
+    if let Err(err) = checked {
+        let message = format!("invalid liquid template: {}", err);
+        return syn::Error::new(text.span(), message)
+            .to_compile_error()
+            .into();
+    }
+
+    let output = quote! {
+        {
+            static TEMPLATE: ::liquid::once_cell::sync::Lazy<::liquid::Template> =
+                ::liquid::once_cell::sync::Lazy::new(|| {
+                    ::liquid::ParserBuilder::with_stdlib()
+                        .build()
+                        .and_then(|parser| parser.parse(#text))
+                        .expect("Template was checked by `liquid_template!`")
+                });
+            &*TEMPLATE
+        }
+    };
+    output.into()
+}
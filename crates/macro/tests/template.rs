@@ -0,0 +1,15 @@
+use liquid_macro::liquid_template;
+
+#[test]
+fn renders() {
+    let template =
+        liquid_template!("{% for item in items %}{{ item | upcase }}{% unless forloop.last %}, {% endunless %}{% endfor %}");
+    let globals = liquid::object!({ "items": ["a", "b"] });
+    assert_eq!(template.render(&globals).unwrap(), "A, B");
+}
+
+#[test]
+fn parses_once() {
+    let templates: Vec<&liquid::Template> = (0..2).map(|_| liquid_template!("{{ x }}")).collect();
+    assert!(std::ptr::eq(templates[0], templates[1]));
+}
@@ -0,0 +1,335 @@
+//! A minimal static site generator, showing how the parts of `liquid` fit together.
+//!
+//! A site is a directory of:
+//! - `_config.yml`, optional, available as `site`.
+//! - `_layouts/<name>.html`, wrapping the pages whose front matter sets `layout: <name>` with
+//!   the page's output as `content`.  Layouts may set a `layout` of their own.
+//! - `_includes/<name>`, partials for Jekyll's `{% include <name> %}`, compiled on first use.
+//! - `_posts/*`, a collection written to `posts/` and listed as `site.posts`, by file name
+//!   from newest to oldest, like for `2020-01-31-hello.html`.
+//! - Any other file not starting with `_` or `.`: pages when they start with YAML front matter
+//!   between `---` lines, available as `page`, and otherwise copied as is.
+//!
+//! Pages are only rendered as liquid, converting markdown is left out.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! let site = liquid_ssg::Site::load("my-site").unwrap();
+//! let report = site.build("my-site/_site", true).unwrap();
+//! println!("{} written, {} up to date", report.written.len(), report.skipped.len());
+//! ```
+
+use std::collections::HashMap;
+use std::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use liquid::partials::{InMemorySource, LazyCompiler};
+use liquid::{IntoValue, Object, Parser, ParserBuilder, Template, ValueView};
+
+/// Errors of loading and building a site, like IO, YAML and liquid errors.
+pub type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+/// Layouts wrapping a layout wrapping a ..., deeper than this are assumed to be a cycle.
+const MAX_LAYOUT_DEPTH: usize = 16;
+
+/// The files written and left alone by `Site::build`, relative to the destination.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildReport {
+    /// Outputs rendered or copied.
+    pub written: Vec<PathBuf>,
+    /// Outputs newer than everything they are built from, when building incrementally.
+    pub skipped: Vec<PathBuf>,
+}
+
+/// A page or post, with its front matter split off.
+struct Document {
+    /// Path of the output, relative to the destination.
+    output: PathBuf,
+    page: Object,
+    template: Template,
+    modified: SystemTime,
+}
+
+/// A file without front matter, copied as is.
+struct StaticFile {
+    source: PathBuf,
+    output: PathBuf,
+    modified: SystemTime,
+}
+
+struct Layout {
+    front_matter: Object,
+    template: Template,
+}
+
+/// A site loaded from its source directory, see the crate documentation for the layout.
+pub struct Site {
+    config: Object,
+    layouts: HashMap<String, Layout>,
+    pages: Vec<Document>,
+    posts: Vec<Document>,
+    files: Vec<StaticFile>,
+    /// Newest change to what every page may depend on: the config, layouts, includes and posts.
+    shared_modified: SystemTime,
+}
+
+impl Site {
+    /// Read and parse the site in `source`.
+    pub fn load<P: AsRef<Path>>(source: P) -> Result<Self> {
+        let source = source.as_ref();
+        let mut shared_modified = SystemTime::UNIX_EPOCH;
+
+        let config_path = source.join("_config.yml");
+        let config = if config_path.is_file() {
+            shared_modified = shared_modified.max(modified(&config_path)?);
+            serde_yaml::from_str(&fs::read_to_string(&config_path)?)?
+        } else {
+            Object::new()
+        };
+
+        let mut includes = InMemorySource::new();
+        for path in list_files(&source.join("_includes"))? {
+            shared_modified = shared_modified.max(modified(&path)?);
+            includes.add(file_name(&path), fs::read_to_string(&path)?);
+        }
+        let parser = ParserBuilder::ssg()
+            .partials(LazyCompiler::new(includes))
+            .build()?;
+
+        let mut layouts = HashMap::new();
+        for path in list_files(&source.join("_layouts"))? {
+            shared_modified = shared_modified.max(modified(&path)?);
+            let text = fs::read_to_string(&path)?;
+            let (front_matter, body) = split_front_matter(&text)?;
+            let template = parse(&parser, &path, body)?;
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            layouts.insert(
+                name,
+                Layout {
+                    front_matter,
+                    template,
+                },
+            );
+        }
+
+        let mut posts = Vec::new();
+        let mut post_paths = list_files(&source.join("_posts"))?;
+        post_paths.sort_unstable_by(|a, b| b.cmp(a));
+        for path in post_paths {
+            shared_modified = shared_modified.max(modified(&path)?);
+            let output = Path::new("posts").join(file_name(&path));
+            let text = fs::read_to_string(&path)?;
+            posts.push(load_document(&parser, &path, output, &text)?);
+        }
+
+        let mut pages = Vec::new();
+        let mut files = Vec::new();
+        for path in walk(source)? {
+            let output = path.strip_prefix(source)?.to_owned();
+            let bytes = fs::read(&path)?;
+            match String::from_utf8(bytes) {
+                Ok(ref text) if text.starts_with("---") => {
+                    pages.push(load_document(&parser, &path, output, text)?);
+                }
+                _ => files.push(StaticFile {
+                    modified: modified(&path)?,
+                    source: path,
+                    output,
+                }),
+            }
+        }
+
+        Ok(Self {
+            config,
+            layouts,
+            pages,
+            posts,
+            files,
+            shared_modified,
+        })
+    }
+
+    /// Write the site into `dest`.
+    ///
+    /// When `incremental`, outputs newer than their source, and than the config, layouts,
+    /// includes and posts, are left alone.
+    pub fn build<P: AsRef<Path>>(&self, dest: P, incremental: bool) -> Result<BuildReport> {
+        let dest = dest.as_ref();
+        let mut report = BuildReport::default();
+
+        let mut site = self.config.clone();
+        let posts: Vec<_> = self
+            .posts
+            .iter()
+            .map(|post| post.page.clone().into_value())
+            .collect();
+        site.insert("posts".into(), posts.into_value());
+
+        for document in self.posts.iter().chain(self.pages.iter()) {
+            let output = dest.join(&document.output);
+            if incremental && is_fresh(&output, document.modified.max(self.shared_modified)) {
+                report.skipped.push(document.output.clone());
+                continue;
+            }
+            let rendered = self.render(&site, document)?;
+            write(&output, rendered.as_bytes())?;
+            report.written.push(document.output.clone());
+        }
+
+        for file in self.files.iter() {
+            let output = dest.join(&file.output);
+            if incremental && is_fresh(&output, file.modified) {
+                report.skipped.push(file.output.clone());
+                continue;
+            }
+            write(&output, &fs::read(&file.source)?)?;
+            report.written.push(file.output.clone());
+        }
+
+        Ok(report)
+    }
+
+    /// Render `document` and wrap it in its layouts.
+    fn render(&self, site: &Object, document: &Document) -> Result<String> {
+        let mut globals = Object::new();
+        globals.insert("site".into(), site.clone().into_value());
+        globals.insert("page".into(), document.page.clone().into_value());
+        let mut content = document.template.render(&globals)?;
+
+        let mut layout_name = layout_of(&document.page);
+        for _ in 0..MAX_LAYOUT_DEPTH {
+            let name = match layout_name {
+                Some(name) => name,
+                None => return Ok(content),
+            };
+            let layout = self.layouts.get(&name).ok_or_else(|| {
+                liquid::Error::with_msg("Unknown layout")
+                    .context("layout", name.clone())
+                    .context("page", document.output.to_string_lossy().into_owned())
+            })?;
+            globals.insert("layout".into(), layout.front_matter.clone().into_value());
+            globals.insert("content".into(), content.into_value());
+            content = layout.template.render(&globals)?;
+            layout_name = layout_of(&layout.front_matter);
+        }
+        Err(liquid::Error::with_msg("Layouts are nested too deep")
+            .context("page", document.output.to_string_lossy().into_owned())
+            .into())
+    }
+}
+
+fn load_document(parser: &Parser, path: &Path, output: PathBuf, text: &str) -> Result<Document> {
+    let (mut page, body) = split_front_matter(text)?;
+    let url = format!("/{}", output.to_string_lossy().replace('\\', "/"));
+    page.insert("url".into(), url.into_value());
+    Ok(Document {
+        template: parse(parser, path, body)?,
+        page,
+        output,
+        modified: modified(path)?,
+    })
+}
+
+/// Split YAML front matter between leading `---` lines from the body.
+fn split_front_matter(text: &str) -> Result<(Object, &str)> {
+    if !text.starts_with("---") {
+        return Ok((Object::new(), text));
+    }
+    let rest = text[3..].trim_start_matches('\r').trim_start_matches('\n');
+    let end = rest
+        .find("\n---")
+        .ok_or_else(|| liquid::Error::with_msg("Unclosed front matter"))?;
+    let yaml = &rest[..end];
+    let body = rest[end + 4..].trim_start_matches('\r');
+    let body = if body.starts_with('\n') {
+        &body[1..]
+    } else {
+        body
+    };
+    let front_matter = if yaml.trim().is_empty() {
+        Object::new()
+    } else {
+        serde_yaml::from_str(yaml)?
+    };
+    Ok((front_matter, body))
+}
+
+fn parse(parser: &Parser, path: &Path, body: &str) -> Result<Template> {
+    parser.parse(body).map_err(|e| {
+        e.context("path", path.to_string_lossy().into_owned())
+            .into()
+    })
+}
+
+fn layout_of(front_matter: &Object) -> Option<String> {
+    front_matter
+        .get("layout")
+        .filter(|layout| !layout.is_nil())
+        .map(|layout| layout.to_kstr().to_string())
+}
+
+fn is_fresh(output: &Path, source_modified: SystemTime) -> bool {
+    fs::metadata(output)
+        .and_then(|metadata| metadata.modified())
+        .map(|output_modified| source_modified <= output_modified)
+        .unwrap_or(false)
+}
+
+fn write(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+fn modified(path: &Path) -> Result<SystemTime> {
+    Ok(fs::metadata(path)?.modified()?)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Files directly in `dir`, if it exists.
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort_unstable();
+    Ok(files)
+}
+
+/// Files under `dir`, skipping files and directories named starting with `_` or `.`.
+fn walk(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = file_name(&path);
+        if name.starts_with('_') || name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            files.extend(walk(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort_unstable();
+    Ok(files)
+}
@@ -0,0 +1,37 @@
+//! Build a site, like `liquid-ssg my-site my-site/_site`.
+//!
+//! Pass `--incremental` to leave up-to-date outputs alone.
+
+use std::env;
+use std::process;
+
+fn main() {
+    let mut args: Vec<_> = env::args().skip(1).collect();
+    let incremental = match args.iter().position(|arg| arg == "--incremental") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let (source, dest) = match args.as_slice() {
+        [source, dest] => (source, dest),
+        _ => {
+            eprintln!("Usage: liquid-ssg [--incremental] <SOURCE> <DEST>");
+            process::exit(2);
+        }
+    };
+
+    let report = liquid_ssg::Site::load(source).and_then(|site| site.build(dest, incremental));
+    match report {
+        Ok(report) => println!(
+            "{} written, {} up to date",
+            report.written.len(),
+            report.skipped.len()
+        ),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
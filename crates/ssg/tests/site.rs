@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+fn write(root: &Path, path: &str, contents: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+fn paths(paths: &[&str]) -> Vec<PathBuf> {
+    paths.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn builds_site() {
+    let root = std::env::temp_dir().join(format!("liquid-ssg-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let source = root.join("site");
+    let dest = root.join("out");
+
+    write(&source, "_config.yml", "title: Notes\n");
+    write(
+        &source,
+        "_layouts/default.html",
+        "<title>{{ site.title }}</title>{% include nav.html %}{{ content }}",
+    );
+    write(
+        &source,
+        "_layouts/post.html",
+        "---\nlayout: default\n---\n<article>{{ content }}</article>",
+    );
+    write(&source, "_includes/nav.html", "<nav>Home</nav>");
+    write(
+        &source,
+        "_posts/2020-01-01-first.html",
+        "---\nlayout: post\ntitle: First post\n---\n{{ page.title | slugify }}",
+    );
+    write(
+        &source,
+        "_posts/2020-02-01-second.html",
+        "---\nlayout: post\ntitle: Second post\n---\n{{ page.title }}",
+    );
+    write(
+        &source,
+        "index.html",
+        "---\nlayout: default\n---\n{% for post in site.posts %}<a href=\"{{ post.url }}\">{{ post.title }}</a>{% endfor %}",
+    );
+    write(&source, "robots.txt", "User-agent: *");
+
+    let site = liquid_ssg::Site::load(&source).unwrap();
+    let report = site.build(&dest, true).unwrap();
+    assert_eq!(
+        report.written,
+        paths(&[
+            "posts/2020-02-01-second.html",
+            "posts/2020-01-01-first.html",
+            "index.html",
+            "robots.txt",
+        ])
+    );
+    assert_eq!(
+        fs::read_to_string(dest.join("index.html")).unwrap(),
+        "<title>Notes</title><nav>Home</nav>\
+         <a href=\"/posts/2020-02-01-second.html\">Second post</a>\
+         <a href=\"/posts/2020-01-01-first.html\">First post</a>"
+    );
+    assert_eq!(
+        fs::read_to_string(dest.join("posts/2020-01-01-first.html")).unwrap(),
+        "<title>Notes</title><nav>Home</nav><article>first-post</article>"
+    );
+    assert_eq!(
+        fs::read_to_string(dest.join("robots.txt")).unwrap(),
+        "User-agent: *"
+    );
+
+    // Outputs newer than their sources are left alone.
+    let report = site.build(&dest, true).unwrap();
+    assert!(report.written.is_empty());
+    assert_eq!(report.skipped.len(), 4);
+
+    // An output older than its source is rebuilt.
+    fs::OpenOptions::new()
+        .write(true)
+        .open(dest.join("index.html"))
+        .unwrap()
+        .set_modified(SystemTime::UNIX_EPOCH)
+        .unwrap();
+    let report = site.build(&dest, true).unwrap();
+    assert_eq!(report.written, paths(&["index.html"]));
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,94 @@
+//! Opt-in (de)serialization of byte buffers as bytes scalars.
+//!
+//! Serde serializes `Vec<u8>` as a sequence, which becomes an array of integers.  Fields marked
+//! with `#[serde(with = "liquid_value::bytes")]` become a bytes scalar instead, for filters like
+//! `base64_encode`.  Other formats see them as bytes, like with `serde_bytes`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use liquid_value::ValueView;
+//!
+//! #[derive(serde::Serialize)]
+//! struct Signed {
+//!     #[serde(with = "liquid_value::bytes")]
+//!     signature: Vec<u8>,
+//!     checksum: Vec<u8>,
+//! }
+//!
+//! let signed = Signed {
+//!     signature: vec![0xca, 0xfe],
+//!     checksum: vec![1, 2],
+//! };
+//! let value = liquid_value::to_value(&signed).unwrap();
+//! let object = value.as_object().unwrap();
+//! let signature = object.get("signature").unwrap().as_scalar().unwrap();
+//! assert_eq!(signature.as_bytes(), Some(&[0xca, 0xfe][..]));
+//! assert_eq!(object.get("checksum").unwrap().as_array().unwrap().size(), 2);
+//! ```
+
+use std::fmt;
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserializer, Serialize, Serializer};
+
+/// Name of the newtype struct that `to_value` turns into a bytes scalar.
+pub(crate) const BYTES_TOKEN: &str = "$liquid_value::Bytes";
+
+struct RawBytes<'b>(&'b [u8]);
+
+impl<'b> Serialize for RawBytes<'b> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Serialize `bytes` as a bytes scalar.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + AsRef<[u8]>,
+    S: Serializer,
+{
+    serializer.serialize_newtype_struct(BYTES_TOKEN, &RawBytes(bytes.as_ref()))
+}
+
+/// Deserialize a bytes scalar, or a sequence of integers, into a byte buffer.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: From<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_bytes(BytesVisitor).map(T::from)
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Vec<u8>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,135 @@
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+use kstring::KStringCow;
+
+use crate::{DisplayCow, Scalar, ScalarCow, State, Value, ValueView};
+
+/// A domain type embedded in a `Scalar`, like a currency amount or a SKU.
+///
+/// Filters that know the type get it back with `ScalarCow::downcast_ref`, so it doesn't need to
+/// be converted to a string when building the globals.
+///
+/// # Comparisons
+///
+/// When either side of `==` is a custom scalar, the first answer is taken from:
+/// 1. the left side's `eq_scalar`, if it is custom,
+/// 2. the right side's `eq_scalar`, if it is custom,
+/// 3. Ruby truthiness, when the other side is a bool,
+/// 4. comparing both as text, with `to_kstr`.
+///
+/// Orderings, like for `<`, try the left side's `cmp_scalar` then the right side's, reversed,
+/// and are otherwise unordered.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::any::Any;
+///
+/// use kstring::KStringCow;
+/// use liquid_value::{Scalar, ScalarValue, ValueView};
+///
+/// #[derive(Debug)]
+/// struct Money {
+///     cents: i64,
+/// }
+///
+/// impl ScalarValue for Money {
+///     fn type_name(&self) -> &'static str {
+///         "money"
+///     }
+///
+///     fn to_kstr(&self) -> KStringCow<'_> {
+///         format!("${}.{:02}", self.cents / 100, self.cents % 100).into()
+///     }
+///
+///     fn as_any(&self) -> &dyn Any {
+///         self
+///     }
+/// }
+///
+/// let price = Scalar::custom(Money { cents: 1250 });
+/// assert_eq!(price.render().to_string(), "$12.50");
+/// assert_eq!(price.downcast_ref::<Money>().unwrap().cents, 1250);
+/// ```
+pub trait ScalarValue: fmt::Debug + Send + Sync + 'static {
+    /// Name of the type, see `ValueView::type_name`.
+    fn type_name(&self) -> &'static str;
+
+    /// Text of the value, used when it is rendered or treated as a string.
+    fn to_kstr(&self) -> KStringCow<'_>;
+
+    /// Render for the user, like by `{{ }}`.
+    ///
+    /// Defaults to `to_kstr`.
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(self.to_kstr()))
+    }
+
+    /// Render as source code, like for debugging.
+    ///
+    /// Defaults to `to_kstr`, quoted.
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(format!(r#""{}""#, self.to_kstr())))
+    }
+
+    /// Whether the value equals `other`, like for `==` and `contains`, or `None` to leave it to
+    /// the fallbacks, see "Comparisons".
+    ///
+    /// Defaults to `None`.
+    fn eq_scalar(&self, _other: &ScalarCow<'_>) -> Option<bool> {
+        None
+    }
+
+    /// How the value orders against `other`, like for `<` and `sort`, or `None` to leave it to
+    /// the fallbacks, see "Comparisons".
+    ///
+    /// Defaults to `None`.
+    fn cmp_scalar(&self, _other: &ScalarCow<'_>) -> Option<Ordering> {
+        None
+    }
+
+    /// Access the concrete type, see `ScalarCow::downcast_ref`.
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// A `ScalarValue` shared by the scalars holding it.
+#[derive(Clone, Debug)]
+pub(crate) struct CustomScalar(pub(crate) Arc<dyn ScalarValue>);
+
+impl ValueView for CustomScalar {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        self.0.render()
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        self.0.source()
+    }
+    fn type_name(&self) -> &'static str {
+        self.0.type_name()
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue | State::Empty | State::Blank => {
+                self.0.to_kstr().as_str().is_empty()
+            }
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.0.to_kstr()
+    }
+    fn to_value(&self) -> Value {
+        Value::Scalar(Scalar::from_custom(self.clone()))
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::from_custom(self.clone()))
+    }
+}
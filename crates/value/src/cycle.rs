@@ -0,0 +1,261 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use kstring::KStringCow;
+use liquid_error::{Error, Result};
+
+use crate::ArrayView;
+use crate::DisplayCow;
+use crate::ObjectView;
+use crate::ScalarCow;
+use crate::State;
+use crate::Value;
+use crate::ValueView;
+
+/// Displayed in place of a shared value that contains itself.
+pub const CYCLE_PLACEHOLDER: &str = "[circular]";
+
+/// How to handle shared values that contain themselves.
+///
+/// Cycles can only be formed through `Arc` and `Rc`, which are tracked while they are being
+/// displayed or converted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CyclePolicy {
+    /// Fail with an error.
+    Error,
+    /// Replace the repeated value with `CYCLE_PLACEHOLDER`.
+    Placeholder,
+}
+
+#[derive(Default)]
+struct Visits {
+    addresses: Vec<usize>,
+    found: bool,
+}
+
+thread_local! {
+    static VISITS: RefCell<Visits> = RefCell::new(Visits::default());
+}
+
+/// Run `f`, applying `policy` to any cycles found while it displays or converts values.
+pub fn guard_cycles<R, F>(policy: CyclePolicy, f: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R>,
+{
+    let outer = VISITS.with(|visits| std::mem::replace(&mut visits.borrow_mut().found, false));
+    let result = f();
+    let found = VISITS.with(|visits| {
+        let mut visits = visits.borrow_mut();
+        let found = visits.found;
+        visits.found = outer || found;
+        found
+    });
+    match (found, policy) {
+        (true, CyclePolicy::Error) => result.and_then(|_| {
+            Err(Error::with_msg("Cyclic data").context("cause", "A shared value contains itself"))
+        }),
+        _ => result,
+    }
+}
+
+struct Visit;
+
+impl Visit {
+    /// Mark `address` as being visited, unless it already is.
+    fn enter(address: usize) -> Option<Self> {
+        VISITS.with(|visits| {
+            let mut visits = visits.borrow_mut();
+            if visits.addresses.contains(&address) {
+                visits.found = true;
+                None
+            } else {
+                visits.addresses.push(address);
+                Some(Visit)
+            }
+        })
+    }
+}
+
+impl Drop for Visit {
+    fn drop(&mut self) {
+        VISITS.with(|visits| {
+            visits.borrow_mut().addresses.pop();
+        });
+    }
+}
+
+struct SharedDisplay<'s, T: ValueView + ?Sized> {
+    s: &'s T,
+    source: bool,
+}
+
+impl<'s, T: ValueView + ?Sized> fmt::Display for SharedDisplay<'s, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Visit::enter(address(self.s)) {
+            Some(_visit) if self.source => write!(f, "{}", self.s.source()),
+            Some(_visit) => write!(f, "{}", self.s.render()),
+            None => write!(f, "{}", CYCLE_PLACEHOLDER),
+        }
+    }
+}
+
+fn address<T: ?Sized>(value: &T) -> usize {
+    value as *const T as *const () as usize
+}
+
+macro_rules! shared_value_view {
+    ($shared:ident) => {
+        impl<T: ValueView + ?Sized> ValueView for $shared<T> {
+            fn as_debug(&self) -> &dyn fmt::Debug {
+                self
+            }
+
+            fn render(&self) -> DisplayCow<'_> {
+                DisplayCow::Owned(Box::new(SharedDisplay {
+                    s: self.as_ref(),
+                    source: false,
+                }))
+            }
+            fn source(&self) -> DisplayCow<'_> {
+                DisplayCow::Owned(Box::new(SharedDisplay {
+                    s: self.as_ref(),
+                    source: true,
+                }))
+            }
+            fn type_name(&self) -> &'static str {
+                self.as_ref().type_name()
+            }
+            fn query_state(&self, state: State) -> bool {
+                self.as_ref().query_state(state)
+            }
+
+            fn to_kstr(&self) -> KStringCow<'_> {
+                match Visit::enter(address(self.as_ref())) {
+                    Some(_visit) => self.as_ref().to_kstr(),
+                    None => KStringCow::from_static(CYCLE_PLACEHOLDER),
+                }
+            }
+            fn to_value(&self) -> Value {
+                match Visit::enter(address(self.as_ref())) {
+                    Some(_visit) => self.as_ref().to_value(),
+                    None => Value::scalar(CYCLE_PLACEHOLDER),
+                }
+            }
+
+            fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+                self.as_ref().as_scalar()
+            }
+
+            fn as_array(&self) -> Option<&dyn ArrayView> {
+                self.as_ref().as_array()
+            }
+
+            fn as_object(&self) -> Option<&dyn ObjectView> {
+                self.as_ref().as_object()
+            }
+
+            fn as_state(&self) -> Option<State> {
+                self.as_ref().as_state()
+            }
+
+            fn is_nil(&self) -> bool {
+                self.as_ref().is_nil()
+            }
+        }
+    };
+}
+
+shared_value_view!(Arc);
+shared_value_view!(Rc);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Node {
+        name: &'static str,
+        next: RefCell<Option<Rc<Node>>>,
+    }
+
+    struct NodeRender<'s> {
+        s: &'s Node,
+    }
+
+    impl<'s> fmt::Display for NodeRender<'s> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.s.name)?;
+            if let Some(ref next) = *self.s.next.borrow() {
+                write!(f, "{}", next.render())?;
+            }
+            Ok(())
+        }
+    }
+
+    impl ValueView for Node {
+        fn as_debug(&self) -> &dyn fmt::Debug {
+            self
+        }
+
+        fn render(&self) -> DisplayCow<'_> {
+            DisplayCow::Owned(Box::new(NodeRender { s: self }))
+        }
+        fn source(&self) -> DisplayCow<'_> {
+            DisplayCow::Owned(Box::new(NodeRender { s: self }))
+        }
+        fn type_name(&self) -> &'static str {
+            "node"
+        }
+        fn query_state(&self, _state: State) -> bool {
+            false
+        }
+
+        fn to_kstr(&self) -> KStringCow<'_> {
+            KStringCow::from_string(NodeRender { s: self }.to_string())
+        }
+        fn to_value(&self) -> Value {
+            let next = self.next.borrow().as_ref().map(|next| next.to_value());
+            Value::Array(vec![Value::scalar(self.name), next.unwrap_or(Value::Nil)])
+        }
+    }
+
+    fn cycle() -> Rc<Node> {
+        let b = Rc::new(Node {
+            name: "b",
+            next: RefCell::new(None),
+        });
+        let a = Rc::new(Node {
+            name: "a",
+            next: RefCell::new(Some(b.clone())),
+        });
+        *b.next.borrow_mut() = Some(a.clone());
+        a
+    }
+
+    #[test]
+    fn test_cycle_placeholder() {
+        let a = cycle();
+        let rendered = guard_cycles(CyclePolicy::Placeholder, || Ok(a.render().to_string()));
+        assert_eq!(rendered.unwrap(), "ab[circular]");
+        assert_eq!(a.to_kstr(), "ab[circular]");
+        *a.next.borrow_mut() = None;
+    }
+
+    #[test]
+    fn test_cycle_error() {
+        let a = cycle();
+        assert!(guard_cycles(CyclePolicy::Error, || Ok(a.to_value())).is_err());
+        assert!(guard_cycles(CyclePolicy::Error, || Ok(a.render().to_string())).is_err());
+        *a.next.borrow_mut() = None;
+    }
+
+    #[test]
+    fn test_shared_without_cycle() {
+        let shared = Arc::new(Value::scalar(1));
+        let values = vec![shared.clone(), shared];
+        let rendered = guard_cycles(CyclePolicy::Error, || Ok(values.render().to_string()));
+        assert_eq!(rendered.unwrap(), "11");
+    }
+}
@@ -26,8 +26,52 @@ impl DateTime {
     }
 
     /// Convert a `str` to `Self`
+    ///
+    /// Accepts `now`, `today`, RFC 3339, RFC 2822 and the formats in `DATE_TIME_FORMATS`,
+    /// optionally followed by ` UTC`.  The offset of the string is kept, see `offset`.
     pub fn from_str(other: &str) -> Option<Self> {
-        parse_date_time(other).map(|d| Self { inner: d })
+        parse_date_time(other, DATE_TIME_FORMATS).map(Self::with_chrono)
+    }
+
+    /// Convert a `str` to `Self`, trying `formats` instead of `DATE_TIME_FORMATS`.
+    ///
+    /// To extend the default formats:
+    /// ```rust
+    /// let formats = [liquid_value::DATE_TIME_FORMATS, &["%d.%m.%Y"]].concat();
+    /// let date = liquid_value::DateTime::from_str_with_formats("13.06.2016", &formats);
+    /// assert!(date.is_some());
+    /// ```
+    pub fn from_str_with_formats(other: &str, formats: &[&str]) -> Option<Self> {
+        parse_date_time(other, formats).map(Self::with_chrono)
+    }
+
+    /// Create a `DateTime` in UTC from seconds since the Unix epoch.
+    pub fn from_timestamp(secs: i64) -> Option<Self> {
+        chrono::Utc
+            .timestamp_opt(secs, 0)
+            .single()
+            .map(|d| Self::with_chrono(d.with_timezone(&utc_offset())))
+    }
+
+    /// Create a `DateTime` in UTC from milliseconds since the Unix epoch.
+    pub fn from_timestamp_millis(millis: i64) -> Option<Self> {
+        let secs = millis.div_euclid(1000);
+        let nanos = (millis.rem_euclid(1000) * 1_000_000) as u32;
+        chrono::Utc
+            .timestamp_opt(secs, nanos)
+            .single()
+            .map(|d| Self::with_chrono(d.with_timezone(&utc_offset())))
+    }
+
+    /// Create a `DateTime` in UTC from a Unix timestamp in either seconds or milliseconds.
+    ///
+    /// Timestamps with a magnitude of at least `100_000_000_000` are treated as milliseconds; as
+    /// seconds, they would be over 3000 years away.
+    pub fn from_timestamp_auto(timestamp: i64) -> Option<Self> {
+        match timestamp.checked_abs() {
+            Some(secs) if secs < MILLIS_THRESHOLD => Self::from_timestamp(timestamp),
+            _ => Self::from_timestamp_millis(timestamp),
+        }
     }
 
     /// Replace fields with `other`.
@@ -45,6 +89,54 @@ impl DateTime {
         Self::with_chrono(self.inner.with_timezone(tz))
     }
 
+    /// The offset from UTC the date time is shown in.
+    pub fn offset(&self) -> FixedOffset {
+        *self.inner.offset()
+    }
+
+    /// Adds `duration`, returning `None` on overflow.
+    pub fn checked_add_signed(self, duration: chrono::Duration) -> Option<Self> {
+        self.inner
+            .checked_add_signed(duration)
+            .map(Self::with_chrono)
+    }
+
+    /// Adds calendar months, returning `None` on overflow.
+    ///
+    /// The day is clamped to the last day of the resulting month.
+    pub fn checked_add_months(self, months: i32) -> Option<Self> {
+        use chrono::Datelike;
+        let total = i64::from(self.inner.year()) * 12 + i64::from(self.inner.month0());
+        let total = total.checked_add(i64::from(months))?;
+        let year = total.div_euclid(12) as i32;
+        let month0 = total.rem_euclid(12) as u32;
+        let day = std::cmp::min(self.inner.day(), days_in_month(year, month0 + 1)?);
+        self.inner
+            .with_day(1)?
+            .with_year(year)?
+            .with_month0(month0)?
+            .with_day(day)
+            .map(Self::with_chrono)
+    }
+
+    /// The time elapsed since `other`, negative if `other` is later.
+    pub fn signed_duration_since(self, other: Self) -> chrono::Duration {
+        self.inner.signed_duration_since(other.inner)
+    }
+
+    /// Looks up a component by name, like `year`, `hour` or `iso_week`.
+    ///
+    /// See `DATE_TIME_COMPONENTS` for the available names.
+    pub fn component(&self, name: &str) -> Option<i32> {
+        use chrono::Timelike;
+        match name {
+            "hour" => Some(self.inner.hour() as i32),
+            "minute" => Some(self.inner.minute() as i32),
+            "second" => Some(self.inner.second() as i32),
+            _ => date_component(&self.inner, name),
+        }
+    }
+
     /// Retrieves a date component.
     pub fn date(&self) -> Date {
         Date::with_chrono(self.inner.naive_utc().date())
@@ -56,6 +148,16 @@ impl DateTime {
         self.inner.format(fmt)
     }
 
+    /// Convert from a `chrono::DateTime`.
+    pub fn from_chrono(date: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        Self::with_chrono(date)
+    }
+
+    /// Convert to a `chrono::DateTime`.
+    pub fn to_chrono(self) -> chrono::DateTime<chrono::FixedOffset> {
+        self.inner
+    }
+
     fn with_chrono(inner: DateTimeImpl) -> Self {
         Self { inner }
     }
@@ -191,6 +293,70 @@ impl chrono::Timelike for DateTime {
 
 const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
 
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Formats tried, in order, when converting a string to a `DateTime`.
+///
+/// Formats without an offset are interpreted as UTC and formats without a time as midnight.
+/// Month names are matched in English, full or abbreviated.
+pub const DATE_TIME_FORMATS: &[&str] = &[
+    "%d %B %Y %H:%M:%S %z",
+    "%Y-%m-%d %H:%M:%S %z",
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%d %B %Y %H:%M:%S",
+    "%d %B %Y %H:%M",
+    "%B %d, %Y %H:%M:%S",
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%d %B %Y",
+    "%B %d, %Y",
+    "%B %d %Y",
+];
+
+/// Formats tried, in order, when filters convert a string to a `DateTime`.
+///
+/// Set as a register of the runtime to let templates read the application's formats, see
+/// `ScalarCow::to_date_time_with`.
+///
+/// ```rust
+/// let formats = liquid_value::DateFormats::new().format("%d.%m.%Y");
+/// assert!(formats.parse("13.06.2016").is_some());
+/// assert!(formats.parse("2016-06-13").is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DateFormats {
+    formats: Vec<String>,
+}
+
+impl DateFormats {
+    /// Create with `DATE_TIME_FORMATS`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also try `format`, after the formats already present.
+    pub fn format<S: Into<String>>(mut self, format: S) -> Self {
+        self.formats.push(format.into());
+        self
+    }
+
+    /// Convert a `str` to a `DateTime`, like `DateTime::from_str` but trying these formats.
+    pub fn parse(&self, other: &str) -> Option<DateTime> {
+        parse_date_time(other, &self.formats).map(DateTime::with_chrono)
+    }
+}
+
+impl Default for DateFormats {
+    fn default() -> Self {
+        Self {
+            formats: DATE_TIME_FORMATS.iter().map(|f| (*f).to_owned()).collect(),
+        }
+    }
+}
+
 mod friendly_date_time {
     use super::*;
     use serde::{self, Deserialize, Deserializer, Serializer};
@@ -212,9 +378,11 @@ mod friendly_date_time {
     }
 }
 
-fn parse_date_time(s: &str) -> Option<DateTimeImpl> {
+fn parse_date_time<F: AsRef<str>>(s: &str, formats: &[F]) -> Option<DateTimeImpl> {
+    let s = s.trim();
     match s {
-        "now" => {
+        "" => None,
+        "now" | "today" => {
             use chrono::offset;
             let now = offset::Utc::now();
             let now = now.naive_utc();
@@ -222,15 +390,122 @@ fn parse_date_time(s: &str) -> Option<DateTimeImpl> {
             Some(now)
         }
         _ => {
-            let formats = ["%d %B %Y %H:%M:%S %z", "%Y-%m-%d %H:%M:%S %z"];
-            formats
-                .iter()
-                .filter_map(|f| DateTimeImpl::parse_from_str(s, f).ok())
-                .next()
+            let parse_with_formats = |s: &str| {
+                formats
+                    .iter()
+                    .filter_map(|f| parse_with_format(s, f.as_ref()))
+                    .next()
+            };
+            DateTimeImpl::parse_from_rfc3339(s)
+                .or_else(|_| DateTimeImpl::parse_from_rfc2822(s))
+                .ok()
+                .or_else(|| parse_with_formats(s))
+                .or_else(|| {
+                    // Ruby's `Time#to_s` in UTC, like `2016-06-13 02:30:00 UTC`.
+                    let local = s.trim_end_matches(" UTC");
+                    if local.len() < s.len() {
+                        parse_with_formats(local)
+                    } else {
+                        None
+                    }
+                })
         }
     }
 }
 
+fn parse_with_format(s: &str, format: &str) -> Option<DateTimeImpl> {
+    let utc = utc_offset();
+    DateTimeImpl::parse_from_str(s, format)
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(s, format)
+                .ok()
+                .map(|d| utc.from_utc_datetime(&d))
+        })
+        .or_else(|| {
+            DateImpl::parse_from_str(s, format)
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| utc.from_utc_datetime(&d))
+        })
+}
+
+/// Components available on a `Date`, see `Date::component`.
+///
+/// - `wday` is the day of the week, from Sunday as `0`.
+/// - `yday` is the day of the year, from `1`.
+/// - `iso_week` and `iso_year` are the ISO 8601 week number and its week-based year.
+pub const DATE_COMPONENTS: &[&str] = &[
+    "year", "quarter", "month", "day", "wday", "yday", "iso_week", "iso_year",
+];
+
+/// Components available on a `DateTime`, see `DateTime::component`.
+///
+/// Includes `DATE_COMPONENTS`, in the `DateTime`'s own offset.
+pub const DATE_TIME_COMPONENTS: &[&str] = &[
+    "year", "quarter", "month", "day", "wday", "yday", "iso_week", "iso_year", "hour", "minute",
+    "second",
+];
+
+fn date_component<D: chrono::Datelike>(date: &D, name: &str) -> Option<i32> {
+    let component = match name {
+        "year" => date.year(),
+        "quarter" => (date.month0() / 3 + 1) as i32,
+        "month" => date.month() as i32,
+        "day" => date.day() as i32,
+        "wday" => date.weekday().num_days_from_sunday() as i32,
+        "yday" => date.ordinal() as i32,
+        "iso_week" => date.iso_week().week() as i32,
+        "iso_year" => date.iso_week().year(),
+        _ => return None,
+    };
+    Some(component)
+}
+
+fn days_in_month(year: i32, month: u32) -> Option<u32> {
+    use chrono::Datelike;
+    let next = if month == 12 {
+        DateImpl::from_ymd_opt(year.checked_add(1)?, 1, 1)?
+    } else {
+        DateImpl::from_ymd_opt(year, month + 1, 1)?
+    };
+    next.pred_opt().map(|d| d.day())
+}
+
+/// Parse an offset from UTC, like `+05:30`, `-0800`, `+09`, `Z` or `UTC`.
+///
+/// Named time zones, with daylight saving time, need `chrono-tz` or similar.
+pub fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let s = s.trim();
+    if let "Z" | "z" | "UTC" | "GMT" = s {
+        return Some(utc_offset());
+    }
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    if !rest.bytes().all(|b| b.is_ascii_digit() || b == b':') {
+        return None;
+    }
+    let (hours, minutes) = match rest.len() {
+        2 => (rest, "00"),
+        4 => (&rest[..2], &rest[2..]),
+        5 if &rest[2..3] == ":" => (&rest[..2], &rest[3..]),
+        _ => return None,
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if 60 <= minutes {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn utc_offset() -> chrono::FixedOffset {
+    chrono::Offset::fix(&chrono::Utc)
+}
+
 /// Liquid's native date only type.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
@@ -251,9 +526,29 @@ impl Date {
         Self::with_chrono(DateImpl::from_ymd(year, month, day))
     }
 
+    /// Looks up a component by name, like `year` or `iso_week`.
+    ///
+    /// See `DATE_COMPONENTS` for the available names.
+    pub fn component(&self, name: &str) -> Option<i32> {
+        date_component(&self.inner, name)
+    }
+
     /// Convert a `str` to `Self`
+    ///
+    /// Accepts `today` and the formats in `DATE_FORMATS`.
     pub fn from_str(other: &str) -> Option<Self> {
-        parse_date(other).map(|d| Self { inner: d })
+        parse_date(other, DATE_FORMATS).map(|d| Self { inner: d })
+    }
+
+    /// Convert a `str` to `Self`, trying `formats` instead of `DATE_FORMATS`.
+    pub fn from_str_with_formats(other: &str, formats: &[&str]) -> Option<Self> {
+        parse_date(other, formats).map(|d| Self { inner: d })
+    }
+
+    /// Formats the date with the specified format string. See the
+    /// chrono::format::strftime module on the supported escape sequences.
+    pub fn format<'a>(&self, fmt: &'a str) -> impl fmt::Display + 'a {
+        self.inner.format(fmt)
     }
 
     fn with_chrono(inner: DateImpl) -> Self {
@@ -343,6 +638,9 @@ impl chrono::Datelike for Date {
 
 const DATE_FORMAT: &str = "%Y-%m-%d";
 
+/// Formats tried, in order, when converting a string to a `Date`.
+pub const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%d %B %Y", "%B %d, %Y", "%B %d %Y"];
+
 mod friendly_date {
     use super::*;
     use serde::{self, Deserialize, Deserializer, Serializer};
@@ -364,19 +662,17 @@ mod friendly_date {
     }
 }
 
-fn parse_date(s: &str) -> Option<DateImpl> {
+fn parse_date(s: &str, formats: &[&str]) -> Option<DateImpl> {
+    let s = s.trim();
     match s {
         "today" => {
             use chrono::offset::Utc;
             Some(Utc::today().naive_utc())
         }
-        _ => {
-            let formats = ["%d %B %Y", "%Y-%m-%d"];
-            formats
-                .iter()
-                .filter_map(|f| DateImpl::parse_from_str(s, f).ok())
-                .next()
-        }
+        _ => formats
+            .iter()
+            .filter_map(|f| DateImpl::parse_from_str(s, f).ok())
+            .next(),
     }
 }
 
@@ -385,23 +681,143 @@ mod test {
     use super::*;
     #[test]
     fn parse_date_time_empty_is_bad() {
-        assert!(parse_date_time("").is_none());
-        assert!(parse_date("").is_none());
+        assert!(parse_date_time("", DATE_TIME_FORMATS).is_none());
+        assert!(parse_date("", DATE_FORMATS).is_none());
     }
 
     #[test]
     fn parse_date_time_bad() {
-        assert!(parse_date_time("aaaaa").is_none());
-        assert!(parse_date("aaaaa").is_none());
+        assert!(parse_date_time("aaaaa", DATE_TIME_FORMATS).is_none());
+        assert!(parse_date("aaaaa", DATE_FORMATS).is_none());
     }
 
     #[test]
     fn parse_date_time_now() {
-        assert!(parse_date_time("now").is_some());
+        assert!(parse_date_time("now", DATE_TIME_FORMATS).is_some());
     }
 
     #[test]
     fn parse_date_today() {
-        assert!(parse_date("today").is_some());
+        assert!(parse_date("today", DATE_FORMATS).is_some());
+    }
+
+    #[test]
+    fn parse_date_time_formats() {
+        let expected = DateTime::from_str("2016-06-13 02:30:00 +0000").unwrap();
+        let inputs = [
+            "2016-06-13T02:30:00Z",
+            "2016-06-13T02:30:00+00:00",
+            "2016-06-13T05:30:00.000+0300",
+            "Mon, 13 Jun 2016 02:30:00 +0000",
+            "13 Jun 2016 02:30:00",
+            "June 13, 2016 02:30:00",
+            "2016-06-13 02:30",
+        ];
+        for input in inputs.iter() {
+            assert_eq!(DateTime::from_str(input), Some(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn parse_date_time_digits_are_bad() {
+        assert!(DateTime::from_str("2016").is_none());
+        assert!(DateTime::from_str("1465785000").is_none());
+    }
+
+    #[test]
+    fn parse_date_time_keeps_offset() {
+        let date = DateTime::from_str("2016-06-13T05:30:00+03:00").unwrap();
+        assert_eq!(date.offset(), FixedOffset::east(3 * 3600));
+        assert_eq!(date.to_string(), "2016-06-13 05:30:00 +0300");
+
+        let date = DateTime::from_str("2016-06-13 02:30:00 UTC").unwrap();
+        assert_eq!(
+            date,
+            DateTime::from_str("2016-06-13 02:30:00 +0000").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_offsets() {
+        assert_eq!(
+            parse_offset("+05:30"),
+            Some(FixedOffset::east(5 * 3600 + 30 * 60))
+        );
+        assert_eq!(parse_offset("-0800"), Some(FixedOffset::west(8 * 3600)));
+        assert_eq!(parse_offset("+09"), Some(FixedOffset::east(9 * 3600)));
+        assert_eq!(parse_offset("UTC"), Some(FixedOffset::east(0)));
+        assert_eq!(parse_offset("Z"), Some(FixedOffset::east(0)));
+        assert_eq!(parse_offset("+05:60"), None);
+        assert_eq!(parse_offset("+24:00"), None);
+        assert_eq!(parse_offset("0530"), None);
+        assert_eq!(parse_offset("Europe/Paris"), None);
+        assert_eq!(parse_offset("+ä5"), None);
+    }
+
+    #[test]
+    fn parse_date_time_date_only() {
+        let expected = DateTime::from_str("2016-06-13 00:00:00 +0000").unwrap();
+        let inputs = ["2016-06-13", "2016/06/13", "13 Jun 2016", "June 13, 2016"];
+        for input in inputs.iter() {
+            assert_eq!(DateTime::from_str(input), Some(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn from_timestamp_auto() {
+        assert_eq!(DateTime::from_timestamp_auto(0), Some(DateTime::default()));
+        assert_eq!(
+            DateTime::from_timestamp_auto(1_465_785_000),
+            DateTime::from_timestamp_auto(1_465_785_000_000)
+        );
+    }
+
+    #[test]
+    fn add_months_clamps_day() {
+        let date = DateTime::from_str("2016-01-31 02:30:00 +0300").unwrap();
+        assert_eq!(
+            date.checked_add_months(1),
+            DateTime::from_str("2016-02-29 02:30:00 +0300")
+        );
+        assert_eq!(
+            date.checked_add_months(-2),
+            DateTime::from_str("2015-11-30 02:30:00 +0300")
+        );
+        assert_eq!(
+            date.checked_add_months(24),
+            DateTime::from_str("2018-01-31 02:30:00 +0300")
+        );
+    }
+
+    #[test]
+    fn date_time_components() {
+        // 2016-01-01 is a Friday in the last ISO week of 2015.
+        let date = DateTime::from_str("2016-01-01 02:30:15 +0300").unwrap();
+        let components: Vec<_> = DATE_TIME_COMPONENTS
+            .iter()
+            .map(|name| date.component(name).unwrap())
+            .collect();
+        assert_eq!(components, vec![2016, 1, 1, 1, 5, 1, 53, 2015, 2, 30, 15]);
+        assert_eq!(date.component("unknown"), None);
+    }
+
+    #[test]
+    fn date_components() {
+        let date = Date::from_ymd(2016, 11, 30);
+        let components: Vec<_> = DATE_COMPONENTS
+            .iter()
+            .map(|name| date.component(name).unwrap())
+            .collect();
+        assert_eq!(components, vec![2016, 4, 11, 30, 3, 335, 48, 2016]);
+        assert_eq!(date.component("hour"), None);
+    }
+
+    #[test]
+    fn parse_date_formats() {
+        let expected = Date::from_ymd(2016, 6, 13);
+        let inputs = ["2016-06-13", "2016/06/13", "13 June 2016", "Jun 13, 2016"];
+        for input in inputs.iter() {
+            assert_eq!(Date::from_str(input), Some(expected), "{}", input);
+        }
     }
 }
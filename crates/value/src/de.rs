@@ -0,0 +1,222 @@
+use std::fmt;
+
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, EnumAccess, IntoDeserializer, VariantAccess, Visitor};
+
+use super::Value;
+
+/// Convert a `liquid_value::Value` into a `T`.
+///
+/// Arrays deserialize as sequences or tuples, objects as maps or structs and `nil` as `None` or
+/// `()`.  Bytes deserialize as bytes or sequences, like for `Vec<u8>`.  Enum variants are a
+/// string for unit variants or an object with a single key, the variant name, otherwise.  Dates
+/// and durations deserialize as their string form.
+///
+/// # Examples
+///
+/// ```rust
+/// let value = liquid_value::value!(["foo", 5]);
+/// let (s, n): (String, i32) = liquid_value::from_value(&value).unwrap();
+/// assert_eq!(s, "foo");
+/// assert_eq!(n, 5);
+/// ```
+pub fn from_value<T>(value: &Value) -> Result<T, liquid_error::Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(ValueDeserializer(value)).map_err(|e| e.0)
+}
+
+#[derive(Debug)]
+struct DeError(liquid_error::Error);
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.0)
+    }
+}
+
+impl ::std::error::Error for DeError {
+    fn source(&self) -> Option<&(dyn (::std::error::Error) + 'static)> {
+        ::std::error::Error::source(&self.0)
+    }
+}
+
+impl de::Error for DeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        DeError(liquid_error::Error::with_msg(format!("{}", msg)))
+    }
+}
+
+struct ValueDeserializer<'v>(&'v Value);
+
+impl<'de, 'v> IntoDeserializer<'de, DeError> for ValueDeserializer<'v> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, 'v> de::Deserializer<'de> for ValueDeserializer<'v> {
+    type Error = DeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Scalar(x) => x.visit(visitor),
+            Value::Array(x) => {
+                let mut seq = SeqDeserializer::new(x.iter().map(ValueDeserializer));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Object(x) => {
+                let mut map =
+                    MapDeserializer::new(x.iter().map(|(k, v)| (k.as_str(), ValueDeserializer(v))));
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            Value::State(_) | Value::Nil => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            // No arguments is an empty array.
+            Value::Array(x) if x.is_empty() => visitor.visit_unit(),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        if let Value::Scalar(x) = self.0 {
+            // For `Vec<u8>` without `serde_bytes`.
+            if let Some(bytes) = x.as_bytes() {
+                let mut seq = SeqDeserializer::<_, DeError>::new(bytes.iter().cloned());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                return Ok(value);
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Scalar(x) => {
+                let variant: String = x.clone().into_string().as_str().to_owned();
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            Value::Object(x) if x.len() == 1 => {
+                let (variant, value) = x.iter().next().expect("length checked");
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            _ => Err(de::Error::custom(
+                "variant name or object with a single key expected",
+            )),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct EnumDeserializer<'v> {
+    variant: &'v str,
+    value: &'v Value,
+}
+
+impl<'de, 'v> EnumAccess<'de> for EnumDeserializer<'v> {
+    type Error = DeError;
+    type Variant = ValueDeserializer<'v>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), DeError>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant: de::value::StrDeserializer<'_, DeError> = self.variant.into_deserializer();
+        let variant = seed.deserialize(variant)?;
+        Ok((variant, ValueDeserializer(self.value)))
+    }
+}
+
+impl<'de, 'v> VariantAccess<'de> for ValueDeserializer<'v> {
+    type Error = DeError;
+
+    fn unit_variant(self) -> Result<(), DeError> {
+        match self.0 {
+            Value::Nil => Ok(()),
+            _ => Err(de::Error::custom("unit variant expected")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, DeError>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::ValueView;
+
 /// Abstract the lifetime of a `Display`.
 #[allow(missing_debug_implementations)]
 pub enum DisplayCow<'a> {
@@ -33,3 +35,77 @@ impl fmt::Display for StrDisplay {
         write!(f, "{}", self.s)
     }
 }
+
+/// Default formatting of values rendered for the user, like by `{{ }}`.
+///
+/// Unset choices keep the standard rendering.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DisplayPolicy {
+    pub(crate) float_precision: Option<usize>,
+    pub(crate) date_format: Option<String>,
+    pub(crate) date_time_format: Option<String>,
+    pub(crate) bool_text: Option<(String, String)>,
+}
+
+impl DisplayPolicy {
+    /// Create a policy with the standard rendering.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render fractional numbers with exactly `digits` decimals, like `1.0` for `digits == 1`.
+    pub fn float_precision(mut self, digits: usize) -> Self {
+        self.float_precision = Some(digits);
+        self
+    }
+
+    /// Render dates with a `strftime` format.
+    pub fn date_format<S: Into<String>>(mut self, format: S) -> Self {
+        self.date_format = Some(format.into());
+        self
+    }
+
+    /// Render date times with a `strftime` format.
+    pub fn date_time_format<S: Into<String>>(mut self, format: S) -> Self {
+        self.date_time_format = Some(format.into());
+        self
+    }
+
+    /// Render booleans as `true_text` and `false_text`.
+    pub fn bool_text<T: Into<String>, F: Into<String>>(
+        mut self,
+        true_text: T,
+        false_text: F,
+    ) -> Self {
+        self.bool_text = Some((true_text.into(), false_text.into()));
+        self
+    }
+
+    /// A `Display` for a value rendered for the user, following this policy.
+    pub fn render<'v>(&'v self, value: &'v dyn ValueView) -> DisplayCow<'v> {
+        DisplayCow::Owned(Box::new(PolicyRender {
+            policy: self,
+            value,
+        }))
+    }
+}
+
+struct PolicyRender<'v> {
+    policy: &'v DisplayPolicy,
+    value: &'v dyn ValueView,
+}
+
+impl<'v> fmt::Display for PolicyRender<'v> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scalar) = self.value.as_scalar() {
+            scalar.fmt_with_policy(self.policy, f)
+        } else if let Some(array) = self.value.as_array() {
+            for item in array.values() {
+                write!(f, "{}", self.policy.render(item))?;
+            }
+            Ok(())
+        } else {
+            write!(f, "{}", self.value.render())
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt;
+
+/// Liquid's native length of time type.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Duration {
+    #[serde(with = "friendly_duration")]
+    inner: DurationImpl,
+}
+
+type DurationImpl = chrono::Duration;
+
+/// Comfortably within the range of both `i64` and `chrono::Duration`.
+const MAX_MILLISECONDS: f64 = 9e18;
+
+impl Duration {
+    /// Create a `Duration` from a number of seconds.
+    pub fn from_seconds(seconds: i64) -> Option<Self> {
+        let milliseconds = seconds.checked_mul(1000)?;
+        Some(Self::with_chrono(DurationImpl::milliseconds(milliseconds)))
+    }
+
+    /// Create a `Duration` from a fractional number of seconds.
+    pub fn from_seconds_f64(seconds: f64) -> Option<Self> {
+        let milliseconds = (seconds * 1000.0).round();
+        if !milliseconds.is_finite() || MAX_MILLISECONDS < milliseconds.abs() {
+            return None;
+        }
+        Some(Self::with_chrono(DurationImpl::milliseconds(
+            milliseconds as i64,
+        )))
+    }
+
+    /// Convert a `str` to `Self`
+    ///
+    /// Accepts ISO 8601 durations, like `P1DT2H15M` or `PT1.5S`, and whole seconds, like `90`.
+    /// Years and months are rejected as they don't have a fixed length.
+    pub fn from_str(other: &str) -> Option<Self> {
+        parse_duration(other.trim()).map(Self::with_chrono)
+    }
+
+    /// The total number of whole seconds, truncated towards zero.
+    pub fn num_seconds(&self) -> i64 {
+        self.inner.num_seconds()
+    }
+
+    /// The total number of whole milliseconds, truncated towards zero.
+    pub fn num_milliseconds(&self) -> i64 {
+        self.inner.num_milliseconds()
+    }
+
+    /// Convert to a `chrono::Duration`.
+    pub fn to_chrono(self) -> chrono::Duration {
+        self.inner
+    }
+
+    /// Convert from a `chrono::Duration`.
+    pub fn with_chrono(inner: chrono::Duration) -> Self {
+        Self { inner }
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let milliseconds = self.inner.num_milliseconds();
+        if milliseconds < 0 {
+            write!(f, "-")?;
+        }
+        let milliseconds = i128::from(milliseconds).abs();
+        let (seconds, milliseconds) = (milliseconds / 1000, milliseconds % 1000);
+        let (minutes, seconds) = (seconds / 60, seconds % 60);
+        let (hours, minutes) = (minutes / 60, minutes % 60);
+        let (days, hours) = (hours / 24, hours % 24);
+
+        write!(f, "P")?;
+        if 0 < days {
+            write!(f, "{}D", days)?;
+        }
+        if hours == 0 && minutes == 0 && seconds == 0 && milliseconds == 0 {
+            if days == 0 {
+                write!(f, "T0S")?;
+            }
+            return Ok(());
+        }
+        write!(f, "T")?;
+        if 0 < hours {
+            write!(f, "{}H", hours)?;
+        }
+        if 0 < minutes {
+            write!(f, "{}M", minutes)?;
+        }
+        if 0 < milliseconds {
+            let fraction = format!("{:03}", milliseconds);
+            write!(f, "{}.{}S", seconds, fraction.trim_end_matches('0'))?;
+        } else if 0 < seconds {
+            write!(f, "{}S", seconds)?;
+        }
+        Ok(())
+    }
+}
+
+mod friendly_duration {
+    use super::*;
+    use serde::{self, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S>(duration: &DurationImpl, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let s = Duration::with_chrono(*duration).to_string();
+        serializer.serialize_str(&s)
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<DurationImpl, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        parse_duration(&s).ok_or_else(|| serde::de::Error::custom("invalid duration"))
+    }
+}
+
+fn parse_duration(s: &str) -> Option<DurationImpl> {
+    if let Ok(seconds) = s.parse::<i64>() {
+        return Duration::from_seconds(seconds).map(|d| d.inner);
+    }
+
+    let unsigned = s.trim_start_matches('-');
+    let negative = match s.len() - unsigned.len() {
+        0 => false,
+        1 => true,
+        _ => return None,
+    };
+    let mut chars = unsigned.chars();
+    if chars.next() != Some('P') {
+        return None;
+    }
+
+    let mut milliseconds: i64 = 0;
+    let mut in_time = false;
+    let mut any = false;
+    let mut number = String::new();
+    for c in chars {
+        match c {
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            _ => {
+                let unit = match (in_time, c) {
+                    (false, 'W') => 7 * 24 * 60 * 60 * 1000,
+                    (false, 'D') => 24 * 60 * 60 * 1000,
+                    (true, 'H') => 60 * 60 * 1000,
+                    (true, 'M') => 60 * 1000,
+                    (true, 'S') => 1000,
+                    _ => return None,
+                };
+                let value: f64 = number.parse().ok()?;
+                number.clear();
+                let value = (value * unit as f64).round();
+                if MAX_MILLISECONDS < value {
+                    return None;
+                }
+                milliseconds = milliseconds.checked_add(value as i64)?;
+                any = true;
+            }
+        }
+    }
+    if !any || !number.is_empty() {
+        return None;
+    }
+
+    let milliseconds = if negative {
+        -milliseconds
+    } else {
+        milliseconds
+    };
+    Some(DurationImpl::milliseconds(milliseconds))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_duration_bad() {
+        assert!(Duration::from_str("").is_none());
+        assert!(Duration::from_str("P").is_none());
+        assert!(Duration::from_str("PT").is_none());
+        assert!(Duration::from_str("P1M").is_none());
+        assert!(Duration::from_str("P1Y").is_none());
+        assert!(Duration::from_str("PT1").is_none());
+        assert!(Duration::from_str("aaaaa").is_none());
+    }
+
+    #[test]
+    fn parse_duration_iso_8601() {
+        assert_eq!(
+            Duration::from_str("P1DT2H15M").map(|d| d.num_seconds()),
+            Some(24 * 3600 + 2 * 3600 + 15 * 60)
+        );
+        assert_eq!(
+            Duration::from_str("P2W").map(|d| d.num_seconds()),
+            Some(14 * 24 * 3600)
+        );
+        assert_eq!(
+            Duration::from_str("PT1.5S").map(|d| d.num_milliseconds()),
+            Some(1500)
+        );
+        assert_eq!(
+            Duration::from_str("-PT90M").map(|d| d.num_seconds()),
+            Some(-90 * 60)
+        );
+    }
+
+    #[test]
+    fn parse_duration_seconds() {
+        assert_eq!(Duration::from_str("90").map(|d| d.num_seconds()), Some(90));
+    }
+
+    #[test]
+    fn display_duration() {
+        let cases = [
+            ("P1DT2H15M", "P1DT2H15M"),
+            ("PT0S", "PT0S"),
+            ("P1D", "P1D"),
+            ("PT36H", "P1DT12H"),
+            ("PT1.50S", "PT1.5S"),
+            ("-PT90M", "-PT1H30M"),
+        ];
+        for &(input, expected) in cases.iter() {
+            assert_eq!(Duration::from_str(input).unwrap().to_string(), expected);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use kstring::KString;
+
+use super::{Date, DateTime, Duration, Object, Scalar, ScalarCow, Value};
+
+/// Convert directly into a `Value`, without going through `serde`.
+///
+/// `to_value` works for anything `Serialize` but builds the `Value` one serde call at a time.
+/// Types converted on hot paths, like the globals of every render, can implement this instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use liquid_value::{IntoValue, Value};
+///
+/// struct Product {
+///     title: String,
+///     price: f64,
+/// }
+///
+/// impl IntoValue for Product {
+///     fn into_value(self) -> Value {
+///         let mut object = liquid_value::Object::with_capacity(2);
+///         object.insert("title".into(), self.title.into_value());
+///         object.insert("price".into(), self.price.into_value());
+///         Value::Object(object)
+///     }
+/// }
+///
+/// let products = vec![Product { title: "Tea".to_owned(), price: 3.5 }].into_value();
+/// assert_eq!(products, liquid_value::value!([{"title": "Tea", "price": 3.5}]));
+/// ```
+pub trait IntoValue {
+    /// Convert `self` into a `Value`.
+    fn into_value(self) -> Value;
+}
+
+impl IntoValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl IntoValue for Scalar {
+    fn into_value(self) -> Value {
+        Value::Scalar(self)
+    }
+}
+
+impl IntoValue for Object {
+    fn into_value(self) -> Value {
+        Value::Object(self)
+    }
+}
+
+macro_rules! scalar_into_value {
+    ($($t:ty),*) => {
+        $(
+            impl IntoValue for $t {
+                fn into_value(self) -> Value {
+                    Value::scalar(self)
+                }
+            }
+        )*
+    };
+}
+
+scalar_into_value!(i32, i64, i128, u64, f64, bool, String, KString, DateTime, Date, Duration);
+#[cfg(feature = "decimal")]
+scalar_into_value!(crate::Decimal);
+
+macro_rules! widen_into_value {
+    ($($t:ty => $wide:ty),*) => {
+        $(
+            impl IntoValue for $t {
+                fn into_value(self) -> Value {
+                    Value::scalar(<$wide>::from(self))
+                }
+            }
+        )*
+    };
+}
+
+widen_into_value!(i8 => i32, i16 => i32, u8 => i32, u16 => i32, u32 => i64, f32 => f64);
+
+impl<'s> IntoValue for &'s str {
+    fn into_value(self) -> Value {
+        Value::scalar(ScalarCow::new(self).into_owned())
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Value {
+        match self {
+            Some(value) => value.into_value(),
+            None => Value::Nil,
+        }
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::Array(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+impl<K, V, S> IntoValue for HashMap<K, V, S>
+where
+    K: Into<KString>,
+    V: IntoValue,
+    S: BuildHasher,
+{
+    fn into_value(self) -> Value {
+        let mut object = Object::with_capacity(self.len());
+        for (key, value) in self {
+            object.insert(key.into(), value.into_value());
+        }
+        Value::Object(object)
+    }
+}
+
+impl<K, V> IntoValue for BTreeMap<K, V>
+where
+    K: Into<KString>,
+    V: IntoValue,
+{
+    fn into_value(self) -> Value {
+        let mut object = Object::with_capacity(self.len());
+        for (key, value) in self {
+            object.insert(key.into(), value.into_value());
+        }
+        Value::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_to_value() {
+        let mut prices = HashMap::new();
+        prices.insert("tea".to_owned(), vec![Some(3.5), None]);
+        prices.insert("cake".to_owned(), vec![Some(4.25)]);
+
+        let expected = crate::to_value(&prices).unwrap();
+        assert_eq!(prices.into_value(), expected);
+    }
+
+    #[test]
+    fn widens_integers() {
+        assert_eq!(7u8.into_value(), Value::scalar(7));
+        assert_eq!(u32::MAX.into_value(), Value::scalar(i64::from(u32::MAX)));
+    }
+}
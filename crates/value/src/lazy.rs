@@ -0,0 +1,128 @@
+use std::fmt;
+
+use kstring::KStringCow;
+use once_cell::sync::Lazy;
+
+use crate::{ArrayView, DisplayCow, ObjectView, ScalarCow, State, Value, ValueView};
+
+type Init = Box<dyn FnOnce() -> Value + Send>;
+
+/// A value computed the first time it is read, like a database-backed field that most templates
+/// never use.
+///
+/// It is computed at most once, even when read from several threads.
+///
+/// # Examples
+///
+/// ```rust
+/// use liquid_value::{LazyValue, Value, ValueView};
+///
+/// let orders = LazyValue::new(|| Value::scalar(3));
+/// assert!(!orders.is_computed());
+/// assert_eq!(orders.render().to_string(), "3");
+/// assert!(orders.is_computed());
+/// ```
+pub struct LazyValue {
+    value: Lazy<Value, Init>,
+}
+
+impl LazyValue {
+    /// Create a value computed by `init` on first read.
+    pub fn new<F>(init: F) -> Self
+    where
+        F: FnOnce() -> Value + Send + 'static,
+    {
+        Self {
+            value: Lazy::new(Box::new(init)),
+        }
+    }
+
+    /// The value, computing it if this is the first read.
+    pub fn get(&self) -> &Value {
+        Lazy::force(&self.value)
+    }
+
+    /// Whether the value has been read, so computed.
+    pub fn is_computed(&self) -> bool {
+        Lazy::get(&self.value).is_some()
+    }
+}
+
+impl fmt::Debug for LazyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Lazy::get(&self.value) {
+            Some(value) => f.debug_tuple("LazyValue").field(value).finish(),
+            None => f.write_str("LazyValue(<not computed>)"),
+        }
+    }
+}
+
+impl ValueView for LazyValue {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        self.get().render()
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        self.get().source()
+    }
+    fn type_name(&self) -> &'static str {
+        self.get().type_name()
+    }
+    fn query_state(&self, state: State) -> bool {
+        self.get().query_state(state)
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.get().to_kstr()
+    }
+    fn to_value(&self) -> Value {
+        self.get().clone()
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        self.get().as_scalar()
+    }
+
+    fn as_array(&self) -> Option<&dyn ArrayView> {
+        self.get().as_array()
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        self.get().as_object()
+    }
+
+    fn as_state(&self) -> Option<State> {
+        self.get().as_state()
+    }
+
+    fn is_nil(&self) -> bool {
+        self.get().is_nil()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn computes_once_on_first_read() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let lazy = LazyValue::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            crate::value!({"total": 5})
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let object = lazy.as_object().unwrap();
+        assert_eq!(object.get("total").unwrap().to_kstr(), "5");
+        assert_eq!(lazy.to_value(), crate::value!({"total": 5}));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -12,8 +12,14 @@ mod macros;
 
 mod array;
 mod cow;
+mod custom;
+mod cycle;
 mod date;
+mod de;
 mod display;
+mod duration;
+mod into_value;
+mod lazy;
 mod object;
 mod path;
 mod scalar;
@@ -22,12 +28,19 @@ mod state;
 mod values;
 mod view;
 
+pub mod bytes;
 pub mod map;
 
 pub use crate::array::*;
 pub use crate::cow::*;
+pub use crate::custom::ScalarValue;
+pub use crate::cycle::*;
 pub use crate::date::*;
+pub use crate::de::*;
 pub use crate::display::*;
+pub use crate::duration::*;
+pub use crate::into_value::*;
+pub use crate::lazy::*;
 pub use crate::object::*;
 pub use crate::path::*;
 pub use crate::scalar::*;
@@ -35,3 +48,7 @@ pub use crate::ser::*;
 pub use crate::state::*;
 pub use crate::values::*;
 pub use crate::view::*;
+
+/// Exact decimal numbers, see `ScalarCow::to_decimal`.
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
@@ -40,6 +40,14 @@ impl Map {
         }
     }
 
+    /// Makes a new empty Map with room for at least `capacity` entries.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Map {
+            map: MapImpl::with_capacity(capacity),
+        }
+    }
+
     /// Clears the map, removing all values.
     #[inline]
     pub fn clear(&mut self) {
@@ -174,6 +182,23 @@ impl Map {
             iter: self.map.values_mut(),
         }
     }
+
+    /// Merges `other` into this map, with the values of `other` taking precedence.
+    ///
+    /// Objects present in both maps are merged recursively; any other value, including arrays,
+    /// is replaced.
+    pub fn deep_merge(&mut self, other: Map) {
+        for (key, value) in other {
+            match (self.map.get_mut(&key), value) {
+                (Some(Value::Object(ref mut existing)), Value::Object(value)) => {
+                    existing.deep_merge(value)
+                }
+                (_, value) => {
+                    self.map.insert(key, value);
+                }
+            }
+        }
+    }
 }
 
 impl Default for Map {
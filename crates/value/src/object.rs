@@ -30,6 +30,12 @@ pub trait ObjectView: ValueView {
     fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView>;
 }
 
+/// Keys of an object that are known from its type, like with `derive(ObjectView)`.
+pub trait ObjectKeys {
+    /// Keys every instance has.
+    fn object_keys() -> &'static [&'static str];
+}
+
 /// Type representing a Liquid object, payload of the `Value::Object` variant
 pub type Object = map::Map;
 
@@ -325,4 +331,27 @@ mod test {
         let view: &dyn ValueView = object.as_value();
         println!("{}", view.source());
     }
+
+    #[test]
+    fn test_deep_merge() {
+        let mut obj: Object = crate::object!({
+            "site": {"title": "Blog", "author": {"name": "Ann", "email": "ann@example.com"}},
+            "tags": ["a", "b"],
+        });
+        obj.deep_merge(crate::object!({
+            "site": {"author": {"name": "Bob"}, "lang": "en"},
+            "tags": ["c"],
+        }));
+        assert_eq!(
+            obj,
+            crate::object!({
+                "site": {
+                    "title": "Blog",
+                    "author": {"name": "Bob", "email": "ann@example.com"},
+                    "lang": "en",
+                },
+                "tags": ["c"],
+            })
+        );
+    }
 }
@@ -186,7 +186,9 @@ fn augmented_get<'o>(value: &'o dyn ValueView, index: &ScalarCow) -> Option<Valu
             "size" => Some(ValueCow::Owned(Value::scalar(
                 scalar.to_kstr().as_str().len() as i32,
             ))),
-            _ => None,
+            _ => scalar
+                .date_component(index.as_str())
+                .map(|c| ValueCow::Owned(Value::scalar(c))),
         }
     } else {
         None
@@ -217,6 +219,12 @@ pub fn find<'o>(value: &'o dyn ValueView, path: PathRef<'_, '_>) -> Result<Value
                 } else if let Some(obj) = parent.as_object() {
                     let available: Vec<_> = obj.keys().collect();
                     available
+                } else if let Some(scalar) = parent.as_scalar() {
+                    scalar
+                        .date_components()
+                        .iter()
+                        .map(|c| KStringCow::from_static(c))
+                        .collect()
                 } else {
                     Vec::new()
                 };
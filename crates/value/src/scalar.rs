@@ -1,12 +1,20 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt;
+use std::sync::Arc;
 
 use kstring::KString;
 use kstring::KStringCow;
 use kstring::KStringRef;
+#[cfg(feature = "decimal")]
+use num_traits::{FromPrimitive, ToPrimitive};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
 
-use crate::{Date, DateTime, DisplayCow, State};
-use crate::{Value, ValueView};
+use crate::custom::CustomScalar;
+use crate::{Date, DateTime, DisplayCow, Duration, State, DATE_COMPONENTS, DATE_TIME_COMPONENTS};
+use crate::{DateFormats, DisplayPolicy, ScalarValue, StrDisplay, Value, ValueView};
 
 /// A Liquid scalar value
 #[derive(Clone, Serialize, Deserialize)]
@@ -22,10 +30,30 @@ pub type Scalar = ScalarCow<'static>;
 #[serde(untagged)]
 enum ScalarCowEnum<'s> {
     Integer(i32),
+    // Only for whole numbers that don't fit in an `Integer`.
+    #[serde(with = "big_integer")]
+    BigInteger(i128),
     Float(f64),
+    // Decimals are only created natively, and serialize as their text to keep every digit.
+    #[cfg(feature = "decimal")]
+    #[serde(skip_deserializing, serialize_with = "serialize_decimal")]
+    Decimal(Decimal),
     Bool(bool),
     DateTime(DateTime),
     Date(Date),
+    // Durations are only created natively; strings in data stay strings.
+    #[serde(skip_deserializing)]
+    Duration(Duration),
+    // Bytes come from fields marked with `liquid_value::bytes` or from formats with binary data,
+    // and must be encoded by a filter to be rendered.
+    #[serde(
+        serialize_with = "crate::bytes::serialize",
+        deserialize_with = "deserialize_bytes"
+    )]
+    Bytes(Cow<'s, [u8]>),
+    // Custom values are only created natively, and serialize as their text.
+    #[serde(skip_deserializing, serialize_with = "serialize_custom")]
+    Custom(CustomScalar),
     Str(KStringCow<'s>),
 }
 
@@ -39,10 +67,16 @@ impl<'s> ScalarCow<'s> {
     pub fn into_owned(self) -> Scalar {
         match self.0 {
             ScalarCowEnum::Integer(x) => Scalar::new(x),
+            ScalarCowEnum::BigInteger(x) => Scalar::new(x),
             ScalarCowEnum::Float(x) => Scalar::new(x),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => Scalar::new(x),
             ScalarCowEnum::Bool(x) => Scalar::new(x),
             ScalarCowEnum::DateTime(x) => Scalar::new(x),
             ScalarCowEnum::Date(x) => Scalar::new(x),
+            ScalarCowEnum::Duration(x) => Scalar::new(x),
+            ScalarCowEnum::Bytes(x) => Scalar::new(x.into_owned()),
+            ScalarCowEnum::Custom(x) => Scalar::from_custom(x),
             ScalarCowEnum::Str(x) => Scalar::new(x.into_owned()),
         }
     }
@@ -51,10 +85,16 @@ impl<'s> ScalarCow<'s> {
     pub fn as_ref<'r: 's>(&'r self) -> ScalarCow<'r> {
         match self.0 {
             ScalarCowEnum::Integer(x) => ScalarCow::new(x),
+            ScalarCowEnum::BigInteger(x) => ScalarCow::new(x),
             ScalarCowEnum::Float(x) => ScalarCow::new(x),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => ScalarCow::new(x),
             ScalarCowEnum::Bool(x) => ScalarCow::new(x),
             ScalarCowEnum::DateTime(x) => ScalarCow::new(x),
             ScalarCowEnum::Date(x) => ScalarCow::new(x),
+            ScalarCowEnum::Duration(x) => ScalarCow::new(x),
+            ScalarCowEnum::Bytes(ref x) => ScalarCow::new(x.as_ref()),
+            ScalarCowEnum::Custom(ref x) => ScalarCow::from_custom(x.clone()),
             ScalarCowEnum::Str(ref x) => ScalarCow::new(x.as_ref()),
         }
     }
@@ -63,10 +103,16 @@ impl<'s> ScalarCow<'s> {
     pub fn as_view<'r: 's>(&'r self) -> &'s dyn ValueView {
         match self.0 {
             ScalarCowEnum::Integer(ref x) => x,
+            ScalarCowEnum::BigInteger(ref x) => x,
             ScalarCowEnum::Float(ref x) => x,
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => x,
             ScalarCowEnum::Bool(ref x) => x,
             ScalarCowEnum::DateTime(ref x) => x,
             ScalarCowEnum::Date(ref x) => x,
+            ScalarCowEnum::Duration(ref x) => x,
+            ScalarCowEnum::Bytes(ref x) => x,
+            ScalarCowEnum::Custom(ref x) => x,
             ScalarCowEnum::Str(ref x) => x,
         }
     }
@@ -75,10 +121,16 @@ impl<'s> ScalarCow<'s> {
     pub fn into_string(self) -> KString {
         match self.0 {
             ScalarCowEnum::Integer(x) => x.to_string().into(),
+            ScalarCowEnum::BigInteger(x) => x.to_string().into(),
             ScalarCowEnum::Float(x) => x.to_string().into(),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => x.to_string().into(),
             ScalarCowEnum::Bool(x) => x.to_string().into(),
             ScalarCowEnum::DateTime(x) => x.to_string().into(),
             ScalarCowEnum::Date(x) => x.to_string().into(),
+            ScalarCowEnum::Duration(x) => x.to_string().into(),
+            ScalarCowEnum::Bytes(_) => KString::from_static(""),
+            ScalarCowEnum::Custom(x) => x.0.to_kstr().into_owned(),
             ScalarCowEnum::Str(x) => x.into_owned(),
         }
     }
@@ -92,16 +144,131 @@ impl<'s> ScalarCow<'s> {
         }
     }
 
+    /// Interpret as an integer of any size, if possible
+    pub fn to_big_integer(&self) -> Option<i128> {
+        match self.0 {
+            ScalarCowEnum::Integer(ref x) => Some(i128::from(*x)),
+            ScalarCowEnum::BigInteger(ref x) => Some(*x),
+            ScalarCowEnum::Str(ref x) => x.parse::<i128>().ok(),
+            _ => None,
+        }
+    }
+
     /// Interpret as a float, if possible
     pub fn to_float(&self) -> Option<f64> {
         match self.0 {
             ScalarCowEnum::Integer(ref x) => Some(f64::from(*x)),
+            ScalarCowEnum::BigInteger(ref x) => Some(*x as f64),
             ScalarCowEnum::Float(ref x) => Some(*x),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => x.to_f64(),
             ScalarCowEnum::Str(ref x) => x.parse::<f64>().ok(),
             _ => None,
         }
     }
 
+    /// Interpret as a decimal, if possible
+    ///
+    /// Floats are converted through their shortest text, so `0.1` stays `0.1`.
+    #[cfg(feature = "decimal")]
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        match self.0 {
+            ScalarCowEnum::Str(ref x) => x.parse::<Decimal>().ok(),
+            _ => self.to_number_decimal(),
+        }
+    }
+
+    /// Whether this is a native decimal, rather than a number that can be read as one.
+    ///
+    /// Math filters use decimal arithmetic when either side is one.
+    #[cfg(feature = "decimal")]
+    pub fn is_decimal(&self) -> bool {
+        match self.0 {
+            ScalarCowEnum::Decimal(_) => true,
+            _ => false,
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    fn is_number(&self) -> bool {
+        match self.0 {
+            ScalarCowEnum::Integer(_)
+            | ScalarCowEnum::BigInteger(_)
+            | ScalarCowEnum::Float(_)
+            | ScalarCowEnum::Decimal(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Numbers as a decimal, when in its range.
+    #[cfg(feature = "decimal")]
+    fn to_number_decimal(&self) -> Option<Decimal> {
+        match self.0 {
+            ScalarCowEnum::Integer(x) => Some(Decimal::from(x)),
+            ScalarCowEnum::BigInteger(x) => Decimal::from_i128(x),
+            ScalarCowEnum::Float(x) if x.is_finite() => x.to_string().parse::<Decimal>().ok(),
+            ScalarCowEnum::Decimal(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Extracts the bytes if they are bytes.
+    ///
+    /// Bytes render as nothing, so they need to be encoded, like with `base64_encode`.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self.0 {
+            ScalarCowEnum::Bytes(ref x) => Some(x.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Embed a domain type, see `ScalarValue`.
+    pub fn custom<T: ScalarValue>(value: T) -> Self {
+        Self::from_custom(CustomScalar(Arc::new(value)))
+    }
+
+    pub(crate) fn from_custom(value: CustomScalar) -> Self {
+        ScalarCow {
+            0: ScalarCowEnum::Custom(value),
+        }
+    }
+
+    /// Extracts the domain type if it is a `T`, see `ScalarValue`.
+    pub fn downcast_ref<T: ScalarValue>(&self) -> Option<&T> {
+        match self.0 {
+            ScalarCowEnum::Custom(ref x) => x.0.as_any().downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// Render for the user, following `policy`.
+    pub(crate) fn fmt_with_policy(
+        &self,
+        policy: &DisplayPolicy,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self.0 {
+            ScalarCowEnum::Float(x) => match policy.float_precision {
+                Some(digits) => write!(f, "{:.*}", digits, x),
+                None => write!(f, "{}", x),
+            },
+            ScalarCowEnum::Date(ref x) => match policy.date_format {
+                Some(ref format) => write!(f, "{}", x.format(format)),
+                None => write!(f, "{}", x),
+            },
+            ScalarCowEnum::DateTime(ref x) => match policy.date_time_format {
+                Some(ref format) => write!(f, "{}", x.format(format)),
+                None => write!(f, "{}", x),
+            },
+            ScalarCowEnum::Bool(x) => match policy.bool_text {
+                Some((ref true_text, _)) if x => write!(f, "{}", true_text),
+                Some((_, ref false_text)) => write!(f, "{}", false_text),
+                None => write!(f, "{}", x),
+            },
+            _ => write!(f, "{}", self.render()),
+        }
+    }
+
     /// Interpret as a bool, if possible
     pub fn to_bool(&self) -> Option<bool> {
         match self.0 {
@@ -111,14 +278,65 @@ impl<'s> ScalarCow<'s> {
     }
 
     /// Interpret as a date time, if possible
+    ///
+    /// Integers are interpreted as Unix timestamps, see `DateTime::from_timestamp_auto`.
     pub fn to_date_time(&self) -> Option<DateTime> {
+        self.to_date_time_parsed(DateTime::from_str)
+    }
+
+    /// Interpret as a date time, if possible, parsing strings with `formats`
+    pub fn to_date_time_with(&self, formats: &DateFormats) -> Option<DateTime> {
+        self.to_date_time_parsed(|s| formats.parse(s))
+    }
+
+    fn to_date_time_parsed<P>(&self, parse: P) -> Option<DateTime>
+    where
+        P: FnOnce(&str) -> Option<DateTime>,
+    {
         match self.0 {
             ScalarCowEnum::DateTime(ref x) => Some(*x),
-            ScalarCowEnum::Str(ref x) => DateTime::from_str(x.as_str()),
+            ScalarCowEnum::Date(ref x) => Some(DateTime::default().with_date(*x)),
+            ScalarCowEnum::Integer(x) => DateTime::from_timestamp_auto(x.into()),
+            ScalarCowEnum::BigInteger(x) => i64::try_from(x)
+                .ok()
+                .and_then(DateTime::from_timestamp_auto),
+            ScalarCowEnum::Str(ref x) => parse(x.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Interpret as a duration, if possible
+    ///
+    /// Numbers are interpreted as seconds.
+    pub fn to_duration(&self) -> Option<Duration> {
+        match self.0 {
+            ScalarCowEnum::Duration(ref x) => Some(*x),
+            ScalarCowEnum::Integer(x) => Duration::from_seconds(x.into()),
+            ScalarCowEnum::BigInteger(x) => i64::try_from(x).ok().and_then(Duration::from_seconds),
+            ScalarCowEnum::Float(x) => Duration::from_seconds_f64(x),
+            ScalarCowEnum::Str(ref x) => Duration::from_str(x.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Look up a component of a native date or date time, like `year`.
+    pub(crate) fn date_component(&self, name: &str) -> Option<i32> {
+        match self.0 {
+            ScalarCowEnum::DateTime(ref x) => x.component(name),
+            ScalarCowEnum::Date(ref x) => x.component(name),
             _ => None,
         }
     }
 
+    /// Names accepted by `date_component`.
+    pub(crate) fn date_components(&self) -> &'static [&'static str] {
+        match self.0 {
+            ScalarCowEnum::DateTime(_) => DATE_TIME_COMPONENTS,
+            ScalarCowEnum::Date(_) => DATE_COMPONENTS,
+            _ => &[],
+        }
+    }
+
     /// Interpret as a date time, if possible
     pub fn to_date(&self) -> Option<Date> {
         match self.0 {
@@ -128,6 +346,28 @@ impl<'s> ScalarCow<'s> {
             _ => None,
         }
     }
+
+    /// Hand the native value to a serde `visitor`, see `from_value`.
+    pub(crate) fn visit<'de, V, E>(&self, visitor: V) -> Result<V::Value, E>
+    where
+        V: serde::de::Visitor<'de>,
+        E: serde::de::Error,
+    {
+        match self.0 {
+            ScalarCowEnum::Integer(x) => visitor.visit_i32(x),
+            ScalarCowEnum::BigInteger(x) => visitor.visit_i128(x),
+            ScalarCowEnum::Float(x) => visitor.visit_f64(x),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => visitor.visit_string(x.to_string()),
+            ScalarCowEnum::Bool(x) => visitor.visit_bool(x),
+            ScalarCowEnum::DateTime(x) => visitor.visit_string(x.to_string()),
+            ScalarCowEnum::Date(x) => visitor.visit_string(x.to_string()),
+            ScalarCowEnum::Duration(x) => visitor.visit_string(x.to_string()),
+            ScalarCowEnum::Bytes(ref x) => visitor.visit_bytes(x),
+            ScalarCowEnum::Custom(ref x) => visitor.visit_str(x.0.to_kstr().as_str()),
+            ScalarCowEnum::Str(ref x) => visitor.visit_str(x.as_str()),
+        }
+    }
 }
 
 impl<'s> fmt::Debug for ScalarCow<'s> {
@@ -235,6 +475,78 @@ impl<'s> PartialOrd<i32> for ScalarCow<'s> {
     }
 }
 
+impl ValueView for i128 {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn type_name(&self) -> &'static str {
+        "whole number"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue => false,
+            State::Empty => false,
+            State::Blank => false,
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.render().to_string().into()
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(*self)
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(*self))
+    }
+}
+
+impl<'s> From<i128> for ScalarCow<'s> {
+    fn from(s: i128) -> Self {
+        match i32::try_from(s) {
+            Ok(s) => ScalarCow::new(s),
+            Err(_) => ScalarCow {
+                0: ScalarCowEnum::BigInteger(s),
+            },
+        }
+    }
+}
+
+impl<'s> From<i64> for ScalarCow<'s> {
+    fn from(s: i64) -> Self {
+        ScalarCow::new(i128::from(s))
+    }
+}
+
+impl<'s> From<u64> for ScalarCow<'s> {
+    fn from(s: u64) -> Self {
+        ScalarCow::new(i128::from(s))
+    }
+}
+
+impl<'s> PartialEq<i128> for ScalarCow<'s> {
+    fn eq(&self, other: &i128) -> bool {
+        let other = (*other).into();
+        scalar_eq(self, &other)
+    }
+}
+
+impl<'s> PartialOrd<i128> for ScalarCow<'s> {
+    fn partial_cmp(&self, other: &i128) -> Option<Ordering> {
+        let other = (*other).into();
+        scalar_cmp(self, &other)
+    }
+}
+
 impl ValueView for f64 {
     fn as_debug(&self) -> &dyn fmt::Debug {
         self
@@ -292,6 +604,67 @@ impl<'s> PartialOrd<f64> for ScalarCow<'s> {
     }
 }
 
+#[cfg(feature = "decimal")]
+impl ValueView for Decimal {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn type_name(&self) -> &'static str {
+        "decimal"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue => false,
+            State::Empty => false,
+            State::Blank => false,
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.render().to_string().into()
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(*self)
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(*self))
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'s> From<Decimal> for ScalarCow<'s> {
+    fn from(s: Decimal) -> Self {
+        ScalarCow {
+            0: ScalarCowEnum::Decimal(s),
+        }
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'s> PartialEq<Decimal> for ScalarCow<'s> {
+    fn eq(&self, other: &Decimal) -> bool {
+        let other = (*other).into();
+        scalar_eq(self, &other)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'s> PartialOrd<Decimal> for ScalarCow<'s> {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        let other = (*other).into();
+        scalar_cmp(self, &other)
+    }
+}
+
 impl ValueView for bool {
     fn as_debug(&self) -> &dyn fmt::Debug {
         self
@@ -463,6 +836,63 @@ impl<'s> PartialOrd<Date> for ScalarCow<'s> {
     }
 }
 
+impl ValueView for Duration {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn type_name(&self) -> &'static str {
+        "duration"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue => false,
+            State::Empty => false,
+            State::Blank => false,
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.render().to_string().into()
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(*self)
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(*self))
+    }
+}
+
+impl<'s> From<Duration> for ScalarCow<'s> {
+    fn from(s: Duration) -> Self {
+        ScalarCow {
+            0: ScalarCowEnum::Duration(s),
+        }
+    }
+}
+
+impl<'s> PartialEq<Duration> for ScalarCow<'s> {
+    fn eq(&self, other: &Duration) -> bool {
+        let other = (*other).into();
+        scalar_eq(self, &other)
+    }
+}
+
+impl<'s> PartialOrd<Duration> for ScalarCow<'s> {
+    fn partial_cmp(&self, other: &Duration) -> Option<Ordering> {
+        let other = (*other).into();
+        scalar_cmp(self, &other)
+    }
+}
+
 impl<'s> ValueView for &'s str {
     fn as_debug(&self) -> &dyn fmt::Debug {
         self
@@ -777,8 +1207,175 @@ impl<'s> PartialOrd<KStringRef<'s>> for ScalarCow<'s> {
     }
 }
 
+impl<'s> ValueView for Cow<'s, [u8]> {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(StrDisplay { s: "" }))
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(BytesSource { len: self.len() }))
+    }
+    fn type_name(&self) -> &'static str {
+        "bytes"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue | State::Empty | State::Blank => self.is_empty(),
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        KStringCow::from_static("")
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(self.clone().into_owned())
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(self.as_ref()))
+    }
+}
+
+impl<'s> From<Vec<u8>> for ScalarCow<'s> {
+    fn from(s: Vec<u8>) -> Self {
+        ScalarCow {
+            0: ScalarCowEnum::Bytes(Cow::Owned(s)),
+        }
+    }
+}
+
+impl<'s> From<&'s [u8]> for ScalarCow<'s> {
+    fn from(s: &'s [u8]) -> Self {
+        ScalarCow {
+            0: ScalarCowEnum::Bytes(Cow::Borrowed(s)),
+        }
+    }
+}
+
+impl<'s> PartialEq<[u8]> for ScalarCow<'s> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == Some(other)
+    }
+}
+
+struct BytesSource {
+    len: usize,
+}
+
+impl fmt::Display for BytesSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} bytes>", self.len)
+    }
+}
+
 impl<'s> Eq for ScalarCow<'s> {}
 
+impl<'s> From<Arc<dyn ScalarValue>> for ScalarCow<'s> {
+    fn from(s: Arc<dyn ScalarValue>) -> Self {
+        ScalarCow {
+            0: ScalarCowEnum::Custom(CustomScalar(s)),
+        }
+    }
+}
+
+fn serialize_custom<S>(value: &CustomScalar, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(value.0.to_kstr().as_str())
+}
+
+#[cfg(feature = "decimal")]
+fn serialize_decimal<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+fn deserialize_bytes<'de, 's, D>(deserializer: D) -> Result<Cow<'s, [u8]>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct BytesVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("bytes")
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+            Ok(value.to_vec())
+        }
+
+        fn visit_byte_buf<E: serde::de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+            Ok(value)
+        }
+    }
+
+    deserializer.deserialize_bytes(BytesVisitor).map(Cow::Owned)
+}
+
+mod big_integer {
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::convert::TryFrom;
+    use std::fmt;
+
+    pub(crate) fn serialize<S>(value: &i128, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Not all formats support 128-bit integers.
+        if let Ok(value) = i64::try_from(*value) {
+            serializer.serialize_i64(value)
+        } else if let Ok(value) = u64::try_from(*value) {
+            serializer.serialize_u64(value)
+        } else {
+            serializer.serialize_i128(*value)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<i128, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(BigIntegerVisitor)
+    }
+
+    struct BigIntegerVisitor;
+
+    impl<'de> Visitor<'de> for BigIntegerVisitor {
+        type Value = i128;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a whole number")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<i128, E> {
+            Ok(i128::from(value))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<i128, E> {
+            Ok(i128::from(value))
+        }
+
+        fn visit_i128<E: de::Error>(self, value: i128) -> Result<i128, E> {
+            Ok(value)
+        }
+
+        fn visit_u128<E: de::Error>(self, value: u128) -> Result<i128, E> {
+            i128::try_from(value).map_err(|_| E::custom("whole number is too large"))
+        }
+    }
+}
+
 /// A `Display` for a `Scalar` as source code.
 #[derive(Debug)]
 pub struct ScalarSource<'s>(&'s ScalarCowEnum<'s>);
@@ -787,10 +1384,16 @@ impl<'s> fmt::Display for ScalarSource<'s> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {
             ScalarCowEnum::Integer(ref x) => write!(f, "{}", x),
+            ScalarCowEnum::BigInteger(ref x) => write!(f, "{}", x),
             ScalarCowEnum::Float(ref x) => write!(f, "{}", x),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => write!(f, "{}", x),
             ScalarCowEnum::Bool(ref x) => write!(f, "{}", x),
             ScalarCowEnum::DateTime(ref x) => write!(f, "{}", x),
             ScalarCowEnum::Date(ref x) => write!(f, "{}", x),
+            ScalarCowEnum::Duration(ref x) => write!(f, "{}", x),
+            ScalarCowEnum::Bytes(ref x) => write!(f, "{}", BytesSource { len: x.len() }),
+            ScalarCowEnum::Custom(ref x) => write!(f, "{}", x.0.source()),
             ScalarCowEnum::Str(ref x) => write!(f, r#""{}""#, x),
         }
     }
@@ -805,10 +1408,16 @@ impl<'s> fmt::Display for ScalarRendered<'s> {
         // Must match `ScalarCow::to_str`
         match self.0 {
             ScalarCowEnum::Integer(ref x) => write!(f, "{}", x),
+            ScalarCowEnum::BigInteger(ref x) => write!(f, "{}", x),
             ScalarCowEnum::Float(ref x) => write!(f, "{}", x),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => write!(f, "{}", x),
             ScalarCowEnum::Bool(ref x) => write!(f, "{}", x),
             ScalarCowEnum::DateTime(ref x) => write!(f, "{}", x),
             ScalarCowEnum::Date(ref x) => write!(f, "{}", x),
+            ScalarCowEnum::Duration(ref x) => write!(f, "{}", x),
+            ScalarCowEnum::Bytes(_) => Ok(()),
+            ScalarCowEnum::Custom(ref x) => write!(f, "{}", x.0.render()),
             ScalarCowEnum::Str(ref x) => write!(f, "{}", x),
         }
     }
@@ -820,12 +1429,26 @@ fn scalar_eq<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> bool {
         (&ScalarCowEnum::Integer(x), &ScalarCowEnum::Float(y)) => (f64::from(x)) == y,
         (&ScalarCowEnum::Float(x), &ScalarCowEnum::Integer(y)) => x == (f64::from(y)),
         (&ScalarCowEnum::Float(x), &ScalarCowEnum::Float(y)) => x == y,
+        (&ScalarCowEnum::BigInteger(x), &ScalarCowEnum::BigInteger(y)) => x == y,
+        (&ScalarCowEnum::Integer(x), &ScalarCowEnum::BigInteger(y)) => i128::from(x) == y,
+        (&ScalarCowEnum::BigInteger(x), &ScalarCowEnum::Integer(y)) => x == i128::from(y),
+        (&ScalarCowEnum::BigInteger(x), &ScalarCowEnum::Float(y)) => (x as f64) == y,
+        (&ScalarCowEnum::Float(x), &ScalarCowEnum::BigInteger(y)) => x == (y as f64),
         (&ScalarCowEnum::Bool(x), &ScalarCowEnum::Bool(y)) => x == y,
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::DateTime(y)) => x == y,
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::Date(y)) => x == y,
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::Date(y)) => x == x.with_date(y),
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::DateTime(y)) => y.with_date(x) == y,
+        (&ScalarCowEnum::Duration(x), &ScalarCowEnum::Duration(y)) => x == y,
+        (ScalarCowEnum::Bytes(x), ScalarCowEnum::Bytes(y)) => x == y,
         (&ScalarCowEnum::Str(ref x), &ScalarCowEnum::Str(ref y)) => x == y,
+        (ScalarCowEnum::Custom(_), _) | (_, ScalarCowEnum::Custom(_)) => custom_eq(lhs, rhs),
+        #[cfg(feature = "decimal")]
+        (ScalarCowEnum::Decimal(_), _) | (_, ScalarCowEnum::Decimal(_))
+            if lhs.is_number() && rhs.is_number() =>
+        {
+            decimal_cmp(lhs, rhs) == Some(Ordering::Equal)
+        }
         // encode Ruby truthiness: all values except false and nil are true
         (_, &ScalarCowEnum::Bool(b)) | (&ScalarCowEnum::Bool(b), _) => b,
         _ => false,
@@ -838,16 +1461,66 @@ fn scalar_cmp<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> Option<Ordering>
         (&ScalarCowEnum::Integer(x), &ScalarCowEnum::Float(y)) => (f64::from(x)).partial_cmp(&y),
         (&ScalarCowEnum::Float(x), &ScalarCowEnum::Integer(y)) => x.partial_cmp(&(f64::from(y))),
         (&ScalarCowEnum::Float(x), &ScalarCowEnum::Float(y)) => x.partial_cmp(&y),
+        (&ScalarCowEnum::BigInteger(x), &ScalarCowEnum::BigInteger(y)) => x.partial_cmp(&y),
+        (&ScalarCowEnum::Integer(x), &ScalarCowEnum::BigInteger(y)) => {
+            i128::from(x).partial_cmp(&y)
+        }
+        (&ScalarCowEnum::BigInteger(x), &ScalarCowEnum::Integer(y)) => {
+            x.partial_cmp(&i128::from(y))
+        }
+        (&ScalarCowEnum::BigInteger(x), &ScalarCowEnum::Float(y)) => (x as f64).partial_cmp(&y),
+        (&ScalarCowEnum::Float(x), &ScalarCowEnum::BigInteger(y)) => x.partial_cmp(&(y as f64)),
         (&ScalarCowEnum::Bool(x), &ScalarCowEnum::Bool(y)) => x.partial_cmp(&y),
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::DateTime(y)) => x.partial_cmp(&y),
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::Date(y)) => x.partial_cmp(&y),
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::Date(y)) => x.partial_cmp(&x.with_date(y)),
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::DateTime(y)) => y.with_date(x).partial_cmp(&y),
+        (&ScalarCowEnum::Duration(x), &ScalarCowEnum::Duration(y)) => x.partial_cmp(&y),
+        (ScalarCowEnum::Bytes(x), ScalarCowEnum::Bytes(y)) => x.partial_cmp(y),
         (&ScalarCowEnum::Str(ref x), &ScalarCowEnum::Str(ref y)) => x.partial_cmp(y),
+        (ScalarCowEnum::Custom(_), _) | (_, ScalarCowEnum::Custom(_)) => custom_cmp(lhs, rhs),
+        #[cfg(feature = "decimal")]
+        (ScalarCowEnum::Decimal(_), _) | (_, ScalarCowEnum::Decimal(_)) => decimal_cmp(lhs, rhs),
         _ => None,
     }
 }
 
+/// Ordering involving a decimal, exact when both sides fit in one.
+#[cfg(feature = "decimal")]
+fn decimal_cmp<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> Option<Ordering> {
+    if !lhs.is_number() || !rhs.is_number() {
+        return None;
+    }
+    match (lhs.to_number_decimal(), rhs.to_number_decimal()) {
+        (Some(x), Some(y)) => x.partial_cmp(&y),
+        // Numbers beyond the range of a decimal.
+        _ => lhs.to_float()?.partial_cmp(&rhs.to_float()?),
+    }
+}
+
+/// Equality involving a custom scalar, in the order documented by `ScalarValue`.
+fn custom_eq<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> bool {
+    let hook = |x: &ScalarCow<'s>, other: &ScalarCow<'s>| match x.0 {
+        ScalarCowEnum::Custom(ref x) => x.0.eq_scalar(other),
+        _ => None,
+    };
+    hook(lhs, rhs)
+        .or_else(|| hook(rhs, lhs))
+        .unwrap_or_else(|| match (&lhs.0, &rhs.0) {
+            (_, &ScalarCowEnum::Bool(b)) | (&ScalarCowEnum::Bool(b), _) => b,
+            _ => lhs.to_kstr() == rhs.to_kstr(),
+        })
+}
+
+/// Ordering involving a custom scalar, in the order documented by `ScalarValue`.
+fn custom_cmp<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> Option<Ordering> {
+    let hook = |x: &ScalarCow<'s>, other: &ScalarCow<'s>| match x.0 {
+        ScalarCowEnum::Custom(ref x) => x.0.cmp_scalar(other),
+        _ => None,
+    };
+    hook(lhs, rhs).or_else(|| hook(rhs, lhs).map(Ordering::reverse))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1061,4 +1734,135 @@ mod test {
         assert_eq!(empty, TRUE);
         assert!(empty.query_state(State::Truthy));
     }
+
+    #[test]
+    fn big_integers() {
+        let id: ScalarCow<'_> = 18_446_744_073_709_551_615u64.into();
+        assert_eq!(id.to_kstr(), "18446744073709551615");
+        assert_eq!(id.to_integer(), None);
+        assert_eq!(id.to_big_integer(), Some(18_446_744_073_709_551_615));
+
+        let small: ScalarCow<'_> = 42i64.into();
+        assert_eq!(small.to_integer(), Some(42));
+
+        let negative: ScalarCow<'_> = (-9_000_000_000i64).into();
+        assert!(negative < small);
+        assert!(small < id);
+        assert!(negative < ScalarCow::new(0.5));
+        assert_eq!(
+            ScalarCow::new(1i128 << 40),
+            ScalarCow::new((1u64 << 40) as f64)
+        );
+    }
+
+    #[test]
+    fn big_integers_round_trip() {
+        let id: Scalar = 18_446_744_073_709_551_615u64.into();
+        let yaml = serde_yaml::to_string(&id).unwrap();
+        let round_trip: Scalar = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(round_trip.to_big_integer(), id.to_big_integer());
+
+        let negative: Scalar = (-9_000_000_000i64).into();
+        let yaml = serde_yaml::to_string(&negative).unwrap();
+        let round_trip: Scalar = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(round_trip.to_big_integer(), Some(-9_000_000_000));
+    }
+
+    #[test]
+    fn bytes_deserialize() {
+        use serde::de::value::{BytesDeserializer, Error};
+        use serde::Deserialize;
+
+        let bytes = Scalar::deserialize(BytesDeserializer::<Error>::new(b"\x00\xff")).unwrap();
+        assert_eq!(bytes.as_bytes(), Some(&b"\x00\xff"[..]));
+
+        let text: Scalar = serde_yaml::from_str("abc").unwrap();
+        assert_eq!(text.as_bytes(), None);
+        assert_eq!(text, ScalarCow::new("abc"));
+
+        let plain: Vec<u8> = crate::from_value(&Value::scalar(vec![1u8, 2])).unwrap();
+        assert_eq!(plain, vec![1, 2]);
+    }
+
+    #[test]
+    fn bytes_serialize() {
+        let bytes = Value::scalar(vec![0xcau8, 0xfe]);
+        assert_eq!(crate::to_value(&bytes).unwrap(), bytes);
+
+        // Without `liquid_value::bytes`, byte buffers stay sequences.
+        let plain = crate::to_value(&vec![1u8, 2]).unwrap();
+        assert_eq!(
+            plain,
+            Value::Array(vec![Value::scalar(1), Value::scalar(2)])
+        );
+    }
+
+    #[test]
+    fn unsigned_integers_widen() {
+        let max = crate::to_scalar(&u32::MAX).unwrap();
+        assert_eq!(max.to_big_integer(), Some(4_294_967_295));
+        assert_eq!(max.to_integer(), None);
+    }
+
+    #[derive(Debug)]
+    struct Money {
+        cents: i64,
+    }
+
+    impl ScalarValue for Money {
+        fn type_name(&self) -> &'static str {
+            "money"
+        }
+
+        fn to_kstr(&self) -> KStringCow<'_> {
+            format!("{}.{:02}", self.cents / 100, self.cents % 100).into()
+        }
+
+        fn eq_scalar(&self, other: &ScalarCow<'_>) -> Option<bool> {
+            other.to_float().map(|x| (x * 100.0) as i64 == self.cents)
+        }
+
+        fn cmp_scalar(&self, other: &ScalarCow<'_>) -> Option<Ordering> {
+            other
+                .to_float()
+                .and_then(|x| (self.cents as f64).partial_cmp(&(x * 100.0)))
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn custom_comparisons() {
+        let price = Scalar::custom(Money { cents: 1050 });
+        assert_eq!(price, ScalarCow::new(10.5));
+        assert_eq!(ScalarCow::new(10.5), price);
+        assert!(price < ScalarCow::new(11));
+        assert!(ScalarCow::new(11) > price);
+        assert_eq!(price, ScalarCow::new(true));
+        assert_eq!(price, ScalarCow::new("10.50"));
+        assert_eq!(price.partial_cmp(&ScalarCow::new("cheap")), None);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimals_are_exact() {
+        let price: Scalar = "12.50".parse::<Decimal>().unwrap().into();
+        assert_eq!(price.to_kstr(), "12.50");
+        assert_eq!(price, ScalarCow::new(12.5));
+        assert_eq!(ScalarCow::new(12.5), price);
+        assert!(price < ScalarCow::new(13));
+        assert!(ScalarCow::new(1i128 << 100) > price);
+        assert_eq!(price, ScalarCow::new(true));
+        assert_ne!(price, ScalarCow::new("12.50"));
+        assert_eq!(
+            ScalarCow::new(0.1).to_decimal(),
+            "0.1".parse::<Decimal>().ok()
+        );
+
+        let yaml = serde_yaml::to_string(&price).unwrap();
+        let round_trip: Scalar = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(round_trip.to_decimal(), price.to_decimal());
+    }
 }
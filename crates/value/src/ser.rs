@@ -5,12 +5,15 @@ use num_traits;
 use serde::ser::Impossible;
 use serde::{self, Serialize};
 
+use super::bytes::BYTES_TOKEN;
 use super::Object;
 use super::Scalar;
 use super::Value;
 
 /// Convert a `T` into `liquid_value::Value`.
 ///
+/// For hot conversion paths, see `IntoValue`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -111,6 +114,10 @@ impl serde::Serializer for ValueSerializer {
         ScalarSerializer.serialize_i64(value).map(Value::Scalar)
     }
 
+    fn serialize_i128(self, value: i128) -> Result<Value, SerError> {
+        ScalarSerializer.serialize_i128(value).map(Value::Scalar)
+    }
+
     #[inline]
     fn serialize_u8(self, value: u8) -> Result<Value, SerError> {
         ScalarSerializer.serialize_u8(value).map(Value::Scalar)
@@ -131,6 +138,10 @@ impl serde::Serializer for ValueSerializer {
         ScalarSerializer.serialize_u64(value).map(Value::Scalar)
     }
 
+    fn serialize_u128(self, value: u128) -> Result<Value, SerError> {
+        ScalarSerializer.serialize_u128(value).map(Value::Scalar)
+    }
+
     #[inline]
     fn serialize_f32(self, value: f32) -> Result<Value, SerError> {
         ScalarSerializer.serialize_f32(value).map(Value::Scalar)
@@ -181,12 +192,15 @@ impl serde::Serializer for ValueSerializer {
     #[inline]
     fn serialize_newtype_struct<T: ?Sized>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Value, SerError>
     where
         T: Serialize,
     {
+        if name == BYTES_TOKEN {
+            return value.serialize(ScalarSerializer).map(Value::Scalar);
+        }
         value.serialize(ValueSerializer)
     }
 
@@ -255,9 +269,9 @@ impl serde::Serializer for ValueSerializer {
         })
     }
 
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerError> {
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, SerError> {
         Ok(SerializeMap::Map {
-            map: Object::new(),
+            map: Object::with_capacity(len.unwrap_or(0)),
             next_key: None,
             other: std::marker::PhantomData,
         })
@@ -276,11 +290,11 @@ impl serde::Serializer for ValueSerializer {
         _name: &'static str,
         _variant_index: u32,
         variant: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStructVariant, SerError> {
         Ok(SerializeStructVariant {
             name: KString::from_static(variant),
-            map: Object::new(),
+            map: Object::with_capacity(len),
             other: std::marker::PhantomData,
         })
     }
@@ -522,9 +536,9 @@ impl serde::Serializer for ObjectSerializer {
         })
     }
 
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerError> {
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, SerError> {
         Ok(SerializeMap::Map {
-            map: Object::new(),
+            map: Object::with_capacity(len.unwrap_or(0)),
             next_key: None,
             other: std::marker::PhantomData,
         })
@@ -543,11 +557,11 @@ impl serde::Serializer for ObjectSerializer {
         _name: &'static str,
         _variant_index: u32,
         variant: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStructVariant, SerError> {
         Ok(SerializeStructVariant {
             name: KString::from_static(variant),
-            map: Object::new(),
+            map: Object::with_capacity(len),
             other: std::marker::PhantomData,
         })
     }
@@ -908,7 +922,11 @@ impl serde::Serializer for ScalarSerializer {
     }
 
     fn serialize_i64(self, value: i64) -> Result<Scalar, SerError> {
-        serialize_as_i32(value)
+        Ok(Scalar::new(value))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Scalar, SerError> {
+        Ok(Scalar::new(value))
     }
 
     #[inline]
@@ -923,12 +941,18 @@ impl serde::Serializer for ScalarSerializer {
 
     #[inline]
     fn serialize_u32(self, value: u32) -> Result<Scalar, SerError> {
-        serialize_as_i32(value)
+        Ok(Scalar::new(u64::from(value)))
     }
 
     #[inline]
     fn serialize_u64(self, value: u64) -> Result<Scalar, SerError> {
-        serialize_as_i32(value)
+        Ok(Scalar::new(value))
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<Scalar, SerError> {
+        num_traits::cast::cast::<u128, i128>(value)
+            .map(Scalar::new)
+            .ok_or_else(|| SerError(liquid_error::Error::with_msg("Cannot fit number")))
     }
 
     #[inline]
@@ -953,8 +977,8 @@ impl serde::Serializer for ScalarSerializer {
         Ok(Scalar::new(KString::from_ref(value)))
     }
 
-    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Err(scalar_must_be_a_string())
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Scalar::new(value.to_vec()))
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
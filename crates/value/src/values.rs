@@ -273,6 +273,12 @@ impl PartialEq<crate::Date> for Value {
     }
 }
 
+impl PartialEq<crate::Duration> for Value {
+    fn eq(&self, other: &crate::Duration) -> bool {
+        crate::value_eq(self.as_view(), other)
+    }
+}
+
 impl PartialEq<str> for Value {
     fn eq(&self, other: &str) -> bool {
         let other = KStringCow::from_ref(other);
@@ -174,6 +174,12 @@ impl<'v> PartialEq<crate::Date> for ValueViewCmp<'v> {
     }
 }
 
+impl<'v> PartialEq<crate::Duration> for ValueViewCmp<'v> {
+    fn eq(&self, other: &crate::Duration) -> bool {
+        crate::value_eq(self.0, other)
+    }
+}
+
 impl<'v> PartialEq<str> for ValueViewCmp<'v> {
     fn eq(&self, other: &str) -> bool {
         let other = KStringCow::from_ref(other);
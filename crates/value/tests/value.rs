@@ -156,3 +156,53 @@ pub fn deserialize_object() {
     let expected = liquid_value::Value::Object(expected);
     assert_eq!(actual, expected);
 }
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rect { width: i32, height: i32 },
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Drawing {
+    title: String,
+    shapes: Vec<Shape>,
+    author: Option<String>,
+}
+
+#[test]
+pub fn from_value_struct() {
+    let value = liquid_value::value!({
+        "title": "Sketch",
+        "shapes": [
+            "Point",
+            {"Circle": 1.5},
+            {"Rect": {"width": 2, "height": 3}}
+        ],
+        "author": nil
+    });
+    let actual: Drawing = liquid_value::from_value(&value).unwrap();
+    assert_eq!(
+        actual,
+        Drawing {
+            title: "Sketch".to_owned(),
+            shapes: vec![
+                Shape::Point,
+                Shape::Circle(1.5),
+                Shape::Rect {
+                    width: 2,
+                    height: 3
+                },
+            ],
+            author: None,
+        }
+    );
+}
+
+#[test]
+pub fn from_value_mismatch() {
+    let value = liquid_value::value!({"title": 5, "shapes": []});
+    let actual: Result<Drawing, _> = liquid_value::from_value(&value);
+    assert!(actual.is_err());
+}
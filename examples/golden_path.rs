@@ -0,0 +1,54 @@
+//! Rendering an order confirmation, first in one call and then with a reusable parser.
+//!
+//! Run with `cargo run --example golden_path`.
+
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Item {
+    name: String,
+    quantity: u32,
+    price: f64,
+}
+
+#[derive(Serialize)]
+struct Order {
+    customer: String,
+    items: Vec<Item>,
+}
+
+const CONFIRMATION: &str = "\
+Thanks for your order, {{ customer }}!
+{% for item in items %}
+- {{ item.quantity }} x {{ item.name }} at {{ item.price | number_format: 2 }}
+{%- endfor %}
+";
+
+fn main() -> Result<(), liquid::Error> {
+    let order = Order {
+        customer: "Ann".to_owned(),
+        items: vec![
+            Item {
+                name: "Tea".to_owned(),
+                quantity: 2,
+                price: 3.5,
+            },
+            Item {
+                name: "Cake".to_owned(),
+                quantity: 1,
+                price: 4.25,
+            },
+        ],
+    };
+
+    // One-off renders, like in scripts and tests, don't need a parser of their own.
+    println!("{}", liquid::quick::render(CONFIRMATION, &order)?);
+
+    // Applications rendering the same template repeatedly parse it once, up front.
+    let parser = liquid::ParserBuilder::with_stdlib().build()?;
+    let template = parser.parse(CONFIRMATION)?;
+    let globals = liquid::to_object(&order)?;
+    println!("{}", template.render(&globals)?);
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use liquid_core::compiler;
+#[cfg(feature = "stdlib")]
+use liquid_core::error::{Error, Result};
+use liquid_core::interpreter;
+use liquid_core::Renderable;
+
+#[cfg(feature = "stdlib")]
+use super::Condition;
+use super::Expression;
+use super::Template;
+#[cfg(feature = "stdlib")]
+use liquid_lib::stdlib;
+
+/// Assembles a `Template` in code, without parsing any template text.
+///
+/// # Examples
+///
+/// ```
+/// use liquid::{Condition, Expression, TemplateBuilder};
+///
+/// let item = TemplateBuilder::new()
+///     .output(Expression::variable("item", &[]))
+///     .text(" ");
+/// let template = TemplateBuilder::new()
+///     .text("Items: ")
+///     .for_block("item", Expression::variable("items", &[]), item, None)
+///     .unwrap()
+///     .if_block(
+///         Condition::exists(Expression::variable("more", &[])).unwrap(),
+///         TemplateBuilder::new().text("..."),
+///         None,
+///     )
+///     .build();
+///
+/// let globals = liquid::object!({"items": [1, 2], "more": true});
+/// assert_eq!(template.render(&globals).unwrap(), "Items: 1 2 ...");
+/// ```
+#[derive(Debug, Default)]
+pub struct TemplateBuilder {
+    elements: Vec<Box<dyn Renderable>>,
+}
+
+impl TemplateBuilder {
+    /// Create an empty template.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append text, rendered as-is.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.elements.push(Box::new(compiler::Text::new(text)));
+        self
+    }
+
+    /// Append an output, like `{{ }}`.
+    pub fn output(mut self, expression: Expression) -> Self {
+        self.elements.push(Box::new(expression.expression));
+        self
+    }
+
+    /// Append an `{% if %}` block.
+    #[cfg(feature = "stdlib")]
+    pub fn if_block(
+        mut self,
+        condition: Condition,
+        if_true: TemplateBuilder,
+        if_false: Option<TemplateBuilder>,
+    ) -> Self {
+        let block = condition.condition.into_block(
+            if_true.into_block(),
+            if_false.map(TemplateBuilder::into_block),
+        );
+        self.elements.push(block);
+        self
+    }
+
+    /// Append a `{% for %}` block, rendering `item_template` for each of `items` as `var_name`,
+    /// and `else_template` when there are none.
+    ///
+    /// `items` can't have filters, like in `{% for %}`.
+    #[cfg(feature = "stdlib")]
+    pub fn for_block(
+        mut self,
+        var_name: &str,
+        items: Expression,
+        item_template: TemplateBuilder,
+        else_template: Option<TemplateBuilder>,
+    ) -> Result<Self> {
+        let items = items.into_unfiltered()?;
+        let block = stdlib::for_loop(
+            var_name,
+            items,
+            item_template.into_block(),
+            else_template.map(TemplateBuilder::into_block),
+        );
+        self.elements.push(block);
+        Ok(self)
+    }
+
+    /// Append an element created by a plugin.
+    pub fn element(mut self, element: Box<dyn Renderable>) -> Self {
+        self.elements.push(element);
+        self
+    }
+
+    /// Create the `Template`.
+    pub fn build(self) -> Template {
+        Template {
+            template: self.into_block(),
+            partials: None,
+            text: None,
+            counts_tags: false,
+            records_coverage: false,
+        }
+    }
+
+    fn into_block(self) -> interpreter::Template {
+        interpreter::Template::new(self.elements)
+    }
+}
+
+#[cfg(feature = "stdlib")]
+impl Expression {
+    /// Reject filters, for places that only accept a value, like `{% if %}` and `{% for %}`.
+    pub(crate) fn into_unfiltered(self) -> Result<interpreter::Expression> {
+        let display = self.expression.to_string();
+        let (entry, filters) = self.expression.into_parts();
+        if filters.is_empty() {
+            Ok(entry)
+        } else {
+            Error::with_msg("Filters are not supported here")
+                .context("expression", display)
+                .into_err()
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+use liquid_core::error::Result;
+use liquid_core::interpreter;
+use liquid_lib::stdlib;
+
+use super::Expression;
+
+/// A standalone condition, using the syntax of `{% if %}`.
+///
+/// Unlike `{% if %}`, evaluating a condition is strict: unknown variables and ordering values
+/// that can't be compared, like a string and a number, are errors rather than `false`.
+///
+/// Created with `Parser::parse_condition`.
+#[derive(Clone, Debug)]
+pub struct Condition {
+    pub(crate) condition: stdlib::IfCondition,
+}
+
+impl Condition {
+    /// Parse a condition with the standard library's filters.
+    ///
+    /// This builds a new `Parser` on each call.  When parsing many conditions, prefer
+    /// `Parser::parse_condition`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let condition = liquid::Condition::parse("user.plan == 'pro' and cart.total > 50").unwrap();
+    ///
+    /// let globals = liquid::object!({
+    ///     "user": { "plan": "pro" },
+    ///     "cart": { "total": 70 },
+    /// });
+    /// assert!(condition.matches(&globals).unwrap());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self> {
+        crate::ParserBuilder::with_stdlib()
+            .build()?
+            .parse_condition(text)
+    }
+
+    /// A condition that `lh` is truthy.
+    ///
+    /// `lh` can't have filters, like in `{% if %}`.
+    pub fn exists(lh: Expression) -> Result<Self> {
+        let condition = stdlib::IfCondition::exists(lh.into_unfiltered()?);
+        Ok(Self { condition })
+    }
+
+    /// A condition comparing `lh` and `rh` with an operator, like `==` or `contains`.
+    ///
+    /// Neither side can have filters, like in `{% if %}`.
+    pub fn compare(lh: Expression, operator: &str, rh: Expression) -> Result<Self> {
+        let condition =
+            stdlib::IfCondition::compare(lh.into_unfiltered()?, operator, rh.into_unfiltered()?)?;
+        Ok(Self { condition })
+    }
+
+    /// A condition that both `self` and `other` hold.
+    pub fn and(self, other: Self) -> Self {
+        let condition = self.condition.and(other.condition);
+        Self { condition }
+    }
+
+    /// A condition that either `self` or `other` holds.
+    pub fn or(self, other: Self) -> Self {
+        let condition = self.condition.or(other.condition);
+        Self { condition }
+    }
+
+    /// Evaluate the condition, using the given globals.
+    pub fn matches(&self, globals: &dyn crate::ObjectView) -> Result<bool> {
+        let runtime = interpreter::RuntimeBuilder::new()
+            .set_globals(globals)
+            .build();
+        self.condition.evaluate_strict(&runtime)
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.condition)
+    }
+}
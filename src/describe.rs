@@ -0,0 +1,39 @@
+use crate::CompatLevel;
+
+/// The effective configuration of a `Parser`, see `Parser::describe`.
+///
+/// Names are sorted, so descriptions of parsers built the same way compare equal, and with the
+/// `serde` feature they serialize the same, like for logging at startup and diffing across
+/// services.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParserDescription {
+    /// Release the parser behaves like, see `ParserBuilder::compat_level`.
+    pub compat_level: CompatLevel,
+    /// Names of the registered tags.
+    pub tags: Vec<String>,
+    /// Names of the registered blocks.
+    pub blocks: Vec<String>,
+    /// Names of the registered filters.
+    pub filters: Vec<String>,
+    /// Names of the partials available to include.
+    pub partials: Vec<String>,
+    /// Whether keyword operators are accepted, see `ParserBuilder::keyword_aliases`.
+    pub keyword_aliases: bool,
+    /// Whether `#{}` is expanded in literals, see `ParserBuilder::interpolation`.
+    pub interpolation: bool,
+    /// Whether unknown filters are accepted, see `ParserBuilder::defer_unknown_filters`.
+    pub defer_unknown_filters: bool,
+    /// Whether renders can limit tags, see `ParserBuilder::count_tags`.
+    pub count_tags: bool,
+    /// Whether renders can record coverage, see `ParserBuilder::record_coverage`.
+    pub record_coverage: bool,
+    /// See `ParserBuilder::max_template_size`.
+    pub max_template_size: Option<usize>,
+    /// See `ParserBuilder::max_nodes`.
+    pub max_nodes: Option<usize>,
+    /// See `ParserBuilder::max_nesting`.
+    pub max_nesting: Option<usize>,
+    /// See `ParserBuilder::warn_near_limits`.
+    pub warn_percent: Option<usize>,
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+
+use liquid_core::compiler;
+use liquid_core::error::Result;
+use liquid_core::interpreter;
+use liquid_core::value::Scalar;
+use liquid_core::Value;
+
+/// A standalone liquid expression, like the contents of `{{ }}`, including filters.
+///
+/// Created with `Parser::parse_expression`.
+#[derive(Debug)]
+pub struct Expression {
+    pub(crate) expression: compiler::FilterChain,
+}
+
+impl Expression {
+    /// Parse an expression with the standard library's filters.
+    ///
+    /// This builds a new `Parser` on each call.  When parsing many expressions, prefer
+    /// `Parser::parse_expression`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let expression = liquid::Expression::parse("user.name | upcase").unwrap();
+    ///
+    /// let globals = liquid::object!({
+    ///     "user": { "name": "ann" },
+    /// });
+    /// let value = expression.evaluate(&globals).unwrap();
+    /// assert_eq!(value, liquid::value::Value::scalar("ANN"));
+    /// ```
+    #[cfg(feature = "stdlib")]
+    pub fn parse(text: &str) -> Result<Self> {
+        crate::ParserBuilder::with_stdlib()
+            .build()?
+            .parse_expression(text)
+    }
+
+    /// Reference the variable `name`, indexing into it by each of `indexes`.
+    pub fn variable(name: &str, indexes: &[&str]) -> Self {
+        let mut variable = interpreter::Variable::with_literal(Scalar::new(name.to_string()));
+        for index in indexes {
+            variable = variable.push_literal(Scalar::new(index.to_string()));
+        }
+        Self::from_entry(interpreter::Expression::Variable(variable))
+    }
+
+    /// A constant value.
+    pub fn literal(value: Value) -> Self {
+        Self::from_entry(interpreter::Expression::Literal(value))
+    }
+
+    fn from_entry(entry: interpreter::Expression) -> Self {
+        let expression = compiler::FilterChain::new(entry, Vec::new());
+        Self { expression }
+    }
+
+    /// Evaluate the expression, using the given globals.
+    pub fn evaluate(&self, globals: &dyn crate::ObjectView) -> Result<Value> {
+        let runtime = interpreter::RuntimeBuilder::new()
+            .set_globals(globals)
+            .build();
+        let value = self.expression.evaluate(&runtime)?.into_owned();
+        Ok(value)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+use std::sync::Arc;
+
+use liquid_core::compiler::{
+    Filter, FilterArguments, FilterReflection, ParameterReflection, ParseFilter,
+};
+use liquid_core::error::{Error, Result};
+use liquid_core::interpreter::{Expression, Runtime};
+use liquid_core::value::{from_value, to_value, Value, ValueView};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+type BoxedFn = Arc<dyn Fn(&dyn ValueView, Value) -> Result<Value> + Send + Sync>;
+
+/// Filter backed by a plain Rust function, see `ParserBuilder::filter_fn`.
+#[derive(Clone)]
+pub(crate) struct FnFilterParser {
+    name: String,
+    f: BoxedFn,
+}
+
+impl FnFilterParser {
+    pub(crate) fn new<F, I, A, R>(name: String, f: F) -> Self
+    where
+        F: Fn(I, A) -> Result<R> + Send + Sync + 'static,
+        I: DeserializeOwned,
+        A: DeserializeOwned,
+        R: Serialize,
+    {
+        let f = move |input: &dyn ValueView, args: Value| {
+            let input = from_value(&input.to_value()).map_err(|e| {
+                Error::with_msg("Invalid input").context("cause", e.message().to_owned())
+            })?;
+            let args = from_value(&args).map_err(|e| {
+                Error::with_msg("Invalid argument").context("cause", e.message().to_owned())
+            })?;
+            to_value(&f(input, args)?)
+        };
+        Self {
+            name,
+            f: Arc::new(f),
+        }
+    }
+}
+
+impl FilterReflection for FnFilterParser {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        ""
+    }
+
+    fn positional_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+
+    fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+}
+
+impl ParseFilter for FnFilterParser {
+    fn parse(&self, arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+        let mut keyword = arguments.keyword;
+        if let Some((name, _)) = keyword.next() {
+            return Err(Error::with_msg("Unexpected named argument")
+                .context("argument", name.to_owned())
+                .context("filter", self.name.clone()));
+        }
+        let args = arguments.positional.collect();
+        Ok(Box::new(FnFilter {
+            name: self.name.clone(),
+            args,
+            f: self.f.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+struct FnFilter {
+    name: String,
+    args: Vec<Expression>,
+    f: BoxedFn,
+}
+
+impl Filter for FnFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args: Result<Vec<_>> = self
+            .args
+            .iter()
+            .map(|arg| arg.evaluate(runtime).map(|v| v.into_owned()))
+            .collect();
+        (self.f)(input, Value::array(args?))
+    }
+}
+
+impl fmt::Debug for FnFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnFilter")
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+impl fmt::Display for FnFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{}", sep, arg)?;
+        }
+        Ok(())
+    }
+}
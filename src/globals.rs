@@ -0,0 +1,184 @@
+use std::fmt;
+
+use kstring::KString;
+use liquid_core::error::{Error, Result};
+use liquid_core::{Object, Value};
+use serde::Serialize;
+
+/// Assemble the globals of a render.
+///
+/// # Examples
+///
+/// ```rust
+/// #[derive(serde::Serialize)]
+/// struct User {
+///     name: &'static str,
+/// }
+///
+/// let defaults = liquid::object!({"site": "Example", "theme": "light"});
+/// let globals = liquid::Globals::builder()
+///     .insert("user", &User { name: "Ann" })
+///     .insert_lazy("products", || Ok(vec!["tea", "cake"]))
+///     .merge(defaults)
+///     .build()
+///     .unwrap();
+///
+/// let template = liquid::ParserBuilder::with_stdlib()
+///     .build()
+///     .unwrap()
+///     .parse("{{ user.name }} on {{ site }}: {{ products | join: ', ' }}")
+///     .unwrap();
+/// assert_eq!(template.render(&globals).unwrap(), "Ann on Example: tea, cake");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Globals;
+
+impl Globals {
+    /// Start assembling globals.
+    pub fn builder() -> GlobalsBuilder {
+        GlobalsBuilder::default()
+    }
+}
+
+enum Entry {
+    Value(KString, Value),
+    Lazy(KString, Box<dyn FnOnce() -> Result<Value>>),
+    Defaults(Object),
+    Error(KString, Error),
+}
+
+/// Builder for globals, see `Globals::builder`.
+///
+/// Conversion errors are collected and reported together by `build`, so every insert can be
+/// chained without checking each one.
+#[derive(Default)]
+pub struct GlobalsBuilder {
+    entries: Vec<Entry>,
+}
+
+impl GlobalsBuilder {
+    /// Set `key` to `value`, replacing any earlier value.
+    pub fn insert<K, T>(mut self, key: K, value: &T) -> Self
+    where
+        K: Into<KString>,
+        T: Serialize + ?Sized,
+    {
+        let key = key.into();
+        let entry = match liquid_core::to_value(value) {
+            Ok(value) => Entry::Value(key, value),
+            Err(error) => Entry::Error(key, error),
+        };
+        self.entries.push(entry);
+        self
+    }
+
+    /// Set `key` to the value returned by `f`, replacing any earlier value.
+    ///
+    /// `f` is called by `build`, so the work is skipped when an earlier entry already failed.
+    pub fn insert_lazy<K, F, T>(mut self, key: K, f: F) -> Self
+    where
+        K: Into<KString>,
+        F: FnOnce() -> Result<T> + 'static,
+        T: Serialize,
+    {
+        let f = move || f().and_then(|value| liquid_core::to_value(&value));
+        self.entries.push(Entry::Lazy(key.into(), Box::new(f)));
+        self
+    }
+
+    /// Add the entries of `defaults` that aren't set yet.
+    pub fn merge(mut self, defaults: Object) -> Self {
+        self.entries.push(Entry::Defaults(defaults));
+        self
+    }
+
+    /// Build the globals, or report every entry that failed to convert.
+    pub fn build(self) -> Result<Object> {
+        let mut globals = Object::with_capacity(self.entries.len());
+        let mut errors = Vec::new();
+        for entry in self.entries {
+            match entry {
+                Entry::Value(key, value) => {
+                    globals.insert(key, value);
+                }
+                Entry::Lazy(key, f) => {
+                    if !errors.is_empty() {
+                        continue;
+                    }
+                    match f() {
+                        Ok(value) => {
+                            globals.insert(key, value);
+                        }
+                        Err(error) => errors.push((key, error)),
+                    }
+                }
+                Entry::Defaults(defaults) => {
+                    for (key, value) in defaults {
+                        globals.entry(key).or_insert(value);
+                    }
+                }
+                Entry::Error(key, error) => errors.push((key, error)),
+            }
+        }
+
+        if errors.is_empty() {
+            return Ok(globals);
+        }
+        let error = errors.into_iter().fold(
+            Error::with_msg("Failed to build globals"),
+            |error, (key, cause)| error.context(key, cause.message().to_owned()),
+        );
+        Err(error)
+    }
+}
+
+impl fmt::Debug for GlobalsBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Entry::Value(key, _) | Entry::Lazy(key, _) | Entry::Error(key, _) => Some(key),
+                Entry::Defaults(_) => None,
+            })
+            .collect();
+        f.debug_struct("GlobalsBuilder")
+            .field("keys", &keys)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn merge_keeps_inserted() {
+        let globals = Globals::builder()
+            .insert("theme", "dark")
+            .merge(liquid_core::object!({"theme": "light", "site": "Example"}))
+            .build()
+            .unwrap();
+        assert_eq!(
+            globals,
+            liquid_core::object!({"theme": "dark", "site": "Example"})
+        );
+    }
+
+    #[test]
+    fn build_reports_every_error() {
+        let mut not_an_object = BTreeMap::new();
+        not_an_object.insert(vec![1], 1);
+
+        let error = Globals::builder()
+            .insert("first", &not_an_object)
+            .insert("ok", &5)
+            .insert("second", &not_an_object)
+            .build()
+            .unwrap_err();
+        assert!(error.context_value("first").is_some());
+        assert!(error.context_value("second").is_some());
+    }
+}
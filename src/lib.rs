@@ -21,23 +21,75 @@
 //! assert_eq!(output, "Liquid! 2".to_string());
 //! ```
 
+mod builder;
+#[cfg(feature = "stdlib")]
+mod condition;
+mod describe;
+mod expression;
+#[cfg(feature = "serde")]
+mod fn_filter;
+#[cfg(feature = "serde")]
+mod globals;
+#[cfg(feature = "memory-profiling")]
+mod memory;
 mod parser;
+pub mod prelude;
+#[cfg(all(feature = "stdlib", feature = "serde"))]
+pub mod quick;
 mod reflection;
+#[cfg(feature = "scripting")]
+mod script;
 mod template;
 
+#[doc(hidden)]
+pub use kstring;
 pub use liquid_core::partials;
 #[doc(hidden)]
 pub use liquid_core::value;
+#[doc(hidden)]
+pub use once_cell;
 
+pub use crate::builder::*;
+#[cfg(feature = "stdlib")]
+pub use crate::condition::*;
+pub use crate::describe::*;
+pub use crate::expression::*;
+#[cfg(feature = "serde")]
+pub use crate::globals::*;
+#[cfg(feature = "memory-profiling")]
+pub use crate::memory::*;
 pub use crate::parser::*;
 pub use crate::reflection::*;
 pub use crate::template::*;
+pub use liquid_core::compiler::contains_liquid;
+pub use liquid_core::compiler::CompileReport;
+pub use liquid_core::compiler::LimitWarning;
+pub use liquid_core::from_value;
+pub use liquid_core::interpreter::Coverage;
+pub use liquid_core::interpreter::FunctionRegistry;
+pub use liquid_core::interpreter::Loader;
+pub use liquid_core::interpreter::MissingFilterHandler;
+pub use liquid_core::interpreter::UndefinedPolicy;
+pub use liquid_core::interpreter::{
+    CsvFieldEncoder, HtmlEncoder, LatexEncoder, OutputEncoder, ShellWordEncoder, XmlEncoder,
+};
+pub use liquid_core::interpreter::{FetchSandbox, HttpFetcher};
 pub use liquid_core::object;
 pub use liquid_core::to_object;
+#[cfg(feature = "decimal")]
+pub use liquid_core::value::Decimal;
+pub use liquid_core::value::LazyValue;
+pub use liquid_core::value::ObjectKeys;
+pub use liquid_core::value::{CyclePolicy, DateFormats, DisplayPolicy};
 pub use liquid_core::Error;
+pub use liquid_core::IntoValue;
 pub use liquid_core::Object;
 pub use liquid_core::{ObjectView, ValueView};
 pub use liquid_derive::{ObjectView, ValueView};
+#[cfg(feature = "fetch")]
+pub use liquid_lib::fetch::FetchTag;
+#[cfg(feature = "stdlib")]
+pub use liquid_lib::Locale;
 
 #[macro_use]
 extern crate doc_comment;
@@ -0,0 +1,116 @@
+//! Allocation statistics for renders, see `CountingAllocator`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Global allocator that counts the allocations of each thread, for
+/// `RenderResult::allocations`.
+///
+/// It forwards to the system allocator.  Install it in the binary being profiled:
+///
+/// ```rust
+/// #[global_allocator]
+/// static ALLOCATOR: liquid::CountingAllocator = liquid::CountingAllocator;
+///
+/// let template = liquid::ParserBuilder::with_stdlib()
+///     .build().unwrap()
+///     .parse("{% for i in (1..3) %}{{ i }}{% endfor %}").unwrap();
+/// let options = liquid::RenderOptions::new();
+/// let result = template.render_with_result(&liquid::Object::new(), &options).unwrap();
+/// let allocations = result.allocations().unwrap();
+/// assert!(0 < allocations.allocations);
+/// assert!(0 < allocations.peak_bytes);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Copy, Clone)]
+struct Counters {
+    allocations: usize,
+    bytes: usize,
+    peak_bytes: usize,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            peak_bytes: 0,
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    // Counting is skipped while the thread is being torn down.
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        if allocated != 0 {
+            c.allocations += 1;
+        }
+        c.bytes = (c.bytes + allocated).saturating_sub(freed);
+        c.peak_bytes = c.peak_bytes.max(c.bytes);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        record(layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Allocations made by the current thread during a render.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    /// Number of allocations and reallocations.
+    pub allocations: usize,
+    /// Most bytes allocated at once, beyond what was allocated before the render.
+    pub peak_bytes: usize,
+}
+
+/// Runs `f`, measuring its allocations if `CountingAllocator` is installed.
+pub(crate) fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocationStats>) {
+    let before = COUNTERS.with(|counters| {
+        let before = counters.get();
+        counters.set(Counters {
+            peak_bytes: before.bytes,
+            ..before
+        });
+        before
+    });
+    let result = f();
+    let after = COUNTERS.with(|counters| {
+        let after = counters.get();
+        counters.set(Counters {
+            peak_bytes: after.peak_bytes.max(before.peak_bytes),
+            ..after
+        });
+        after
+    });
+    let stats = AllocationStats {
+        allocations: after.allocations - before.allocations,
+        peak_bytes: after.peak_bytes.saturating_sub(before.bytes),
+    };
+    let stats = if INSTALLED.load(Ordering::Relaxed) {
+        Some(stats)
+    } else {
+        None
+    };
+    (result, stats)
+}
@@ -1,13 +1,23 @@
+use std::borrow;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::prelude::Read;
 use std::path;
 use std::sync;
 
 use liquid_core::compiler;
-use liquid_core::error::{Result, ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::error::{Error, Result, ResultLiquidExt, ResultLiquidReplaceExt};
 use liquid_core::interpreter;
 
+use super::CompileReport;
+#[cfg(feature = "stdlib")]
+use super::Condition;
+use super::Expression;
+use super::ParserDescription;
 use super::Template;
+use super::TypedTemplate;
+#[cfg(feature = "serde")]
+use crate::fn_filter::FnFilterParser;
 use crate::reflection;
 use liquid_core::partials;
 #[cfg(feature = "stdlib")]
@@ -18,6 +28,110 @@ use liquid_lib::stdlib;
 /// This is the recommended policy.  See `liquid::partials` for more options.
 pub type Partials = partials::EagerCompiler<partials::InMemorySource>;
 
+/// Which release of this crate a `Parser` behaves like, so upgrading the crate doesn't change
+/// how existing templates render.
+///
+/// Filters and tags that the standard library gained after the chosen release are left out, as
+/// if unknown, and filters whose behavior changed since are replaced by their earlier
+/// implementation.  Plugins registered with `ParserBuilder::filter`, `tag` or `block` are kept as
+/// they are, even under a standard library name.
+///
+/// Syntax that changes the output of existing templates, like `ParserBuilder::interpolation`, is
+/// opt-in at every level.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CompatLevel {
+    /// The standard library of liquid 0.19.
+    V0_19,
+    /// Adds filters like `where`, `flatten` and `number_format`, and tags like `constant`
+    /// and `call`.
+    ///
+    /// Changes since 0.19:
+    /// - `split` drops trailing empty strings, and splits on runs of whitespace for `" "`.
+    /// - `slice` returns nothing for an offset outside of the input, and counts characters
+    ///   rather than bytes.
+    /// - `size` and `truncate` count characters rather than bytes.
+    /// - `date` accepts more formats, and converts integers as epoch timestamps.
+    V0_20,
+    /// The newest behavior, changing as the crate is upgraded.
+    Latest,
+}
+
+impl Default for CompatLevel {
+    fn default() -> Self {
+        CompatLevel::Latest
+    }
+}
+
+/// A platform whose flavor of liquid a `Parser` targets, see `ParserBuilder::dialect`.
+#[cfg(feature = "stdlib")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// The standard library only.
+    Liquid,
+    /// Jekyll, with its `include` tag taking parameters, array filters like `push`, and content
+    /// helpers like `slugify` and `toc`.
+    #[cfg(feature = "jekyll")]
+    Jekyll,
+    /// Shopify, with filters like `money` and `pluralize`.
+    #[cfg(feature = "shopify")]
+    Shopify,
+}
+
+/// Standard library filters added after 0.19, with the level they are available from.
+#[cfg(feature = "stdlib")]
+const STDLIB_FILTERS_SINCE: &[(&str, CompatLevel)] = &[
+    ("average", CompatLevel::V0_20),
+    ("base64_encode", CompatLevel::V0_20),
+    ("date_add", CompatLevel::V0_20),
+    ("date_diff", CompatLevel::V0_20),
+    ("emojify", CompatLevel::V0_20),
+    ("flatten", CompatLevel::V0_20),
+    ("hex", CompatLevel::V0_20),
+    ("humanize_duration", CompatLevel::V0_20),
+    ("in_time_zone", CompatLevel::V0_20),
+    ("index_of", CompatLevel::V0_20),
+    ("inline_css", CompatLevel::V0_20),
+    ("last_index_of", CompatLevel::V0_20),
+    ("max_by", CompatLevel::V0_20),
+    ("mime_header", CompatLevel::V0_20),
+    ("min_by", CompatLevel::V0_20),
+    ("number_format", CompatLevel::V0_20),
+    ("qr_code", CompatLevel::V0_20),
+    ("quoted_printable", CompatLevel::V0_20),
+    ("reject", CompatLevel::V0_20),
+    ("where", CompatLevel::V0_20),
+];
+
+/// Standard library tags added after 0.19, with the level they are available from.
+#[cfg(feature = "stdlib")]
+const STDLIB_TAGS_SINCE: &[(&str, CompatLevel)] = &[
+    ("cache_control", CompatLevel::V0_20),
+    ("call", CompatLevel::V0_20),
+    ("constant", CompatLevel::V0_20),
+];
+
+/// The 0.19 implementation of standard library filters whose behavior changed in 0.20.
+#[cfg(feature = "stdlib")]
+fn stdlib_filters_v0_19() -> Vec<Box<dyn compiler::ParseFilter>> {
+    vec![
+        stdlib::v0_19::Date.into(),
+        stdlib::v0_19::Size.into(),
+        stdlib::v0_19::Slice.into(),
+        stdlib::v0_19::Split.into(),
+        stdlib::v0_19::Truncate.into(),
+    ]
+}
+
+/// Names of the plugins registered through `ParserBuilder::block`, `tag` and `filter`, rather
+/// than by the standard library, which `CompatLevel` leaves alone.
+#[derive(Default)]
+struct CustomNames {
+    blocks: HashSet<String>,
+    tags: HashSet<String>,
+    filters: HashSet<String>,
+}
+
 pub struct ParserBuilder<P = Partials>
 where
     P: partials::PartialCompiler,
@@ -25,6 +139,17 @@ where
     blocks: compiler::PluginRegistry<Box<dyn compiler::ParseBlock>>,
     tags: compiler::PluginRegistry<Box<dyn compiler::ParseTag>>,
     filters: compiler::PluginRegistry<Box<dyn compiler::ParseFilter>>,
+    keyword_aliases: bool,
+    interpolation: bool,
+    defer_unknown_filters: bool,
+    count_tags: bool,
+    record_coverage: bool,
+    limits: compiler::ParseLimits,
+    limit_observer: Option<compiler::LimitObserver>,
+    compat_level: CompatLevel,
+    custom: CustomNames,
+    #[cfg(feature = "scripting")]
+    scripts: Option<path::PathBuf>,
     partials: Option<P>,
 }
 
@@ -38,6 +163,56 @@ impl ParserBuilder<Partials> {
     pub fn with_stdlib() -> Self {
         Self::new().stdlib()
     }
+
+    /// Create a parser with the tags and filters of Shopify's Liquid, without this crate's
+    /// additions.
+    ///
+    /// This is the standard library at `CompatLevel::V0_19`.
+    ///
+    /// ```rust
+    /// let parser = liquid::ParserBuilder::minimal().build().unwrap();
+    /// assert!(parser.parse("{{ 'a' | upcase }}").is_ok());
+    /// assert!(parser.parse("{{ 'a' | hex }}").is_err());
+    /// ```
+    #[cfg(feature = "stdlib")]
+    pub fn minimal() -> Self {
+        Self::with_stdlib().compat_level(CompatLevel::V0_19)
+    }
+
+    /// Create a parser for templates from untrusted authors, rendered as HTML.
+    ///
+    /// Templates are limited to 1 MiB, 10,000 elements and 32 levels of nesting.  Escaping is
+    /// chosen when rendering, see `RenderOptions::web_safe`.
+    ///
+    /// ```rust
+    /// let parser = liquid::ParserBuilder::web_safe().build().unwrap();
+    /// let template = parser.parse("<p>{{ name }}</p>").unwrap();
+    ///
+    /// let globals = liquid::object!({"name": "<b>Ann</b>"});
+    /// let options = liquid::RenderOptions::web_safe();
+    /// let output = template.render_with_options(&globals, &options).unwrap();
+    /// assert_eq!(output, "<p>&lt;b&gt;Ann&lt;/b&gt;</p>");
+    /// ```
+    #[cfg(feature = "stdlib")]
+    pub fn web_safe() -> Self {
+        Self::with_stdlib()
+            .max_template_size(1024 * 1024)
+            .max_nodes(10_000)
+            .max_nesting(32)
+            .count_tags(true)
+    }
+
+    /// Create a parser for static site generators, with the Jekyll dialect.
+    ///
+    /// ```rust
+    /// let parser = liquid::ParserBuilder::ssg().build().unwrap();
+    /// let template = parser.parse("{{ 'Hello World' | slugify }}").unwrap();
+    /// assert_eq!(template.render(&liquid::Object::new()).unwrap(), "hello-world");
+    /// ```
+    #[cfg(feature = "jekyll")]
+    pub fn ssg() -> Self {
+        Self::new().dialect(Dialect::Jekyll)
+    }
 }
 
 impl<P> ParserBuilder<P>
@@ -47,90 +222,355 @@ where
     #[cfg(feature = "stdlib")]
     /// Create a Liquid parser with built-in Liquid features
     pub fn stdlib(self) -> Self {
-        self.tag(stdlib::AssignTag)
-            .tag(stdlib::BreakTag)
-            .tag(stdlib::ContinueTag)
-            .tag(stdlib::CycleTag)
-            .tag(stdlib::IncludeTag)
-            .tag(stdlib::IncrementTag)
-            .tag(stdlib::DecrementTag)
-            .block(stdlib::RawBlock)
-            .block(stdlib::IfBlock)
-            .block(stdlib::UnlessBlock)
-            .block(stdlib::IfChangedBlock)
-            .block(stdlib::ForBlock)
-            .block(stdlib::TableRowBlock)
-            .block(stdlib::CommentBlock)
-            .block(stdlib::CaptureBlock)
-            .block(stdlib::CaseBlock)
-            .filter(stdlib::Append)
-            .filter(stdlib::AtLeast)
-            .filter(stdlib::AtMost)
-            .filter(stdlib::Capitalize)
-            .filter(stdlib::Ceil)
-            .filter(stdlib::Compact)
-            .filter(stdlib::Concat)
-            .filter(stdlib::Date)
-            .filter(stdlib::Default)
-            .filter(stdlib::DividedBy)
-            .filter(stdlib::Downcase)
-            .filter(stdlib::Escape)
-            .filter(stdlib::EscapeOnce)
-            .filter(stdlib::First)
-            .filter(stdlib::Floor)
-            .filter(stdlib::Join)
-            .filter(stdlib::Last)
-            .filter(stdlib::Lstrip)
-            .filter(stdlib::Map)
-            .filter(stdlib::Minus)
-            .filter(stdlib::Modulo)
-            .filter(stdlib::NewlineToBr)
-            .filter(stdlib::Plus)
-            .filter(stdlib::Prepend)
-            .filter(stdlib::Remove)
-            .filter(stdlib::RemoveFirst)
-            .filter(stdlib::Replace)
-            .filter(stdlib::ReplaceFirst)
-            .filter(stdlib::Reverse)
-            .filter(stdlib::Round)
-            .filter(stdlib::Rstrip)
-            .filter(stdlib::Size)
-            .filter(stdlib::Slice)
-            .filter(stdlib::Sort)
-            .filter(stdlib::SortNatural)
-            .filter(stdlib::Split)
-            .filter(stdlib::Strip)
-            .filter(stdlib::StripHtml)
-            .filter(stdlib::StripNewlines)
-            .filter(stdlib::Times)
-            .filter(stdlib::Truncate)
-            .filter(stdlib::TruncateWords)
-            .filter(stdlib::Uniq)
-            .filter(stdlib::Upcase)
-            .filter(stdlib::UrlDecode)
-            .filter(stdlib::UrlEncode)
+        let builder = self
+            .stdlib_tag(stdlib::AssignTag)
+            .stdlib_tag(stdlib::BreakTag)
+            .stdlib_tag(stdlib::CacheControlTag)
+            .stdlib_tag(stdlib::CallTag)
+            .stdlib_tag(stdlib::ContinueTag)
+            .stdlib_tag(stdlib::ConstantTag)
+            .stdlib_tag(stdlib::CycleTag)
+            .stdlib_tag(stdlib::IncludeTag)
+            .stdlib_tag(stdlib::IncrementTag)
+            .stdlib_tag(stdlib::DecrementTag)
+            .stdlib_block(stdlib::RawBlock)
+            .stdlib_block(stdlib::IfBlock)
+            .stdlib_block(stdlib::UnlessBlock)
+            .stdlib_block(stdlib::IfChangedBlock)
+            .stdlib_block(stdlib::ForBlock)
+            .stdlib_block(stdlib::TableRowBlock)
+            .stdlib_block(stdlib::CommentBlock)
+            .stdlib_block(stdlib::CaptureBlock)
+            .stdlib_block(stdlib::CaseBlock)
+            .stdlib_filter(stdlib::Append)
+            .stdlib_filter(stdlib::AtLeast)
+            .stdlib_filter(stdlib::AtMost)
+            .stdlib_filter(stdlib::Average)
+            .stdlib_filter(stdlib::Base64Encode)
+            .stdlib_filter(stdlib::Capitalize)
+            .stdlib_filter(stdlib::Ceil)
+            .stdlib_filter(stdlib::Compact)
+            .stdlib_filter(stdlib::Concat)
+            .stdlib_filter(stdlib::Date)
+            .stdlib_filter(stdlib::DateAdd)
+            .stdlib_filter(stdlib::DateDiff)
+            .stdlib_filter(stdlib::Default)
+            .stdlib_filter(stdlib::DividedBy)
+            .stdlib_filter(stdlib::Downcase)
+            .stdlib_filter(stdlib::Escape)
+            .stdlib_filter(stdlib::EscapeOnce)
+            .stdlib_filter(stdlib::First)
+            .stdlib_filter(stdlib::Flatten)
+            .stdlib_filter(stdlib::Floor)
+            .stdlib_filter(stdlib::Hex)
+            .stdlib_filter(stdlib::HumanizeDuration)
+            .stdlib_filter(stdlib::IndexOf)
+            .stdlib_filter(stdlib::Join)
+            .stdlib_filter(stdlib::Last)
+            .stdlib_filter(stdlib::LastIndexOf)
+            .stdlib_filter(stdlib::Lstrip)
+            .stdlib_filter(stdlib::Map)
+            .stdlib_filter(stdlib::MaxBy)
+            .stdlib_filter(stdlib::MinBy)
+            .stdlib_filter(stdlib::Minus)
+            .stdlib_filter(stdlib::Modulo)
+            .stdlib_filter(stdlib::NewlineToBr)
+            .stdlib_filter(stdlib::NumberFormat)
+            .stdlib_filter(stdlib::Plus)
+            .stdlib_filter(stdlib::Prepend)
+            .stdlib_filter(stdlib::Reject)
+            .stdlib_filter(stdlib::Remove)
+            .stdlib_filter(stdlib::RemoveFirst)
+            .stdlib_filter(stdlib::Replace)
+            .stdlib_filter(stdlib::ReplaceFirst)
+            .stdlib_filter(stdlib::Reverse)
+            .stdlib_filter(stdlib::Round)
+            .stdlib_filter(stdlib::Rstrip)
+            .stdlib_filter(stdlib::Size)
+            .stdlib_filter(stdlib::Slice)
+            .stdlib_filter(stdlib::Sort)
+            .stdlib_filter(stdlib::SortNatural)
+            .stdlib_filter(stdlib::Split)
+            .stdlib_filter(stdlib::Strip)
+            .stdlib_filter(stdlib::StripHtml)
+            .stdlib_filter(stdlib::StripNewlines)
+            .stdlib_filter(stdlib::Times)
+            .stdlib_filter(stdlib::Truncate)
+            .stdlib_filter(stdlib::TruncateWords)
+            .stdlib_filter(stdlib::Uniq)
+            .stdlib_filter(stdlib::Upcase)
+            .stdlib_filter(stdlib::UrlDecode)
+            .stdlib_filter(stdlib::UrlEncode)
+            .stdlib_filter(stdlib::Where);
+
+        #[cfg(feature = "time-zones")]
+        let builder = builder.stdlib_filter(stdlib::InTimeZone);
+
+        #[cfg(feature = "email")]
+        let builder = builder
+            .stdlib_filter(liquid_lib::email::MimeHeader)
+            .stdlib_filter(liquid_lib::email::QuotedPrintable);
+
+        #[cfg(feature = "inline-css")]
+        let builder = builder.stdlib_filter(liquid_lib::email::InlineCss);
+
+        #[cfg(feature = "qr")]
+        let builder = builder.stdlib_filter(liquid_lib::qr::QrCode);
+
+        #[cfg(feature = "emoji")]
+        let builder = builder.stdlib_filter(liquid_lib::emoji::Emojify);
+
+        builder
+    }
+
+    /// Register the standard library along with the tags and filters of `dialect`, replacing
+    /// standard ones where the platform's differ.
+    ///
+    /// ```
+    /// let parser = liquid::ParserBuilder::new()
+    ///     .dialect(liquid::Dialect::Liquid)
+    ///     .build().unwrap();
+    /// assert!(parser.parse("{{ 'a,b' | split: ',' | join: ' ' }}").is_ok());
+    /// ```
+    #[cfg(feature = "stdlib")]
+    pub fn dialect(self, dialect: Dialect) -> Self {
+        let builder = self.stdlib();
+        match dialect {
+            Dialect::Liquid => builder,
+            #[cfg(feature = "jekyll")]
+            Dialect::Jekyll => builder
+                .stdlib_tag(liquid_lib::jekyll::IncludeTag)
+                .stdlib_tag(liquid_lib::jekyll::TocTag)
+                .stdlib_filter(liquid_lib::jekyll::ArrayToSentenceString)
+                .stdlib_filter(liquid_lib::jekyll::Excerpt)
+                .stdlib_filter(liquid_lib::jekyll::HeadingAnchor)
+                .stdlib_filter(liquid_lib::jekyll::Pop)
+                .stdlib_filter(liquid_lib::jekyll::Push)
+                .stdlib_filter(liquid_lib::jekyll::ReadingTime)
+                .stdlib_filter(liquid_lib::jekyll::Shift)
+                .stdlib_filter(liquid_lib::jekyll::Slugify)
+                .stdlib_filter(liquid_lib::jekyll::Smartify)
+                .stdlib_filter(liquid_lib::jekyll::Unshift),
+            #[cfg(feature = "shopify")]
+            Dialect::Shopify => builder
+                .stdlib_filter(liquid_lib::shopify::Money)
+                .stdlib_filter(liquid_lib::shopify::Pluralize),
+        }
     }
 
     /// Inserts a new custom block into the parser
     pub fn block<B: Into<Box<dyn compiler::ParseBlock>>>(mut self, block: B) -> Self {
         let block = block.into();
-        self.blocks
-            .register(block.reflection().start_tag().to_owned(), block);
+        let name = block.reflection().start_tag().to_owned();
+        self.custom.blocks.insert(name.clone());
+        self.blocks.register(name, block);
         self
     }
 
     /// Inserts a new custom tag into the parser
     pub fn tag<T: Into<Box<dyn compiler::ParseTag>>>(mut self, tag: T) -> Self {
         let tag = tag.into();
-        self.tags.register(tag.reflection().tag().to_owned(), tag);
+        let name = tag.reflection().tag().to_owned();
+        self.custom.tags.insert(name.clone());
+        self.tags.register(name, tag);
         self
     }
 
     /// Inserts a new custom filter into the parser
     pub fn filter<F: Into<Box<dyn compiler::ParseFilter>>>(mut self, filter: F) -> Self {
         let filter = filter.into();
-        self.filters
-            .register(filter.reflection().name().to_owned(), filter);
+        let name = filter.reflection().name().to_owned();
+        self.custom.filters.insert(name.clone());
+        self.filters.register(name, filter);
+        self
+    }
+
+    #[cfg(feature = "stdlib")]
+    fn stdlib_block<B: Into<Box<dyn compiler::ParseBlock>>>(mut self, block: B) -> Self {
+        let block = block.into();
+        let name = block.reflection().start_tag().to_owned();
+        self.custom.blocks.remove(&name);
+        self.blocks.register(name, block);
+        self
+    }
+
+    #[cfg(feature = "stdlib")]
+    fn stdlib_tag<T: Into<Box<dyn compiler::ParseTag>>>(mut self, tag: T) -> Self {
+        let tag = tag.into();
+        let name = tag.reflection().tag().to_owned();
+        self.custom.tags.remove(&name);
+        self.tags.register(name, tag);
+        self
+    }
+
+    #[cfg(feature = "stdlib")]
+    fn stdlib_filter<F: Into<Box<dyn compiler::ParseFilter>>>(mut self, filter: F) -> Self {
+        let filter = filter.into();
+        let name = filter.reflection().name().to_owned();
+        self.custom.filters.remove(&name);
+        self.filters.register(name, filter);
+        self
+    }
+
+    /// Inserts a filter backed by a plain Rust function.
+    ///
+    /// The filter's input and its positional arguments, as a tuple, are converted to the
+    /// function's parameter types with serde, and its return value back into a `Value`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let parser = liquid::ParserBuilder::with_stdlib()
+    ///     .filter_fn("lookup_price", |sku: String, (currency,): (String,)| {
+    ///         Ok(format!("{} {}", sku.len() * 100, currency))
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let template = parser.parse("{{ 'ABC' | lookup_price: 'EUR' }}").unwrap();
+    /// let output = template.render(&liquid::Object::new()).unwrap();
+    /// assert_eq!(output, "300 EUR");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn filter_fn<N, F, I, A, R>(self, name: N, f: F) -> Self
+    where
+        N: Into<String>,
+        F: Fn(I, A) -> Result<R> + Send + Sync + 'static,
+        I: serde::de::DeserializeOwned,
+        A: serde::de::DeserializeOwned,
+        R: serde::Serialize,
+    {
+        self.filter(FnFilterParser::new(name.into(), f))
+    }
+
+    /// Accept `&&`, `||`, `eq`, `ne`, `lt`, `gt`, `le` and `ge` as aliases of the standard
+    /// operators.
+    ///
+    /// Disabled by default for parity with Shopify's Liquid.
+    pub fn keyword_aliases(mut self, yes: bool) -> Self {
+        self.keyword_aliases = yes;
+        self
+    }
+
+    /// Expand `#{value}` in double-quoted string literals, like `"Hi #{user.name}"`.
+    ///
+    /// Disabled by default, as templates may already contain such literals.
+    pub fn interpolation(mut self, yes: bool) -> Self {
+        self.interpolation = yes;
+        self
+    }
+
+    /// Accept filters that aren't registered, resolving them when rendering with
+    /// `RenderOptions::missing_filter_handler`.
+    ///
+    /// Rendering one without a handler fails, like for other render errors, see
+    /// `RenderOptions::lax`.
+    ///
+    /// ```
+    /// use liquid::ValueView;
+    ///
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .defer_unknown_filters(true)
+    ///     .build().unwrap()
+    ///     .parse("{{ 'hi' | shout: 3 }}").unwrap();
+    ///
+    /// let shout = liquid::MissingFilterHandler::new(|_name, input, args, _keyword| {
+    ///     let times = args[0].as_scalar().and_then(|n| n.to_integer()).unwrap_or(1);
+    ///     let loud = input.to_kstr().to_uppercase() + &"!".repeat(times as usize);
+    ///     Ok(liquid::value::Value::scalar(loud))
+    /// });
+    /// let options = liquid::RenderOptions::new().missing_filter_handler(std::sync::Arc::new(shout));
+    /// let output = template.render_with_options(&liquid::Object::new(), &options).unwrap();
+    /// assert_eq!(output, "HI!!!");
+    /// ```
+    pub fn defer_unknown_filters(mut self, yes: bool) -> Self {
+        self.defer_unknown_filters = yes;
+        self
+    }
+
+    /// Count the runs of each tag and block, so renders can limit them with
+    /// `RenderOptions::tag_limit`.
+    ///
+    /// Disabled by default, as it adds work to every run of a tag.
+    pub fn count_tags(mut self, yes: bool) -> Self {
+        self.count_tags = yes;
+        self
+    }
+
+    /// Record which tags, blocks and outputs run, so renders can report it with
+    /// `RenderOptions::coverage`.
+    ///
+    /// Disabled by default, as it adds work to every run of an element.
+    pub fn record_coverage(mut self, yes: bool) -> Self {
+        self.record_coverage = yes;
+        self
+    }
+
+    /// Behave like an earlier release, see `CompatLevel`.
+    ///
+    /// Defaults to `CompatLevel::Latest`.  Standard library plugins newer than `level` are
+    /// removed, and those that changed since are replaced, when the parser is built.
+    ///
+    /// ```
+    /// let parser = liquid::ParserBuilder::with_stdlib()
+    ///     .compat_level(liquid::CompatLevel::V0_19)
+    ///     .build().unwrap();
+    /// assert!(parser.parse("{{ items | flatten }}").is_err());
+    ///
+    /// let template = parser.parse("{{ 'a,b,,' | split: ',' | size }}").unwrap();
+    /// assert_eq!(template.render(&liquid::Object::new()).unwrap(), "4");
+    /// ```
+    pub fn compat_level(mut self, level: CompatLevel) -> Self {
+        self.compat_level = level;
+        self
+    }
+
+    /// Reject templates longer than `bytes`.
+    pub fn max_template_size(mut self, bytes: usize) -> Self {
+        self.limits.max_size = Some(bytes);
+        self
+    }
+
+    /// Reject templates with more than `nodes` text runs, outputs, tags and blocks.
+    pub fn max_nodes(mut self, nodes: usize) -> Self {
+        self.limits.max_nodes = Some(nodes);
+        self
+    }
+
+    /// Reject templates with blocks nested more than `depth` deep.
+    pub fn max_nesting(mut self, depth: usize) -> Self {
+        self.limits.max_nesting = Some(depth);
+        self
+    }
+
+    /// Report templates that reach `percent` of a limit to `observer`, while still accepting
+    /// them.
+    ///
+    /// This warns template owners before their templates start failing.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let warnings = Arc::new(Mutex::new(Vec::new()));
+    /// let seen = warnings.clone();
+    /// let parser = liquid::ParserBuilder::with_stdlib()
+    ///     .max_template_size(10)
+    ///     .warn_near_limits(80, move |warning| seen.lock().unwrap().push(warning.to_string()))
+    ///     .build().unwrap();
+    ///
+    /// parser.parse("Hello!").unwrap();
+    /// parser.parse("Hello you!").unwrap();
+    /// assert_eq!(
+    ///     *warnings.lock().unwrap(),
+    ///     vec!["Template is near parse limit `max_size` (10 of 10)".to_owned()]
+    /// );
+    /// ```
+    pub fn warn_near_limits<F>(mut self, percent: usize, observer: F) -> Self
+    where
+        F: Fn(&crate::LimitWarning) + Send + Sync + 'static,
+    {
+        self.limits.warn_percent = Some(percent);
+        self.limit_observer = Some(sync::Arc::new(observer));
         self
     }
 
@@ -140,35 +580,149 @@ where
             blocks,
             tags,
             filters,
+            keyword_aliases,
+            interpolation,
+            defer_unknown_filters,
+            count_tags,
+            record_coverage,
+            limits,
+            limit_observer,
+            compat_level,
+            custom,
+            #[cfg(feature = "scripting")]
+            scripts,
             partials: _partials,
         } = self;
         ParserBuilder {
             blocks,
             tags,
             filters,
+            keyword_aliases,
+            interpolation,
+            defer_unknown_filters,
+            count_tags,
+            record_coverage,
+            limits,
+            limit_observer,
+            compat_level,
+            custom,
+            #[cfg(feature = "scripting")]
+            scripts,
             partials: Some(partials),
         }
     }
 
+    /// Remove the standard library plugins newer than the `CompatLevel`, and restore the earlier
+    /// implementation of those that changed since.  Custom plugins are left alone.
+    #[cfg(feature = "stdlib")]
+    fn with_pinned_stdlib(mut self) -> Self {
+        let level = self.compat_level;
+        let custom = &self.custom;
+        let newer = |&&(_, since): &&(&str, CompatLevel)| level < since;
+        for &(name, _) in STDLIB_FILTERS_SINCE.iter().filter(newer) {
+            if !custom.filters.contains(name) {
+                self.filters.unregister(name);
+            }
+        }
+        for &(name, _) in STDLIB_TAGS_SINCE.iter().filter(newer) {
+            if !custom.tags.contains(name) {
+                self.tags.unregister(name);
+            }
+        }
+        if level < CompatLevel::V0_20 {
+            for filter in stdlib_filters_v0_19() {
+                let name = filter.reflection().name().to_owned();
+                if self.filters.get(&name).is_some() && !custom.filters.contains(&name) {
+                    self.filters.register(name, filter);
+                }
+            }
+        }
+        self
+    }
+
+    /// Load the filters and tags written in Rhai from `dir`, when the parser is built.
+    ///
+    /// Each `filters/<name>.rhai` is a filter defining `fn filter(input, args)`, with `args` the
+    /// filter's positional arguments.  Each `tags/<name>.rhai` is a tag defining `fn render(args)`,
+    /// with `args` the tag's space-separated values, and whose result is written out.  Scripts
+    /// replace plugins of the same name, and run at most a million operations per call.
+    ///
+    /// This lets filters be added without recompiling the application.
+    ///
+    /// ```rust,no_run
+    /// // scripts/filters/shout.rhai: fn filter(input, args) { input.to_upper() + "!" }
+    /// let parser = liquid::ParserBuilder::with_stdlib()
+    ///     .scripts("scripts")
+    ///     .build().unwrap();
+    /// let template = parser.parse("{{ 'hi' | shout }}").unwrap();
+    /// assert_eq!(template.render(&liquid::Object::new()).unwrap(), "HI!");
+    /// ```
+    #[cfg(feature = "scripting")]
+    pub fn scripts<D: Into<path::PathBuf>>(mut self, dir: D) -> Self {
+        self.scripts = Some(dir.into());
+        self
+    }
+
+    /// Register the plugins of the scripts directory, if any.
+    #[cfg(feature = "scripting")]
+    fn with_scripts(mut self) -> Result<Self> {
+        if let Some(dir) = self.scripts.take() {
+            let scripts = crate::script::Scripts::load(&dir)?;
+            for filter in scripts.filters {
+                self = self.filter(filter);
+            }
+            for tag in scripts.tags {
+                self = self.tag(tag);
+            }
+        }
+        Ok(self)
+    }
+
     /// Create a parser
     pub fn build(self) -> Result<Parser> {
+        #[cfg(feature = "stdlib")]
+        let builder = self.with_pinned_stdlib();
+        #[cfg(not(feature = "stdlib"))]
+        let builder = self;
+        #[cfg(feature = "scripting")]
+        let builder = builder.with_scripts()?;
         let Self {
             blocks,
             tags,
             filters,
+            keyword_aliases,
+            interpolation,
+            defer_unknown_filters,
+            count_tags,
+            record_coverage,
+            limits,
+            limit_observer,
+            compat_level,
             partials,
-        } = self;
+            ..
+        } = builder;
 
         let mut options = compiler::Language::empty();
         options.blocks = blocks;
         options.tags = tags;
         options.filters = filters;
+        options.keyword_aliases = keyword_aliases;
+        options.interpolation = interpolation;
+        options.defer_unknown_filters = defer_unknown_filters;
+        options.count_tags = count_tags;
+        options.record_coverage = record_coverage;
+        options.limits = limits;
+        options.limit_observer = limit_observer;
         let options = sync::Arc::new(options);
         let partials = partials
             .map(|p| p.compile(options.clone()))
             .map_or(Ok(None), |r| r.map(Some))?
             .map(|p| p.into());
-        let p = Parser { options, partials };
+        let p = Parser {
+            options,
+            partials,
+            compat_level,
+        };
         Ok(p)
     }
 }
@@ -182,6 +736,17 @@ where
             blocks: Default::default(),
             tags: Default::default(),
             filters: Default::default(),
+            keyword_aliases: false,
+            interpolation: false,
+            defer_unknown_filters: false,
+            count_tags: false,
+            record_coverage: false,
+            limits: Default::default(),
+            limit_observer: None,
+            compat_level: CompatLevel::Latest,
+            custom: Default::default(),
+            #[cfg(feature = "scripting")]
+            scripts: None,
             partials: Default::default(),
         }
     }
@@ -217,6 +782,7 @@ where
 pub struct Parser {
     options: sync::Arc<compiler::Language>,
     partials: Option<sync::Arc<dyn interpreter::PartialStore + Send + Sync>>,
+    compat_level: CompatLevel,
 }
 
 impl Parser {
@@ -240,13 +806,211 @@ impl Parser {
     /// ```
     ///
     pub fn parse(&self, text: &str) -> Result<Template> {
-        let template = compiler::parse(text, &self.options).map(interpreter::Template::new)?;
+        self.parse_cow(borrow::Cow::Borrowed(text))
+    }
+
+    /// Parses a liquid template read from `reader`.
+    ///
+    /// The source is read into memory once, and a template without liquid keeps it rather than
+    /// a copy.  Other templates need the source while they are parsed, so peak memory is about
+    /// the size of the source plus the size of the parsed template.
+    ///
+    /// With `ParserBuilder::max_template_size`, reading stops once the source is over the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    /// let template = parser.parse_reader("Hello {{ name }}".as_bytes()).unwrap();
+    ///
+    /// let globals = liquid::object!({"name": "Ann"});
+    /// assert_eq!(template.render(&globals).unwrap(), "Hello Ann");
+    /// ```
+    pub fn parse_reader<R: Read>(&self, mut reader: R) -> Result<Template> {
+        let max_size = self.options.limits.max_size;
+        let mut buf = Vec::new();
+        match max_size {
+            // One byte past the limit tells a source over it from one at it.
+            Some(max_size) => reader.take(max_size as u64 + 1).read_to_end(&mut buf),
+            None => reader.read_to_end(&mut buf),
+        }
+        .replace("Cannot read template")?;
+        if let Some(max_size) = max_size {
+            if max_size < buf.len() {
+                return Error::with_msg("Template exceeds parse limit")
+                    .context("limit", "max_size")
+                    .context("allowed", max_size.to_string())
+                    .into_err();
+            }
+        }
+        let text = String::from_utf8(buf).replace("Cannot read template")?;
+        self.parse_cow(borrow::Cow::Owned(text))
+    }
+
+    fn parse_cow(&self, text: borrow::Cow<'_, str>) -> Result<Template> {
+        if !compiler::contains_liquid(&text) {
+            self.options.limits.check(&text, &self.options)?;
+            return Ok(Template {
+                template: interpreter::Template::new(Vec::new()),
+                partials: self.partials.clone(),
+                text: Some(text.into_owned()),
+                counts_tags: self.options.count_tags,
+                records_coverage: self.options.record_coverage,
+            });
+        }
+        let template = compiler::parse(&text, &self.options).map(interpreter::Template::new)?;
         Ok(Template {
             template,
             partials: self.partials.clone(),
+            text: None,
+            counts_tags: self.options.count_tags,
+            records_coverage: self.options.record_coverage,
         })
     }
 
+    /// Parses a liquid template, checking that every variable it reads is a key of `T`.
+    ///
+    /// The check is by top-level name, catching typos before the template is rendered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use liquid::ObjectView;
+    ///
+    /// #[derive(liquid::ObjectView, liquid::ValueView, serde::Serialize, Debug)]
+    /// struct Page {
+    ///     title: String,
+    /// }
+    ///
+    /// let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    /// assert!(parser.parse_typed::<Page>("{{ titel }}").is_err());
+    ///
+    /// let template = parser.parse_typed::<Page>("{{ title | upcase }}").unwrap();
+    /// let page = Page { title: "Hello".to_owned() };
+    /// assert_eq!(template.render(&page).unwrap(), "HELLO");
+    /// ```
+    pub fn parse_typed<T: crate::ObjectKeys + crate::ObjectView>(
+        &self,
+        text: &str,
+    ) -> Result<TypedTemplate<T>> {
+        let template = self.parse(text)?;
+        let keys = T::object_keys();
+        let report = compiler::analyze(text, &self.options);
+        if let Some(unknown) = report
+            .variables()
+            .iter()
+            .find(|variable| !keys.contains(&variable.as_str()))
+        {
+            return Error::with_msg("Unknown variable")
+                .context("requested variable", unknown.clone())
+                .context("available variables", keys.join(", "))
+                .into_err();
+        }
+        Ok(TypedTemplate::new(template))
+    }
+
+    /// Parses a standalone expression, like the contents of `{{ }}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    /// let expression = parser.parse_expression("items | size | plus: 1").unwrap();
+    ///
+    /// let globals = liquid::object!({
+    ///     "items": [1, 2, 3],
+    /// });
+    /// let value = expression.evaluate(&globals).unwrap();
+    /// assert_eq!(value, liquid::value::Value::scalar(4));
+    /// ```
+    pub fn parse_expression(&self, text: &str) -> Result<Expression> {
+        let expression = compiler::parse_expression(text, &self.options)?;
+        Ok(Expression { expression })
+    }
+
+    /// Parses a standalone condition, using the syntax of `{% if %}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    /// let condition = parser.parse_condition("tags contains 'beta' or user.admin").unwrap();
+    ///
+    /// let globals = liquid::object!({
+    ///     "tags": ["beta"],
+    ///     "user": { "admin": false },
+    /// });
+    /// assert!(condition.matches(&globals).unwrap());
+    /// ```
+    #[cfg(feature = "stdlib")]
+    pub fn parse_condition(&self, text: &str) -> Result<Condition> {
+        let arguments = compiler::parse_tag_arguments(text)?;
+        let condition = stdlib::IfCondition::parse(arguments, &self.options)?;
+        Ok(Condition { condition })
+    }
+
+    /// Summarizes the structure of a liquid template, after checking that it parses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    /// let report = parser
+    ///     .report("{% for i in items %}{% if i %}{{ i | upcase }}{% endif %}{% endfor %}")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(report.max_nesting(), 2);
+    /// assert_eq!(report.filters()["upcase"], 1);
+    /// ```
+    pub fn report(&self, text: &str) -> Result<CompileReport> {
+        compiler::parse(text, &self.options)?;
+        Ok(compiler::analyze(text, &self.options))
+    }
+
+    /// Describe the effective configuration of the parser, like to log it at startup and catch
+    /// services whose engines drifted apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let parser = liquid::ParserBuilder::with_stdlib()
+    ///     .compat_level(liquid::CompatLevel::V0_19)
+    ///     .max_nesting(8)
+    ///     .build()
+    ///     .unwrap();
+    /// let description = parser.describe();
+    ///
+    /// assert_eq!(description.compat_level, liquid::CompatLevel::V0_19);
+    /// assert!(description.blocks.contains(&"for".to_owned()));
+    /// assert!(!description.filters.contains(&"flatten".to_owned()));
+    /// assert_eq!(description.max_nesting, Some(8));
+    /// ```
+    pub fn describe(&self) -> ParserDescription {
+        fn sorted<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+            let mut names: Vec<_> = names.map(|name| name.to_owned()).collect();
+            names.sort_unstable();
+            names
+        }
+
+        let limits = &self.options.limits;
+        ParserDescription {
+            compat_level: self.compat_level,
+            tags: sorted(self.options.tags.plugin_names()),
+            blocks: sorted(self.options.blocks.plugin_names()),
+            filters: sorted(self.options.filters.plugin_names()),
+            partials: sorted(self.partials.iter().flat_map(|p| p.names())),
+            keyword_aliases: self.options.keyword_aliases,
+            interpolation: self.options.interpolation,
+            defer_unknown_filters: self.options.defer_unknown_filters,
+            count_tags: self.options.count_tags,
+            record_coverage: self.options.record_coverage,
+            max_template_size: limits.max_size,
+            max_nodes: limits.max_nodes,
+            max_nesting: limits.max_nesting,
+            warn_percent: limits.warn_percent,
+        }
+    }
+
     /// Parse a liquid template from a file, returning a `Result<Template, Error>`.
     /// # Examples
     ///
@@ -281,13 +1045,15 @@ impl Parser {
             .replace("Cannot open file")
             .context_key("path")
             .value_with(|| file.to_string_lossy().into_owned().into())?;
-        let mut buf = String::new();
+        // Reserving the file's size avoids over-allocating while the buffer grows.
+        let size = f.metadata().map(|m| m.len() as usize).unwrap_or(0);
+        let mut buf = String::with_capacity(size);
         f.read_to_string(&mut buf)
             .replace("Cannot read file")
             .context_key("path")
             .value_with(|| file.to_string_lossy().into_owned().into())?;
 
-        self.parse(&buf)
+        self.parse_cow(borrow::Cow::Owned(buf))
     }
 }
 
@@ -0,0 +1,21 @@
+//! The types most templates need, for a glob import.
+//!
+//! ```rust
+//! use liquid::prelude::*;
+//!
+//! let template = ParserBuilder::with_stdlib()
+//!     .build()
+//!     .unwrap()
+//!     .parse("Hello {{ name }}")
+//!     .unwrap();
+//! let globals: Object = object!({"name": "Ann"});
+//! let output = template
+//!     .render_with_options(&globals, &RenderOptions::new())
+//!     .unwrap();
+//! assert_eq!(output, "Hello Ann");
+//! ```
+
+#[cfg(feature = "serde")]
+pub use crate::Globals;
+pub use crate::{object, Error, IntoValue, Object, ObjectView, ValueView};
+pub use crate::{Parser, ParserBuilder, RenderOptions, Template};
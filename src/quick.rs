@@ -0,0 +1,43 @@
+//! Render templates in one call, for scripts and tests.
+//!
+//! ```rust
+//! #[derive(serde::Serialize)]
+//! struct Order {
+//!     id: u32,
+//!     items: Vec<&'static str>,
+//! }
+//!
+//! let order = Order { id: 42, items: vec!["tea", "cake"] };
+//! let output = liquid::quick::render("#{{ id }}: {{ items | join: ', ' }}", &order).unwrap();
+//! assert_eq!(output, "#42: tea, cake");
+//! ```
+
+use liquid_core::error::{Error, Result};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::Parser;
+
+/// Parser with the standard library, shared by all quick renders.
+static PARSER: Lazy<Parser> = Lazy::new(|| {
+    crate::ParserBuilder::with_stdlib()
+        .build()
+        .expect("the standard library is valid")
+});
+
+/// The parser used by `render`, with the standard library.
+pub fn parser() -> &'static Parser {
+    &PARSER
+}
+
+/// Parse `template` and render it against `globals`.
+///
+/// `globals` may be anything serializing to a map, like a struct or a `serde_json::Value`
+/// object.
+pub fn render<T: Serialize>(template: &str, globals: &T) -> Result<String> {
+    let globals = crate::to_object(globals).map_err(|e| {
+        Error::with_msg("Globals must serialize to an object, like a struct or map")
+            .context("cause", e.message().to_owned())
+    })?;
+    PARSER.parse(template)?.render(&globals)
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path;
+use std::sync::Arc;
+
+use liquid_core::compiler::{
+    Filter, FilterArguments, FilterReflection, Language, ParameterReflection, ParseFilter,
+    ParseTag, TagReflection, TagTokenIter,
+};
+use liquid_core::error::{Error, Result, ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::interpreter::{Expression, Renderable, Runtime};
+use liquid_core::value::{Value, ValueView};
+
+/// Operations a script may run per call, so a runaway loop fails the render instead of hanging
+/// it.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Filters and tags loaded from a scripts directory, see `ParserBuilder::scripts`.
+pub(crate) struct Scripts {
+    pub(crate) filters: Vec<ScriptFilterParser>,
+    pub(crate) tags: Vec<ScriptTagParser>,
+}
+
+impl Scripts {
+    /// Compile the scripts of `dir/filters` and `dir/tags`, either of which may be missing.
+    pub(crate) fn load(dir: &path::Path) -> Result<Self> {
+        let mut engine = rhai::Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let engine = Arc::new(engine);
+
+        let filters = compile_dir(&engine, &dir.join("filters"))?
+            .into_iter()
+            .map(|(name, script)| ScriptFilterParser { name, script })
+            .collect();
+        let tags = compile_dir(&engine, &dir.join("tags"))?
+            .into_iter()
+            .map(|(name, script)| ScriptTagParser { name, script })
+            .collect();
+        Ok(Self { filters, tags })
+    }
+}
+
+/// Compile each `.rhai` file of `dir`, named by its file stem.
+fn compile_dir(engine: &Arc<rhai::Engine>, dir: &path::Path) -> Result<Vec<(String, Script)>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let path_context = |path: &path::Path| path.to_string_lossy().into_owned();
+
+    let entries = fs::read_dir(dir)
+        .replace("Cannot read scripts")
+        .context_key("path")
+        .value_with(|| path_context(dir).into())?;
+    let mut scripts = Vec::new();
+    for entry in entries {
+        let path = entry.replace("Cannot read scripts")?.path();
+        if path.extension().map_or(true, |ext| ext != "rhai") {
+            continue;
+        }
+        let name = match path.file_stem() {
+            Some(stem) => stem.to_string_lossy().into_owned(),
+            None => continue,
+        };
+        let source = fs::read_to_string(&path)
+            .replace("Cannot read file")
+            .context_key("path")
+            .value_with(|| path_context(&path).into())?;
+        let ast = engine.compile(&source).map_err(|e| {
+            Error::with_msg("Invalid script")
+                .context("path", path_context(&path))
+                .context("cause", e.to_string())
+        })?;
+        let script = Script {
+            engine: engine.clone(),
+            ast: Arc::new(ast),
+        };
+        scripts.push((name, script));
+    }
+    Ok(scripts)
+}
+
+/// A compiled script, shared by the plugins parsed from it.
+#[derive(Clone)]
+struct Script {
+    engine: Arc<rhai::Engine>,
+    ast: Arc<rhai::AST>,
+}
+
+impl Script {
+    /// Call the script's function `name` with `args` converted into script values.
+    fn call(&self, name: &str, args: Vec<Value>) -> Result<Value> {
+        let args = args
+            .iter()
+            .map(rhai::serde::to_dynamic)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| Error::with_msg("Invalid argument").context("cause", e.to_string()))?;
+        let mut scope = rhai::Scope::new();
+        let output: rhai::Dynamic = self
+            .engine
+            .call_fn(&mut scope, &self.ast, name, args)
+            .map_err(|e| Error::with_msg("Script failed").context("cause", e.to_string()))?;
+        rhai::serde::from_dynamic(&output)
+            .map_err(|e| Error::with_msg("Invalid script result").context("cause", e.to_string()))
+    }
+}
+
+fn evaluate_all(args: &[Expression], runtime: &Runtime<'_>) -> Result<Vec<Value>> {
+    args.iter()
+        .map(|arg| arg.evaluate(runtime).map(|v| v.into_owned()))
+        .collect()
+}
+
+/// Filter backed by a script's `filter(input, args)` function.
+#[derive(Clone)]
+pub(crate) struct ScriptFilterParser {
+    name: String,
+    script: Script,
+}
+
+impl FilterReflection for ScriptFilterParser {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        ""
+    }
+
+    fn positional_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+
+    fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+}
+
+impl ParseFilter for ScriptFilterParser {
+    fn parse(&self, arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+        let mut keyword = arguments.keyword;
+        if let Some((name, _)) = keyword.next() {
+            return Err(Error::with_msg("Unexpected named argument")
+                .context("argument", name.to_owned())
+                .context("filter", self.name.clone()));
+        }
+        let args = arguments.positional.collect();
+        Ok(Box::new(ScriptFilter {
+            name: self.name.clone(),
+            args,
+            script: self.script.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+struct ScriptFilter {
+    name: String,
+    args: Vec<Expression>,
+    script: Script,
+}
+
+impl Filter for ScriptFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &Runtime<'_>) -> Result<Value> {
+        let args = evaluate_all(&self.args, runtime)?;
+        self.script
+            .call("filter", vec![input.to_value(), Value::array(args)])
+    }
+}
+
+impl fmt::Debug for ScriptFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptFilter")
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+impl fmt::Display for ScriptFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{}", sep, arg)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tag backed by a script's `render(args)` function, whose result is written out.
+#[derive(Clone)]
+pub(crate) struct ScriptTagParser {
+    name: String,
+    script: Script,
+}
+
+impl TagReflection for ScriptTagParser {
+    fn tag(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        ""
+    }
+}
+
+impl ParseTag for ScriptTagParser {
+    fn parse(
+        &self,
+        arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let args = arguments
+            .map(|token| token.expect_value().into_result())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Box::new(ScriptTag {
+            name: self.name.clone(),
+            args,
+            script: self.script.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+struct ScriptTag {
+    name: String,
+    args: Vec<Expression>,
+    script: Script,
+}
+
+impl Renderable for ScriptTag {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &mut Runtime<'_>) -> Result<()> {
+        let args = evaluate_all(&self.args, runtime)?;
+        let output = self
+            .script
+            .call("render", vec![Value::array(args)])
+            .trace_with(|| format!("{{% {} %}}", self.name).into())?;
+        write!(writer, "{}", output.render()).replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ScriptTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptTag")
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn loads_filters_and_tags() {
+        let dir = std::env::temp_dir().join(format!("liquid-scripts-{}", std::process::id()));
+        fs::create_dir_all(dir.join("filters")).unwrap();
+        fs::create_dir_all(dir.join("tags")).unwrap();
+        fs::write(
+            dir.join("filters/repeat.rhai"),
+            "fn filter(input, args) { let s = \"\"; for i in 0..args[0] { s += input; } s }",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tags/greet.rhai"),
+            "fn render(args) { \"Hello \" + args[0] }",
+        )
+        .unwrap();
+
+        let parser = crate::ParserBuilder::with_stdlib()
+            .scripts(&dir)
+            .build()
+            .unwrap();
+        let template = parser
+            .parse("{{ 'ab' | repeat: 3 }} {% greet name %}")
+            .unwrap();
+        let globals = liquid_core::object!({"name": "Ann"});
+        assert_eq!(template.render(&globals).unwrap(), "ababab Hello Ann");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
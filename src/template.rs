@@ -1,19 +1,43 @@
 use std::io::Write;
+use std::marker::PhantomData;
 use std::sync;
 
-use liquid_core::error::Result;
+use liquid_core::error::{Error, Result, ResultLiquidReplaceExt};
 use liquid_core::interpreter;
 use liquid_core::interpreter::PartialStore;
 use liquid_core::interpreter::Renderable;
+use liquid_core::value;
 
 pub struct Template {
     pub(crate) template: interpreter::Template,
     pub(crate) partials: Option<sync::Arc<dyn PartialStore + Send + Sync>>,
+    /// Source of a template without any liquid, which renders as is.
+    pub(crate) text: Option<String>,
+    /// Whether the tags count their runs, see `ParserBuilder::count_tags`.
+    pub(crate) counts_tags: bool,
+    /// Whether the elements record their runs, see `ParserBuilder::record_coverage`.
+    pub(crate) records_coverage: bool,
 }
 
 impl Template {
+    /// Whether the template has no tags or outputs, always rendering its source as is.
+    ///
+    /// Such templates render without evaluating anything.
+    ///
+    /// ```rust
+    /// let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    /// assert!(parser.parse("Plain text").unwrap().is_static());
+    /// assert!(!parser.parse("Hello {{ name }}").unwrap().is_static());
+    /// ```
+    pub fn is_static(&self) -> bool {
+        self.text.is_some()
+    }
+
     /// Renders an instance of the Template, using the given globals.
     pub fn render(&self, globals: &dyn crate::ObjectView) -> Result<String> {
+        if let Some(ref text) = self.text {
+            return Ok(text.clone());
+        }
         const BEST_GUESS: usize = 10_000;
         let mut data = Vec::with_capacity(BEST_GUESS);
         self.render_to(&mut data, globals)?;
@@ -23,13 +47,656 @@ impl Template {
 
     /// Renders an instance of the Template, using the given globals.
     pub fn render_to(&self, writer: &mut dyn Write, globals: &dyn crate::ObjectView) -> Result<()> {
+        self.render_to_with_options(writer, globals, &RenderOptions::default())
+    }
+
+    /// Renders an instance of the Template, using the given globals and options.
+    pub fn render_with_options(
+        &self,
+        globals: &dyn crate::ObjectView,
+        options: &RenderOptions,
+    ) -> Result<String> {
+        const BEST_GUESS: usize = 10_000;
+        let mut data = Vec::with_capacity(BEST_GUESS);
+        self.render_to_with_options(&mut data, globals, options)?;
+
+        Ok(convert_buffer(data))
+    }
+
+    /// Renders an instance of the Template, using the given globals and options.
+    pub fn render_to_with_options(
+        &self,
+        writer: &mut dyn Write,
+        globals: &dyn crate::ObjectView,
+        options: &RenderOptions,
+    ) -> Result<()> {
+        if let Some(ref text) = self.text {
+            writer
+                .write_all(text.as_bytes())
+                .replace("Failed to render")?;
+            return Ok(());
+        }
+        self.render_runtime(writer, globals, options, false)?;
+        Ok(())
+    }
+
+    /// Renders the Template without output, reporting the paths it reads from the globals.
+    ///
+    /// This lets lazily-loaded globals be fetched in bulk before the real render.  Values copied
+    /// out of the globals, like loop items, are only reported by the path they were copied from.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{{ user.name }}{% for post in posts %}{{ post.title }}{% endfor %}").unwrap();
+    ///
+    /// let globals = liquid::object!({"user": {"name": "Ann"}, "posts": []});
+    /// let options = liquid::RenderOptions::new();
+    /// let paths = template.data_requirements(&globals, &options).unwrap();
+    /// let paths: Vec<_> = paths.iter().map(|p| p.to_string()).collect();
+    /// assert_eq!(paths, vec!["user.name", "posts"]);
+    /// ```
+    pub fn data_requirements(
+        &self,
+        globals: &dyn crate::ObjectView,
+        options: &RenderOptions,
+    ) -> Result<Vec<value::Path<'static>>> {
+        let runtime = self.render_runtime(&mut std::io::sink(), globals, options, true)?;
+        Ok(runtime.stack().accessed())
+    }
+
+    /// Renders an instance of the Template, collecting everything the render produced besides
+    /// the output, like the variables it assigned.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{% assign layout = 'post' %}{% cache_control max_age: 60 %}Hello").unwrap();
+    ///
+    /// let options = liquid::RenderOptions::new();
+    /// let result = template.render_with_result(&liquid::Object::new(), &options).unwrap();
+    /// assert_eq!(result.output(), "Hello");
+    /// assert_eq!(result.assigned(), &liquid::object!({"layout": "post"}));
+    /// assert!(result.metadata().contains_key("cache_control"));
+    /// ```
+    pub fn render_with_result(
+        &self,
+        globals: &dyn crate::ObjectView,
+        options: &RenderOptions,
+    ) -> Result<RenderResult> {
+        #[cfg(feature = "memory-profiling")]
+        let (result, allocations) = crate::memory::measure(|| self.render_result(globals, options));
+        #[cfg(not(feature = "memory-profiling"))]
+        let result = self.render_result(globals, options);
+        #[cfg(feature = "memory-profiling")]
+        let result = result.map(|result| RenderResult {
+            allocations,
+            ..result
+        });
+        result
+    }
+
+    fn render_result(
+        &self,
+        globals: &dyn crate::ObjectView,
+        options: &RenderOptions,
+    ) -> Result<RenderResult> {
+        const BEST_GUESS: usize = 10_000;
+        let mut data = Vec::with_capacity(BEST_GUESS);
+        let runtime = self.render_runtime(&mut data, globals, options, false)?;
+        let snapshot = runtime.stack().snapshot();
+
+        Ok(RenderResult {
+            output: convert_buffer(data),
+            assigned: snapshot.assigned().clone(),
+            warnings: runtime.stack().warnings(),
+            errors: runtime.render_errors().to_vec(),
+            slots: runtime.slots(),
+            metadata: runtime.metadata().clone(),
+            #[cfg(feature = "memory-profiling")]
+            allocations: None,
+        })
+    }
+
+    fn render_runtime<'g>(
+        &'g self,
+        writer: &mut dyn Write,
+        globals: &'g dyn crate::ObjectView,
+        options: &'g RenderOptions,
+        record_accesses: bool,
+    ) -> Result<interpreter::Runtime<'g>> {
+        if !options.tag_limits.is_empty() && !self.counts_tags && self.text.is_none() {
+            return Error::with_msg("Tag limits need a parser built with `count_tags`").into_err();
+        }
+        if options.coverage.is_some() && !self.records_coverage && self.text.is_none() {
+            return Error::with_msg("Coverage needs a parser built with `record_coverage`")
+                .into_err();
+        }
         let runtime = interpreter::RuntimeBuilder::new().set_globals(globals);
+        let runtime = if record_accesses {
+            runtime.record_accesses()
+        } else {
+            runtime
+        };
         let runtime = match self.partials {
             Some(ref partials) => runtime.set_partials(partials.as_ref()),
             None => runtime,
         };
+        let runtime = options.configure(runtime);
         let mut runtime = runtime.build();
-        self.template.render_to(writer, &mut runtime)
+        let cycle_policy = options.cycle_policy.unwrap_or(value::CyclePolicy::Error);
+        let mut writer = runtime.count_output(writer);
+        match self.text {
+            Some(ref text) => writer
+                .write_all(text.as_bytes())
+                .replace("Failed to render")?,
+            None => value::guard_cycles(cycle_policy, || {
+                self.template.render_to(&mut writer, &mut runtime)
+            })?,
+        }
+        Ok(runtime)
+    }
+}
+
+/// A `Template` checked against the type of globals it is rendered with.
+///
+/// Created with `Parser::parse_typed`.
+pub struct TypedTemplate<T> {
+    template: Template,
+    globals: PhantomData<fn(&T)>,
+}
+
+impl<T: crate::ObjectView> TypedTemplate<T> {
+    pub(crate) fn new(template: Template) -> Self {
+        Self {
+            template,
+            globals: PhantomData,
+        }
+    }
+
+    /// Renders an instance of the Template, using the given globals.
+    pub fn render(&self, globals: &T) -> Result<String> {
+        self.template.render(globals)
+    }
+
+    /// Renders an instance of the Template, using the given globals.
+    pub fn render_to(&self, writer: &mut dyn Write, globals: &T) -> Result<()> {
+        self.template.render_to(writer, globals)
+    }
+
+    /// Access the unchecked `Template`, like for rendering with options.
+    pub fn template(&self) -> &Template {
+        &self.template
+    }
+}
+
+/// Everything a render produced, see `Template::render_with_result`.
+///
+/// Accessors are added as templates gain ways to report to the application.
+#[derive(Clone, Debug)]
+pub struct RenderResult {
+    output: String,
+    assigned: value::Object,
+    warnings: Vec<String>,
+    errors: Vec<Error>,
+    slots: value::Object,
+    metadata: value::Object,
+    #[cfg(feature = "memory-profiling")]
+    allocations: Option<crate::AllocationStats>,
+}
+
+impl RenderResult {
+    /// The rendered template.
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    /// Top-level variables written by the template, like by `{% assign %}` and `{% capture %}`.
+    pub fn assigned(&self) -> &value::Object {
+        &self.assigned
+    }
+
+    /// Problems that didn't stop the render, like a variable only matching ignoring case.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Errors of the elements skipped by a lax render, see `RenderOptions::lax`.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Named content captured for the application to place, see `Runtime::slots`.
+    pub fn slots(&self) -> &value::Object {
+        &self.slots
+    }
+
+    /// Information for the application collected by tags, like the caching hints of
+    /// `{% cache_control %}`.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{% cache_control max_age: 300, vary: 'user.locale' %}Hello").unwrap();
+    ///
+    /// let options = liquid::RenderOptions::new();
+    /// let result = template.render_with_result(&liquid::Object::new(), &options).unwrap();
+    /// assert_eq!(result.output(), "Hello");
+    /// assert_eq!(
+    ///     result.metadata().get("cache_control").unwrap(),
+    ///     &liquid::value::value!({"max_age": 300, "vary": ["user.locale"]})
+    /// );
+    /// ```
+    pub fn metadata(&self) -> &value::Object {
+        &self.metadata
+    }
+
+    /// Allocations made by the render, when `CountingAllocator` is the global allocator.
+    #[cfg(feature = "memory-profiling")]
+    pub fn allocations(&self) -> Option<crate::AllocationStats> {
+        self.allocations
+    }
+
+    /// Split into the output and the assigned variables.
+    pub fn into_parts(self) -> (String, value::Object) {
+        (self.output, self.assigned)
+    }
+}
+
+/// Options for a single render of a `Template`.
+///
+/// ```rust
+/// let template = liquid::ParserBuilder::with_stdlib()
+///     .build().unwrap()
+///     .parse("{{ '2016-06-13' | date: '%d %B' }}").unwrap();
+///
+/// let options = liquid::RenderOptions::new().locale(liquid::Locale::new("de"));
+/// let output = template.render_with_options(&liquid::Object::new(), &options).unwrap();
+/// assert_eq!(output, "13 Juni");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RenderOptions {
+    aliases: Vec<(String, String)>,
+    case_insensitive_lookup: bool,
+    freeze_globals: bool,
+    undefined_policy: Option<interpreter::UndefinedPolicy>,
+    lax: bool,
+    tag_limits: Vec<(String, usize)>,
+    max_iterations: Option<usize>,
+    max_output_size: Option<usize>,
+    cycle_policy: Option<value::CyclePolicy>,
+    display_policy: Option<value::DisplayPolicy>,
+    date_formats: Option<value::DateFormats>,
+    loader: Option<sync::Arc<dyn interpreter::Loader>>,
+    functions: Option<sync::Arc<interpreter::FunctionRegistry>>,
+    fetcher: Option<sync::Arc<interpreter::FetchSandbox>>,
+    coverage: Option<sync::Arc<interpreter::Coverage>>,
+    missing_filters: Option<sync::Arc<interpreter::MissingFilterHandler>>,
+    output_encoder: Option<sync::Arc<dyn interpreter::OutputEncoder>>,
+    #[cfg(feature = "stdlib")]
+    locale: Option<liquid_lib::Locale>,
+}
+
+impl RenderOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create options for rendering HTML from untrusted templates, see `ParserBuilder::web_safe`.
+    ///
+    /// Outputs are HTML-escaped, templates can't assign to the globals, and each render runs
+    /// `include` at most 100 times, loops at most 100,000 iterations and writes at most 10 MiB.
+    pub fn web_safe() -> Self {
+        Self::new()
+            .output_encoder(sync::Arc::new(interpreter::HtmlEncoder))
+            .freeze_globals(true)
+            .tag_limit("include", 100)
+            .max_iterations(100_000)
+            .max_output_size(10 * 1024 * 1024)
+    }
+
+    /// Look up `to` when `from` is missing, like for a renamed variable.
+    ///
+    /// Paths are separated by `.`, like `user.name`.  Each alias used is reported as a warning,
+    /// see `Template::render_with_result`.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{{ author }}").unwrap();
+    ///
+    /// let globals = liquid::object!({"user": {"name": "Ann"}});
+    /// let options = liquid::RenderOptions::new().alias("author", "user.name");
+    /// let result = template.render_with_result(&globals, &options).unwrap();
+    /// assert_eq!(result.output(), "Ann");
+    /// assert_eq!(result.warnings(), ["Variable `author` is deprecated, use `user.name`"]);
+    /// ```
+    pub fn alias<S: Into<String>>(mut self, from: S, to: S) -> Self {
+        self.aliases.push((from.into(), to.into()));
+        self
+    }
+
+    /// Set whether variables and keys that are missing fall back to matching ignoring case.
+    ///
+    /// Each fallback is reported as a warning, see `Template::render_with_result`.
+    pub fn case_insensitive_lookup(mut self, enabled: bool) -> Self {
+        self.case_insensitive_lookup = enabled;
+        self
+    }
+
+    /// Set whether templates may assign to variables of the globals.
+    ///
+    /// When frozen, tags like `assign` and `capture` fail instead of replacing a global, so a
+    /// template can't clobber data provided by the application.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{% assign tenant = 'other' %}{{ tenant }}").unwrap();
+    ///
+    /// let globals = liquid::object!({"tenant": "acme"});
+    /// let options = liquid::RenderOptions::new().freeze_globals(true);
+    /// assert!(template.render_with_options(&globals, &options).is_err());
+    /// ```
+    pub fn freeze_globals(mut self, enabled: bool) -> Self {
+        self.freeze_globals = enabled;
+        self
+    }
+
+    /// Set how variables that are undefined are handled, instead of failing the render.
+    ///
+    /// Warnings of `UndefinedPolicy::Warn` are available through `Template::render_with_result`.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("[{{ missing.title }}]").unwrap();
+    ///
+    /// let options = liquid::RenderOptions::new().undefined_policy(liquid::UndefinedPolicy::Chain);
+    /// let output = template.render_with_options(&liquid::Object::new(), &options).unwrap();
+    /// assert_eq!(output, "[]");
+    /// ```
+    pub fn undefined_policy(mut self, policy: interpreter::UndefinedPolicy) -> Self {
+        self.undefined_policy = Some(policy);
+        self
+    }
+
+    /// Set whether to keep rendering after an element fails, skipping its output.
+    ///
+    /// The errors are available to the template as `render_errors`, each with a `message` and
+    /// the full `details`, unless a variable of that name is defined, and to the caller through
+    /// `Template::render_with_result`.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{{ missing }}Body{% for e in render_errors %} [{{ e.message }}]{% endfor %}")
+    ///     .unwrap();
+    ///
+    /// let options = liquid::RenderOptions::new().lax(true);
+    /// let result = template.render_with_result(&liquid::Object::new(), &options).unwrap();
+    /// assert_eq!(result.output(), "Body [Unknown variable]");
+    /// assert_eq!(result.errors().len(), 1);
+    /// ```
+    pub fn lax(mut self, enabled: bool) -> Self {
+        self.lax = enabled;
+        self
+    }
+
+    /// Fail the render once the tag or block `name` runs more than `max` times, like to allow at
+    /// most 50 `include`s.
+    ///
+    /// The error names the tag and the position of the run over the limit.  Rendering fails if
+    /// the template wasn't parsed with `ParserBuilder::count_tags`.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .count_tags(true)
+    ///     .build().unwrap()
+    ///     .parse("{% for i in (1..3) %}{% increment count %}{% endfor %}").unwrap();
+    ///
+    /// let options = liquid::RenderOptions::new().tag_limit("increment", 2);
+    /// let error = template.render_with_options(&liquid::Object::new(), &options).unwrap_err();
+    /// assert_eq!(error.message(), "Tag limit exceeded");
+    /// ```
+    pub fn tag_limit<S: Into<String>>(mut self, name: S, max: usize) -> Self {
+        self.tag_limits.push((name.into(), max));
+        self
+    }
+
+    /// Fail the render once loops, like `for` and `tablerow`, run more than `max` iterations in
+    /// total.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{% for i in (1..3) %}{% for j in (1..3) %}{% endfor %}{% endfor %}").unwrap();
+    ///
+    /// let options = liquid::RenderOptions::new().max_iterations(10);
+    /// let error = template.render_with_options(&liquid::Object::new(), &options).unwrap_err();
+    /// assert_eq!(error.message(), "Loop iteration limit exceeded");
+    /// ```
+    pub fn max_iterations(mut self, max: usize) -> Self {
+        self.max_iterations = Some(max);
+        self
+    }
+
+    /// Fail the render once more than `bytes` are written to the output.
+    pub fn max_output_size(mut self, bytes: usize) -> Self {
+        self.max_output_size = Some(bytes);
+        self
+    }
+
+    /// Set how shared values that contain themselves are rendered.
+    ///
+    /// Defaults to failing the render.
+    pub fn cycle_policy(mut self, policy: value::CyclePolicy) -> Self {
+        self.cycle_policy = Some(policy);
+        self
+    }
+
+    /// Set how scalars, like floats and dates, are formatted when output by `{{ }}`.
+    pub fn display_policy(mut self, policy: value::DisplayPolicy) -> Self {
+        self.display_policy = Some(policy);
+        self
+    }
+
+    /// Set the formats filters, like `date`, try when converting a string to a date.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{{ '13.06.2016' | date: '%F' }}").unwrap();
+    ///
+    /// let formats = liquid::DateFormats::new().format("%d.%m.%Y");
+    /// let options = liquid::RenderOptions::new().date_formats(formats);
+    /// let output = template.render_with_options(&liquid::Object::new(), &options).unwrap();
+    /// assert_eq!(output, "2016-06-13");
+    /// ```
+    pub fn date_formats(mut self, formats: value::DateFormats) -> Self {
+        self.date_formats = Some(formats);
+        self
+    }
+
+    /// Set the loader that lazy values register their fetches with.
+    ///
+    /// The loader is flushed at the start of every block, see `Loader`.
+    pub fn loader(mut self, loader: sync::Arc<dyn interpreter::Loader>) -> Self {
+        self.loader = Some(loader);
+        self
+    }
+
+    /// Set the host functions templates may call with `{% call %}`.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("{% call pricing.quote sku: 'ABC' into price %}{{ price }}").unwrap();
+    ///
+    /// let mut functions = liquid::FunctionRegistry::new();
+    /// functions.register("pricing.quote", |_args| Ok(liquid::value::Value::scalar(4.5)));
+    /// let options = liquid::RenderOptions::new().functions(std::sync::Arc::new(functions));
+    /// let output = template.render_with_options(&liquid::Object::new(), &options).unwrap();
+    /// assert_eq!(output, "4.5");
+    /// ```
+    pub fn functions(mut self, functions: sync::Arc<interpreter::FunctionRegistry>) -> Self {
+        self.functions = Some(functions);
+        self
+    }
+
+    /// Set the sandbox `{% fetch %}` requests URLs through.
+    ///
+    /// The `fetch` tag must also be registered with the parser, see `FetchTag` with the `fetch`
+    /// feature.
+    pub fn fetcher(mut self, fetcher: sync::Arc<interpreter::FetchSandbox>) -> Self {
+        self.fetcher = Some(fetcher);
+        self
+    }
+
+    /// Record the tags, blocks and outputs that run, to find template code no render reaches.
+    ///
+    /// Share one `Coverage` across the renders of a test suite, then compare it against the
+    /// templates with `CompileReport::uncovered`.  Rendering fails if the template wasn't parsed
+    /// with `ParserBuilder::record_coverage`.
+    ///
+    /// ```rust
+    /// let parser = liquid::ParserBuilder::with_stdlib()
+    ///     .record_coverage(true)
+    ///     .build().unwrap();
+    /// let text = "{% if admin %}{{ secret }}{% else %}Hi{% endif %}";
+    /// let template = parser.parse(text).unwrap();
+    ///
+    /// let coverage = std::sync::Arc::new(liquid::Coverage::new());
+    /// let options = liquid::RenderOptions::new().coverage(coverage.clone());
+    /// let globals = liquid::object!({"admin": false});
+    /// template.render_with_options(&globals, &options).unwrap();
+    ///
+    /// let report = parser.report(text).unwrap();
+    /// assert_eq!(
+    ///     report.uncovered(&coverage, ""),
+    ///     [("{{ secret }}".to_owned(), "1:15".to_owned())]
+    /// );
+    /// ```
+    pub fn coverage(mut self, coverage: sync::Arc<interpreter::Coverage>) -> Self {
+        self.coverage = Some(coverage);
+        self
+    }
+
+    /// Set the encoder escaping the output of `{{ }}` for the format being generated, like
+    /// `CsvFieldEncoder` or `LatexEncoder`.
+    ///
+    /// Outputs whose last filter already encoded them, like `escape` for `HtmlEncoder`, are
+    /// written as is, see `OutputEncoder::accepts`.  To output trusted markup, like a page's
+    /// `{{ content }}`, register a filter whose `Filter::output_encoding` is the encoder's.
+    ///
+    /// ```rust
+    /// let template = liquid::ParserBuilder::with_stdlib()
+    ///     .build().unwrap()
+    ///     .parse("<p>{{ name }}</p>").unwrap();
+    ///
+    /// let globals = liquid::object!({"name": "Tom & Jerry"});
+    /// let options = liquid::RenderOptions::new()
+    ///     .output_encoder(std::sync::Arc::new(liquid::HtmlEncoder));
+    /// let output = template.render_with_options(&globals, &options).unwrap();
+    /// assert_eq!(output, "<p>Tom &amp; Jerry</p>");
+    /// ```
+    pub fn output_encoder(mut self, encoder: sync::Arc<dyn interpreter::OutputEncoder>) -> Self {
+        self.output_encoder = Some(encoder);
+        self
+    }
+
+    /// Set the handler of filters the parser didn't know, see
+    /// `ParserBuilder::defer_unknown_filters`.
+    pub fn missing_filter_handler(
+        mut self,
+        handler: sync::Arc<interpreter::MissingFilterHandler>,
+    ) -> Self {
+        self.missing_filters = Some(handler);
+        self
+    }
+
+    /// Set the default locale for formatting filters, like `date` and `number_format`.
+    #[cfg(feature = "stdlib")]
+    pub fn locale(mut self, locale: liquid_lib::Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    fn configure<'g>(
+        &'g self,
+        runtime: interpreter::RuntimeBuilder<'g>,
+    ) -> interpreter::RuntimeBuilder<'g> {
+        let runtime = self.aliases.iter().fold(runtime, |runtime, (from, to)| {
+            runtime.set_alias(from.as_str(), to.as_str())
+        });
+        let runtime = if self.case_insensitive_lookup {
+            runtime.case_insensitive_lookup()
+        } else {
+            runtime
+        };
+        let runtime = if self.freeze_globals {
+            runtime.freeze_globals()
+        } else {
+            runtime
+        };
+        let runtime = match self.undefined_policy {
+            Some(policy) => runtime.set_undefined_policy(policy),
+            None => runtime,
+        };
+        let runtime = if self.lax { runtime.lax() } else { runtime };
+        let runtime = self
+            .tag_limits
+            .iter()
+            .fold(runtime, |runtime, (name, max)| {
+                runtime.set_tag_limit(name.as_str(), *max)
+            });
+        let runtime = match self.max_iterations {
+            Some(max) => runtime.set_max_iterations(max),
+            None => runtime,
+        };
+        let runtime = match self.max_output_size {
+            Some(bytes) => runtime.set_max_output(bytes),
+            None => runtime,
+        };
+        let runtime = match self.display_policy {
+            Some(ref policy) => runtime.set_register(policy.clone()),
+            None => runtime,
+        };
+        let runtime = match self.date_formats {
+            Some(ref formats) => runtime.set_register(formats.clone()),
+            None => runtime,
+        };
+        let runtime = match self.loader {
+            Some(ref loader) => runtime.set_loader(loader.as_ref()),
+            None => runtime,
+        };
+        let runtime = match self.functions {
+            Some(ref functions) => runtime.set_functions(functions.as_ref()),
+            None => runtime,
+        };
+        let runtime = match self.fetcher {
+            Some(ref fetcher) => runtime.set_fetcher(fetcher.as_ref()),
+            None => runtime,
+        };
+        let runtime = match self.coverage {
+            Some(ref coverage) => runtime.set_coverage(coverage.as_ref()),
+            None => runtime,
+        };
+        let runtime = match self.missing_filters {
+            Some(ref handler) => runtime.set_missing_filter_handler(handler.as_ref()),
+            None => runtime,
+        };
+        let runtime = match self.output_encoder {
+            Some(ref encoder) => runtime.set_output_encoder(encoder.as_ref()),
+            None => runtime,
+        };
+        #[cfg(feature = "stdlib")]
+        let runtime = match self.locale {
+            Some(ref locale) => runtime.set_register(locale.clone()),
+            None => runtime,
+        };
+        runtime
     }
 }
 
@@ -116,3 +116,91 @@ fn test_borrow_object() {
     assert_eq!(uut.contains_key("s"), true);
     assert!(uut.get("s").is_some());
 }
+
+#[test]
+fn test_object_keys() {
+    use liquid::ObjectKeys;
+
+    assert!(TestEmpty::object_keys().is_empty());
+    assert_eq!(
+        TestStatic::object_keys(),
+        &[
+            "boolean",
+            "int",
+            "float",
+            "static_str",
+            "string",
+            "kstring",
+            "array"
+        ]
+    );
+}
+
+mod parse_typed {
+    #[derive(liquid::ObjectView, liquid::ValueView, Debug)]
+    struct Order {
+        total: f64,
+    }
+
+    fn parser() -> liquid::Parser {
+        liquid::ParserBuilder::with_stdlib().build().unwrap()
+    }
+
+    #[test]
+    fn test_constant_is_bound() {
+        let template = parser()
+            .parse_typed::<Order>("{% constant TAX = 0.5 %}{{ total | times: TAX }}")
+            .unwrap();
+        assert_eq!(template.render(&Order { total: 3.0 }).unwrap(), "1.5");
+    }
+
+    #[test]
+    fn test_call_into_is_bound() {
+        let parser = parser();
+        parser
+            .parse_typed::<Order>("{% call tax.rate amount: total into rate %}{{ rate }}")
+            .unwrap();
+        assert!(parser
+            .parse_typed::<Order>("{% call tax.rate amount: subtotal into rate %}")
+            .is_err());
+    }
+
+    #[test]
+    fn test_fetch_into_is_bound() {
+        let parser = liquid::ParserBuilder::with_stdlib()
+            .tag(liquid::FetchTag::new())
+            .build()
+            .unwrap();
+        parser
+            .parse_typed::<Order>(
+                "{% fetch 'https://example.com/rates' into rates %}{{ rates.eur }}",
+            )
+            .unwrap();
+    }
+}
+
+mod without_serde {
+    #[derive(liquid::ObjectView, liquid::ValueView, Debug)]
+    struct Page {
+        title: String,
+        views: i32,
+    }
+
+    #[test]
+    fn test_render_without_serde() {
+        let page = Page {
+            title: "Home".to_owned(),
+            views: 3,
+        };
+        let template = liquid::ParserBuilder::with_stdlib()
+            .build()
+            .unwrap()
+            .parse("{{ title }} ({{ views }})")
+            .unwrap();
+        assert_eq!(template.render(&page).unwrap(), "Home (3)");
+        assert_eq!(
+            liquid::ValueView::to_value(&page),
+            liquid::value::value!({"title": "Home", "views": 3})
+        );
+    }
+}
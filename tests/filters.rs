@@ -525,6 +525,27 @@ pub fn default_pass() {
     assert_eq!(output, "foo".to_string());
 }
 
+#[test]
+pub fn default_undefined() {
+    let text = "{{ text | default: 'bar' }}";
+    let globals = liquid::Object::new();
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "bar".to_string());
+
+    let text = "{{ text | upcase | default: 'bar' }}";
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    assert!(template.render(&globals).is_err());
+}
+
 #[test]
 fn test_compact() {
     let text = "{{hashes | compact: 'a' | map: 'a' | join}}";
@@ -541,3 +562,83 @@ fn test_compact() {
     let output = template.render(&globals).unwrap();
     assert_eq!(output, "A C".to_string());
 }
+
+#[test]
+pub fn index_of() {
+    let text = "{{ list | index_of: \"b\" }},{{ list | last_index_of: \"a\" }}";
+    let globals = liquid::object!({
+        "list": ["a", "b", "a"],
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "1,2".to_string());
+}
+
+#[test]
+pub fn negative_index() {
+    let text = "{{ list[-1] }},{{ list[-3] }},{{ list[i] }}";
+    let globals = liquid::object!({
+        "list": ["a", "b", "c"],
+        "i": -2,
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "c,a,b".to_string());
+}
+
+#[test]
+pub fn slice_graphemes() {
+    let text = "{{ text | slice: 0, 2 }}|{{ text | slice: 0, 2, graphemes: true }}";
+    let globals = liquid::object!({
+        "text": "e\u{301}ab",
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "e\u{301}|e\u{301}a".to_string());
+}
+
+#[test]
+pub fn filter_fn() {
+    fn lookup_price(sku: String, (currency, quantity): (String, Option<i32>)) -> f64 {
+        let unit = if sku == "ABC" { 2.5 } else { 1.0 };
+        let total = unit * f64::from(quantity.unwrap_or(1));
+        if currency == "EUR" {
+            total * 2.0
+        } else {
+            total
+        }
+    }
+
+    let text = "{{ sku | lookup_price: 'EUR', 4 }},{{ sku | lookup_price: 'USD', nil }}";
+    let globals = liquid::object!({
+        "sku": "ABC",
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .filter_fn("lookup_price", |sku, args| Ok(lookup_price(sku, args)))
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "20,2.5".to_string());
+
+    let template = liquid::ParserBuilder::with_stdlib()
+        .filter_fn("lookup_price", |sku, args| Ok(lookup_price(sku, args)))
+        .build()
+        .unwrap()
+        .parse("{{ 5 | lookup_price: 'EUR', 1 }}")
+        .unwrap();
+    assert!(template.render(&globals).is_err());
+}
@@ -69,3 +69,638 @@ topic1-bar\
 ",
     );
 }
+
+#[test]
+pub fn multi_line_tags() {
+    compare(
+        "{%-
+\tassign greeting =
+\t\t\"hello\"
+\t\t| append: \" world\"
+\t\t| upcase
+-%}
+{%
+  if greeting
+    and true
+    or false
+%}
+{{
+  greeting
+  | prepend: \">\"
+}}
+{%- endif %}",
+        "
+>HELLO WORLD",
+    );
+}
+
+#[test]
+pub fn multi_line_tags_with_comments() {
+    compare(
+        "{% assign total = 1 # start from one
+     | plus: 2 # add the extras
+%}{{ total # print it }}{% if total > 2 # big enough? %}!{% endif %}",
+        "3!",
+    );
+}
+
+#[test]
+pub fn multi_line_tags_crlf() {
+    compare(
+        "{%\r\n  assign foo =\r\n    \"bar\"\r\n%}{{\r\n  foo\r\n}}",
+        "bar",
+    );
+}
+
+#[test]
+pub fn date_components() {
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ deadline.year }}-Q{{ deadline.quarter }} W{{ deadline.iso_week }} {{ deadline.hour }}")
+        .unwrap();
+
+    let deadline = value::DateTime::from_str("2016-06-13 02:30:00 +0300").unwrap();
+    let mut globals = Object::new();
+    globals.insert("deadline".into(), value::Value::scalar(deadline));
+    let output = template.render(&globals).unwrap();
+
+    assert_eq!(output, "2016-Q2 W24 2");
+}
+
+#[test]
+fn compile_report() {
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    let report = parser
+        .report(
+            "Hi {{ name | upcase | append: '!' }}\n\
+             {% for p in posts %}{% if p.draft %}{% include 'draft' %}{% else %}{% include \"post\" %}{% endif %}{% endfor %}\n\
+             {% raw %}{% if x %}{{ y | upcase }}{% endraw %}\
+             {% comment %}{% comment %}{% include 'hidden' %}{% endcomment %}{% endcomment %}\
+             {% assign title = page.title | downcase %}{% include 'post' %}",
+        )
+        .unwrap();
+
+    assert_eq!(report.text_nodes(), 3);
+    assert_eq!(report.output_nodes(), 1);
+    assert_eq!(report.tags()["for"], 1);
+    assert_eq!(report.tags()["if"], 1);
+    assert_eq!(report.tags()["else"], 1);
+    assert_eq!(report.tags()["include"], 3);
+    assert_eq!(report.tags()["raw"], 1);
+    assert_eq!(report.tags()["comment"], 1);
+    assert_eq!(report.nodes(), 13);
+    assert_eq!(report.max_nesting(), 2);
+    assert_eq!(report.partial_fanout(), 2);
+    assert_eq!(report.partials()["post"], 2);
+    assert_eq!(report.filters()["upcase"], 1);
+    assert_eq!(report.filters()["append"], 1);
+    assert_eq!(report.filters()["downcase"], 1);
+
+    assert!(parser.report("{% if x %}").is_err());
+}
+
+#[test]
+fn parse_limits() {
+    let parser = ParserBuilder::with_stdlib()
+        .max_template_size(64)
+        .max_nodes(4)
+        .max_nesting(2)
+        .build()
+        .unwrap();
+
+    assert!(parser
+        .parse("{% if a %}{% if b %}{{ c }}{% endif %}{% endif %}")
+        .is_ok());
+    assert!(parser
+        .parse("{% if a %}{% if b %}{% if c %}{% endif %}{% endif %}{% endif %}")
+        .is_err());
+    assert!(parser.parse("{{ a }} {{ b }} {{ c }}").is_err());
+    assert!(parser.parse(&"x".repeat(65)).is_err());
+}
+
+#[test]
+fn parse_limit_warnings() {
+    let warnings = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = warnings.clone();
+    let parser = ParserBuilder::with_stdlib()
+        .max_template_size(100)
+        .max_nodes(5)
+        .warn_near_limits(80, move |warning| {
+            seen.lock().unwrap().push((warning.limit, warning.actual))
+        })
+        .build()
+        .unwrap();
+
+    assert!(parser.parse("{{ a }} {{ b }}").is_ok());
+    assert!(warnings.lock().unwrap().is_empty());
+    assert!(parser.parse("{{ a }} {{ b }} {{ c }}").is_ok());
+    assert_eq!(*warnings.lock().unwrap(), vec![("max_nodes", 5)]);
+    assert!(parser.parse("{{ a }} {{ b }} {{ c }} {{ d }}").is_err());
+    assert_eq!(warnings.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn bytes_must_be_encoded() {
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    let mut globals = Object::new();
+    globals.insert("signature".into(), value::Value::scalar(vec![0xcau8, 0xfe]));
+
+    let template = parser
+        .parse("{{ signature | hex }} {{ signature | base64_encode }}")
+        .unwrap();
+    assert_eq!(template.render(&globals).unwrap(), "cafe yv4=");
+
+    let template = parser.parse("{{ signature }}").unwrap();
+    assert!(template.render(&globals).is_err());
+}
+
+#[test]
+fn display_policy() {
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ 1.0 }} {{ 2.5 | times: 2 }} {{ true }} {{ false }} {{ '2020-01-02' | date: '%F' }} {{ list }}")
+        .unwrap();
+    let globals = object!({ "list": [1.5, false] });
+
+    assert_eq!(
+        template.render(&globals).unwrap(),
+        "1 5 true false 2020-01-02 1.5false"
+    );
+
+    let policy = DisplayPolicy::new()
+        .float_precision(1)
+        .bool_text("yes", "no");
+    let options = RenderOptions::new().display_policy(policy);
+    assert_eq!(
+        template.render_with_options(&globals, &options).unwrap(),
+        "1.0 5.0 yes no 2020-01-02 1.5no"
+    );
+}
+
+#[test]
+fn render_with_result() {
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{% assign layout = 'post' %}{% capture excerpt %}{{ title }}!{% endcapture %}{% for i in (1..2) %}{% assign last = i %}{% endfor %}Body")
+        .unwrap();
+    let globals = object!({ "title": "Hello" });
+
+    let result = template
+        .render_with_result(&globals, &RenderOptions::new())
+        .unwrap();
+    assert_eq!(result.output(), "Body");
+    assert_eq!(
+        result.assigned(),
+        &object!({ "layout": "post", "excerpt": "Hello!", "last": 2 })
+    );
+    assert!(result.assigned().get("title").is_none());
+}
+
+#[test]
+fn loader_is_flushed_per_block() {
+    #[derive(Debug, Default)]
+    struct CountingLoader(std::sync::atomic::AtomicUsize);
+
+    impl Loader for CountingLoader {
+        fn flush(&self) -> Result<(), Error> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{% for i in (1..3) %}{% if i > 1 %}{{ i }}{% endif %}{% endfor %}")
+        .unwrap();
+    let loader = std::sync::Arc::new(CountingLoader::default());
+    let options = RenderOptions::new().loader(loader.clone());
+
+    let output = template
+        .render_with_options(&Object::new(), &options)
+        .unwrap();
+    assert_eq!(output, "23");
+    // The document, each iteration, and each `if` that passed.
+    assert_eq!(loader.0.load(std::sync::atomic::Ordering::SeqCst), 6);
+}
+
+#[test]
+fn template_builder() {
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    let is_admin = Condition::compare(
+        Expression::variable("user", &["role"]),
+        "==",
+        Expression::literal(value::Value::scalar("admin")),
+    )
+    .unwrap();
+    let template = TemplateBuilder::new()
+        .text("Hello ")
+        .output(parser.parse_expression("user.name | upcase").unwrap())
+        .if_block(
+            is_admin,
+            TemplateBuilder::new().text(" (admin)"),
+            Some(TemplateBuilder::new().text(" (guest)")),
+        )
+        .build();
+
+    let globals = object!({ "user": { "name": "ann", "role": "admin" } });
+    assert_eq!(template.render(&globals).unwrap(), "Hello ANN (admin)");
+
+    assert!(Condition::compare(
+        Expression::variable("a", &[]),
+        "=~",
+        Expression::variable("b", &[])
+    )
+    .is_err());
+    assert!(TemplateBuilder::new()
+        .for_block(
+            "i",
+            parser.parse_expression("items | sort").unwrap(),
+            TemplateBuilder::new(),
+            None,
+        )
+        .is_err());
+}
+
+#[test]
+fn compile_report_variables() {
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    let report = parser
+        .report(
+            "{% assign total = cart.total | plus: shipping %}{{ total }}\
+             {% for item in cart.items limit: max reversed %}{{ item.name }}{{ forloop.index }}{% endfor %}\
+             {% if user and tags contains 'a' %}{{ labels[key] }}{% endif %}\
+             {% capture body %}{{ page }}{% endcapture %}{{ body }}{% comment %}{{ hidden }}{% endcomment %}",
+        )
+        .unwrap();
+    let variables: Vec<_> = report.variables().iter().map(|v| v.as_str()).collect();
+    assert_eq!(
+        variables,
+        vec!["cart", "key", "labels", "max", "page", "shipping", "tags", "user"]
+    );
+}
+
+#[test]
+fn compile_report_unused_assigns() {
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    let report = parser
+        .report(
+            "{% assign title = page.title %}{% assign unused = 1 %}{{ title }}\n\
+             {% capture footer %}Bye{% endcapture %}{% assign count = count | plus: 1 %}",
+        )
+        .unwrap();
+    assert_eq!(
+        report.unused_assigns(),
+        [
+            ("unused".to_owned(), "1:42".to_owned()),
+            ("footer".to_owned(), "2:12".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn coverage_by_template() {
+    let mut partials = Partials::empty();
+    partials.add("footer", "{% if year %}{{ year }}{% endif %}");
+    let parser = ParserBuilder::with_stdlib()
+        .record_coverage(true)
+        .partials(partials)
+        .build()
+        .unwrap();
+    let text = "{% for item in items %}{{ item }}{% endfor %}{% include 'footer' %}";
+    let template = parser.parse(text).unwrap();
+
+    let coverage = std::sync::Arc::new(Coverage::new());
+    let options = RenderOptions::new().coverage(coverage.clone());
+    template
+        .render_with_options(&object!({ "items": [] }), &options)
+        .unwrap();
+
+    assert_eq!(coverage.templates(), ["", "footer"]);
+    assert_eq!(
+        parser.report(text).unwrap().uncovered(&coverage, ""),
+        [("{{ item }}".to_owned(), "1:24".to_owned())]
+    );
+    let footer = parser.report("{% if year %}{{ year }}{% endif %}").unwrap();
+    assert_eq!(
+        footer.uncovered(&coverage, "footer"),
+        [("{{ year }}".to_owned(), "1:14".to_owned())]
+    );
+}
+
+#[test]
+fn case_insensitive_lookup() {
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ user.name }}{% if USER.Admin %}!{% endif %}")
+        .unwrap();
+    let globals = object!({ "User": { "Name": "Ann", "admin": true } });
+
+    assert!(template.render(&globals).is_err());
+
+    let options = RenderOptions::new().case_insensitive_lookup(true);
+    let result = template.render_with_result(&globals, &options).unwrap();
+    assert_eq!(result.output(), "Ann!");
+    assert_eq!(result.warnings().len(), 4);
+}
+
+#[test]
+fn compat_level() {
+    let parse = |level, text| {
+        ParserBuilder::with_stdlib()
+            .compat_level(level)
+            .build()
+            .unwrap()
+            .parse(text)
+            .map(|_| ())
+    };
+
+    assert!(parse(CompatLevel::V0_19, "{{ 'a' | upcase }}").is_ok());
+    assert!(parse(CompatLevel::V0_19, "{% constant x = 1 %}").is_err());
+    assert!(parse(CompatLevel::V0_19, "{{ items | where: 'a' }}").is_err());
+    assert!(parse(CompatLevel::V0_20, "{% constant x = 1 %}").is_ok());
+    assert!(parse(CompatLevel::Latest, "{{ items | where: 'a' }}").is_ok());
+}
+
+#[test]
+fn compat_level_pins_behavior() {
+    let render = |level, text: &str| {
+        ParserBuilder::with_stdlib()
+            .compat_level(level)
+            .build()
+            .unwrap()
+            .parse(text)
+            .unwrap()
+            .render(&Object::new())
+            .unwrap()
+    };
+
+    let text = "{{ 'a,b,,' | split: ',' | size }},{{ 'été' | size }},{{ 0 | date: '%Y' }}";
+    assert_eq!(render(CompatLevel::V0_19, text), "4,5,0");
+    assert_eq!(render(CompatLevel::V0_20, text), "2,3,1970");
+}
+
+#[test]
+fn compat_level_keeps_custom_plugins() {
+    let parser = ParserBuilder::with_stdlib()
+        .filter_fn("where", |input: String, _: Vec<String>| Ok(input))
+        .filter_fn("size", |_: String, _: Vec<String>| Ok(-1))
+        .compat_level(CompatLevel::V0_19)
+        .build()
+        .unwrap();
+
+    let template = parser
+        .parse("{{ 'a' | where }},{{ 'abc' | size }}")
+        .unwrap();
+    assert_eq!(template.render(&Object::new()).unwrap(), "a,-1");
+}
+
+#[test]
+fn lax_render_errors() {
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(
+            "{% for i in (1..2) %}[{{ nope[i] }}{{ i }}]{% endfor %}\
+             {% for e in render_errors %}{{ forloop.index }}: {{ e.message }};{% endfor %}",
+        )
+        .unwrap();
+
+    assert!(template.render(&Object::new()).is_err());
+
+    let options = RenderOptions::new().lax(true);
+    let result = template
+        .render_with_result(&Object::new(), &options)
+        .unwrap();
+    assert_eq!(
+        result.output(),
+        "[1][2]1: Unknown variable;2: Unknown variable;"
+    );
+    assert_eq!(result.errors().len(), 2);
+    assert!(result.assigned().is_empty());
+
+    // The template's own `render_errors` is kept.
+    let globals = liquid::object!({"render_errors": [{"message": "Mine"}]});
+    let result = template.render_with_result(&globals, &options).unwrap();
+    assert_eq!(result.output(), "[1][2]1: Mine;");
+    assert_eq!(result.errors().len(), 2);
+}
+
+#[test]
+fn tag_limits() {
+    let mut partials = partials::InMemorySource::new();
+    partials.add("item", "{{ i }}");
+    let template = ParserBuilder::with_stdlib()
+        .count_tags(true)
+        .partials(partials::EagerCompiler::new(partials))
+        .build()
+        .unwrap()
+        .parse("{% for i in (1..3) %}\n  {% include 'item' %}{% endfor %}")
+        .unwrap();
+
+    let options = RenderOptions::new().tag_limit("include", 3);
+    let output = template
+        .render_with_options(&Object::new(), &options)
+        .unwrap();
+    assert_eq!(output, "\n  1\n  2\n  3");
+
+    let options = RenderOptions::new().tag_limit("include", 2);
+    let error = template
+        .render_with_options(&Object::new(), &options)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Tag limit exceeded"));
+    assert!(error.contains("tag=include"));
+    assert!(error.contains("position=2:6"));
+
+    let options = RenderOptions::new().tag_limit("for", 0);
+    assert!(template
+        .render_with_options(&Object::new(), &options)
+        .is_err());
+
+    // Limits can't be enforced on tags that don't count their runs.
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{% increment i %}")
+        .unwrap();
+    let options = RenderOptions::new().tag_limit("include", 1);
+    assert!(template
+        .render_with_options(&Object::new(), &options)
+        .is_err());
+}
+
+#[test]
+fn render_limits() {
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(
+            "{% for i in (1..3) %}{% tablerow j in (1..2) %}{{ j }}{% endtablerow %}{% endfor %}",
+        )
+        .unwrap();
+
+    let options = RenderOptions::new().max_iterations(9);
+    assert!(template
+        .render_with_options(&Object::new(), &options)
+        .is_ok());
+    let options = RenderOptions::new().max_iterations(8);
+    let error = template
+        .render_with_options(&Object::new(), &options)
+        .unwrap_err();
+    assert_eq!(error.message(), "Loop iteration limit exceeded");
+
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{% for i in (1..10) %}0123456789{% endfor %}")
+        .unwrap();
+    let options = RenderOptions::new().max_output_size(100);
+    assert!(template
+        .render_with_options(&Object::new(), &options)
+        .is_ok());
+    let options = RenderOptions::new().max_output_size(99);
+    assert!(template
+        .render_with_options(&Object::new(), &options)
+        .is_err());
+}
+
+#[test]
+fn parse_error_position() {
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    let error = match parser.parse("Hello\n{{ name | }}") {
+        Ok(_) => panic!("expected a parse error"),
+        Err(error) => error,
+    };
+    assert_eq!(error.context_value("position"), Some("2:11"));
+    assert_eq!(error.context_value("missing"), None);
+}
+
+#[test]
+fn static_templates() {
+    let parser = ParserBuilder::with_stdlib()
+        .max_template_size(32)
+        .build()
+        .unwrap();
+    let template = parser.parse("Plain text with { braces }").unwrap();
+    assert!(template.is_static());
+    assert_eq!(
+        template.render(&Object::new()).unwrap(),
+        "Plain text with { braces }"
+    );
+    let result = template
+        .render_with_result(&Object::new(), &RenderOptions::new())
+        .unwrap();
+    assert_eq!(result.output(), "Plain text with { braces }");
+
+    assert!(!parser
+        .parse("{% raw %}text{% endraw %}")
+        .unwrap()
+        .is_static());
+    assert!(!parser.parse("{{- x -}}").unwrap().is_static());
+    assert!(parser.parse(&"x".repeat(33)).is_err());
+}
+
+#[test]
+fn parse_reader() {
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    let template = parser.parse_reader(&b"Plain text"[..]).unwrap();
+    assert!(template.is_static());
+    assert_eq!(template.render(&Object::new()).unwrap(), "Plain text");
+
+    assert!(parser.parse_reader(&[0xffu8, 0xfe][..]).is_err());
+
+    let parser = ParserBuilder::with_stdlib()
+        .max_template_size(4)
+        .build()
+        .unwrap();
+    assert!(parser.parse_reader(&b"text"[..]).is_ok());
+    let error = match parser.parse_reader(std::io::repeat(b'x')) {
+        Ok(_) => panic!("expected the limit to stop reading"),
+        Err(error) => error,
+    };
+    assert_eq!(error.message(), "Template exceeds parse limit");
+}
+
+#[test]
+fn output_encoder() {
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ name }},{{ note }},\"{{ count }}\"")
+        .unwrap();
+    let globals = object!({ "name": "Ann", "note": "says \"hi\", often", "count": 2 });
+
+    let options = RenderOptions::new().output_encoder(std::sync::Arc::new(liquid::CsvFieldEncoder));
+    assert_eq!(
+        template.render_with_options(&globals, &options).unwrap(),
+        "Ann,\"says \"\"hi\"\", often\",\"2\""
+    );
+
+    let options = RenderOptions::new()
+        .display_policy(DisplayPolicy::new().float_precision(1))
+        .output_encoder(std::sync::Arc::new(liquid::LatexEncoder));
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ price }}$ for {{ item }}")
+        .unwrap();
+    let globals = object!({ "price": 5, "item": "50% off" });
+    assert_eq!(
+        template.render_with_options(&globals, &options).unwrap(),
+        "5$ for 50\\% off"
+    );
+}
+
+#[derive(Clone, liquid_core::ParseFilter, liquid_core::FilterReflection)]
+#[filter(
+    name = "markup",
+    description = "Marks trusted HTML as already encoded.",
+    parsed(MarkupFilter)
+)]
+struct Markup;
+
+#[derive(Debug, Default, liquid_core::Display_filter)]
+#[name = "markup"]
+struct MarkupFilter;
+
+impl liquid_core::Filter for MarkupFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &liquid_core::Runtime<'_>,
+    ) -> liquid_core::Result<liquid_core::Value> {
+        Ok(input.to_value())
+    }
+
+    fn output_encoding(&self) -> Option<&'static str> {
+        Some("html")
+    }
+}
+
+#[test]
+fn output_encoder_already_encoded() {
+    let template = ParserBuilder::with_stdlib()
+        .filter(Markup)
+        .build()
+        .unwrap()
+        .parse("{{ x | escape }} {{ x | escape_once }} {{ x | escape | upcase }} {{ content | markup }}")
+        .unwrap();
+    let globals = object!({ "x": "<b>&amp;", "content": "<p>Hi</p>" });
+
+    let options = RenderOptions::new().output_encoder(std::sync::Arc::new(liquid::HtmlEncoder));
+    assert_eq!(
+        template.render_with_options(&globals, &options).unwrap(),
+        "&lt;b&gt;&amp;amp; &lt;b&gt;&amp; &amp;LT;B&amp;GT;&amp;AMP;AMP; <p>Hi</p>"
+    );
+
+    let options = RenderOptions::new().output_encoder(std::sync::Arc::new(liquid::LatexEncoder));
+    assert_eq!(
+        template.render_with_options(&globals, &options).unwrap(),
+        "\\&lt;b\\&gt;\\&amp;amp; \\&lt;b\\&gt;\\&amp; \\&LT;B\\&GT;\\&AMP;AMP; <p>Hi</p>"
+    );
+}